@@ -2,12 +2,13 @@
 #![cfg_attr(not(any(feature = "export-abi", test)), no_main)]
 extern crate alloc;
 
-// Modules and imports
-mod erc20;
-
-use crate::erc20::{Erc20, Erc20Error, Erc20Params};
+// Imports
 use alloy_primitives::{Address, U256};
-use stylus_sdk::{msg, prelude::*};
+use stylus_sdk::{
+    msg,
+    prelude::*,
+    tokens::{Erc20, Erc20Error, Erc20Params},
+};
 
 /// Immutable definitions
 struct StylusTestTokenParams;
@@ -34,19 +35,19 @@ sol_storage! {
 impl StylusTestToken {
     /// Mints tokens
     pub fn mint(&mut self, value: U256) -> Result<(), Erc20Error> {
-        self.erc20.mint(msg::sender(), value)?;
+        self.erc20._mint(msg::sender(), value)?;
         Ok(())
     }
 
     /// Mints tokens to another address
     pub fn mint_to(&mut self, to: Address, value: U256) -> Result<(), Erc20Error> {
-        self.erc20.mint(to, value)?;
+        self.erc20._mint(to, value)?;
         Ok(())
     }
 
     /// Burns tokens
     pub fn burn(&mut self, value: U256) -> Result<(), Erc20Error> {
-        self.erc20.burn(msg::sender(), value)?;
+        self.erc20._burn(msg::sender(), value)?;
         Ok(())
     }
 }
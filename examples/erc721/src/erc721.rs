@@ -378,3 +378,83 @@ impl<T: Erc721Params> Erc721<T> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{abi, function_selector, test_host};
+
+    struct Params;
+
+    impl Erc721Params for Params {
+        const NAME: &'static str = "Test";
+        const SYMBOL: &'static str = "TEST";
+
+        fn token_uri(_token_id: U256) -> String {
+            String::new()
+        }
+    }
+
+    unsafe impl TopLevelStorage for Erc721<Params> {}
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+    const RECEIVER: Address = Address::repeat_byte(0xc0);
+
+    /// Encodes the `bytes4` a receiver's hook returns.
+    fn hook_return(selector: u32) -> Vec<u8> {
+        let mut word = selector.to_be_bytes().to_vec();
+        word.resize(32, 0);
+        word
+    }
+
+    /// Whether the receiver refused the token, returning `returned`.
+    fn refused(result: Result<(), Erc721Error>, returned: u32) -> bool {
+        matches!(
+            result,
+            Err(Erc721Error::ReceiverRefused(err))
+                if err.receiver == RECEIVER && err.returned == FixedBytes(returned.to_be_bytes())
+        )
+    }
+
+    #[test]
+    fn test_receiver_hook() {
+        let mut nft = unsafe { Erc721::<Params>::new(U256::ZERO, 0) };
+        for _ in 0..4 {
+            assert!(nft.mint(ALICE).is_ok());
+        }
+        test_host::set_sender(ALICE);
+        test_host::set_code(RECEIVER, b"receiver");
+
+        // receivers accept by returning the hook's selector
+        test_host::mock_call(RECEIVER, Ok(hook_return(ERC721_TOKEN_RECEIVER_ID)));
+        let data = Bytes(b"hi".to_vec());
+        let result =
+            Erc721::safe_transfer_from_with_data(&mut nft, ALICE, RECEIVER, U256::ZERO, data);
+        assert!(result.is_ok());
+        let (to, calldata, _) = test_host::calls().pop().unwrap();
+        assert_eq!(to, RECEIVER);
+        let selector = function_selector!("onERC721Received", Address, Address, U256, Bytes);
+        let args = (ALICE, ALICE, U256::ZERO, Bytes(b"hi".to_vec()));
+        assert_eq!(calldata, abi::encode_call(selector, &args));
+        assert_eq!(u32::from_be_bytes(selector), ERC721_TOKEN_RECEIVER_ID);
+        assert!(matches!(nft.owner_of(U256::ZERO), Ok(owner) if owner == RECEIVER));
+
+        // returning anything else refuses the token
+        test_host::mock_call(RECEIVER, Ok(hook_return(0xdeadbeef)));
+        let result = Erc721::safe_transfer_from(&mut nft, ALICE, RECEIVER, U256::from(1));
+        assert!(refused(result, 0xdeadbeef));
+
+        // as does reverting
+        test_host::mock_call(RECEIVER, Err(b"no thanks".to_vec()));
+        let result = Erc721::safe_transfer_from(&mut nft, ALICE, RECEIVER, U256::from(2));
+        assert!(refused(result, 0));
+
+        // accounts without code aren't called
+        let calls = test_host::calls().len();
+        let result = Erc721::safe_transfer_from(&mut nft, ALICE, BOB, U256::from(3));
+        assert!(result.is_ok());
+        assert_eq!(test_host::calls().len(), calls);
+        assert!(matches!(nft.owner_of(U256::from(3)), Ok(owner) if owner == BOB));
+    }
+}
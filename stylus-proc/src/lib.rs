@@ -57,10 +57,141 @@ mod types;
 /// One may even implement [`StorageType`] to define custom storage entries, though this is rarely necessary
 /// since the [Stylus SDK][sdk] intends to include all standard Solidity types out-of-the-box.
 ///
+/// # Storage layout
+///
+/// Fields are laid out exactly as Solidity lays out [state variables][layout], so a contract
+/// migrating from Solidity keeps the same slots. Consecutive fields smaller than 32 bytes, like
+/// [`StorageBool`], [`StorageAddress`], and the smaller integers, are packed into a shared slot
+/// in declaration order, starting from its lowest-order bytes. A field that doesn't fit in the
+/// remaining space starts a new slot. There's no opt-in required: a `(bool, address, uint8)` trio
+/// always occupies a single slot.
+///
+/// Nested structs, including those exposed to [`#[public]`][public] inheritance through
+/// `#[borrow]`, always start a new slot, and the field following them does too. This matches
+/// Solidity's treatment of struct members, but not of contract inheritance, where a parent's
+/// variables are packed with the child's. To share a layout with an inheriting Solidity
+/// contract, declare the parent's fields directly.
+///
+/// # Explicit slots
+///
+/// A field marked `#[slot(...)]` lives at the given slot instead, as required by standards like
+/// [EIP-1967] for proxies and useful for keeping state in place across upgrades. The slot is
+/// absolute, regardless of where the struct itself is placed, and must be a constant [`U256`]
+/// expression. Such fields don't affect the layout of the others.
+///
+/// ```ignore
+/// const ADMIN_SLOT: U256 =
+///     uint!(0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103_U256);
+///
+/// #[storage]
+/// pub struct Proxy {
+///     version: StorageU64,
+///     #[slot(ADMIN_SLOT)]
+///     admin: StorageAddress,
+/// }
+/// ```
+///
+/// Constructing the struct fails to compile if explicit slots overlap each other or, assuming the
+/// struct starts at slot zero as top-level contracts do, the slots of its other fields.
+///
+/// Since the slots don't move with the struct, every instance of it would share them. Such structs
+/// can be nested in others, but collections like [`StorageVec`] of them fail to compile, as do
+/// structs with two fields of the same such type. See [`StorageType::FIXED_SLOTS`].
+///
+/// # Namespaced storage
+///
+/// Upgradeable contracts can avoid collisions between the layouts of different versions, or of
+/// different components, by placing each struct at a slot derived from a namespace, as specified
+/// by [ERC-7201]. The struct's fields are then laid out from that slot, which is exposed as the
+/// `NAMESPACE_SLOT` associated constant.
+///
+/// ```ignore
+/// #[storage(namespace = "example.main")]
+/// pub struct Main {
+///     x: StorageU256,
+///     y: StorageU256,
+/// }
+/// ```
+///
+/// This is equivalent to the following Solidity annotation.
+///
+/// ```solidity
+/// /// @custom:storage-location erc7201:example.main
+/// struct MainStorage {
+///     uint256 x;
+///     uint256 y;
+/// }
+/// ```
+///
+/// Like structs with explicit slots, a namespaced struct is at the same slot wherever it's placed,
+/// so it may only be held once: in a contract, or in a struct the contract holds. Collections of
+/// it, and structs with two fields of it, fail to compile.
+///
+/// # Default values
+///
+/// Storage starts out zeroed. Fields marked `#[default(...)]` are given a value by the generated
+/// `init_defaults` method, which contracts call when initializing. It applies the defaults only
+/// the first time it's called, recording that it has at a slot derived from where the struct is
+/// placed, and returns whether it did. Defaults are supported for simple fields that hold a single
+/// value, like [`StorageBool`] and the integers.
+///
+/// ```ignore
+/// #[storage]
+/// #[entrypoint]
+/// pub struct Exchange {
+///     #[default(U256::from(30))]
+///     fee_bips: StorageU256,
+///     #[default(true)]
+///     trading: StorageBool,
+/// }
+///
+/// #[public]
+/// impl Exchange {
+///     pub fn init(&mut self) -> Result<(), Vec<u8>> {
+///         match self.init_defaults() {
+///             true => Ok(()),
+///             false => Err(b"already initialized".to_vec()),
+///         }
+///     }
+/// }
+/// ```
+///
+/// Nested structs with defaults of their own must have their `init_defaults` called separately.
+///
+/// # Borrowing fields separately
+///
+/// Methods that take `&mut self` borrow the whole struct, which prevents holding an accessor
+/// into one field while modifying another. For structs with named fields, `#[storage(split)]`
+/// generates a `split_mut` method returning mutable borrows of every field at once, as a struct
+/// named after the original with a `SplitMut` suffix. These are plain borrows of separate fields,
+/// and don't themselves keep the fields' slots apart. That's left to the layout, as for borrows of
+/// the fields made directly.
+///
+/// ```ignore
+/// #[storage(split)]
+/// pub struct Kennel {
+///     names: StorageVec<StorageString>,
+///     ages: StorageMap<U256, StorageU8>,
+/// }
+///
+/// let KennelSplitMut { names, ages } = self.split_mut();
+/// let mut name = names.grow();
+/// let mut age = ages.setter(id);
+/// ```
+///
 /// Please refer to the [SDK Feature Overview][overview] for more information on defining storage.
 ///
 /// [storage]: macro@storage
+/// [public]: macro@public
+/// [layout]: https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html
+/// [EIP-1967]: https://eips.ethereum.org/EIPS/eip-1967
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+/// [`U256`]: https://docs.rs/alloy-primitives/latest/alloy_primitives/aliases/type.U256.html
+/// [`StorageBool`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageBool.html
+/// [`StorageAddress`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageAddress.html
 /// [`StorageType`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.StorageType.html
+/// [`StorageType::FIXED_SLOTS`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.StorageType.html#associatedconstant.FIXED_SLOTS
+/// [`StorageVec`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageVec.html
 /// [overview]: https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#storage
 /// [sdk]: https://docs.rs/stylus-sdk/latest/stylus_sdk/index.html
 #[proc_macro_attribute]
@@ -152,7 +283,7 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 /// ```ignore
 /// pub fn do_call(account: IService, user: Address) -> Result<String, Error> {
 ///     let config = Call::new()
-///         .gas(evm::gas_left() / 2)       // limit to half the gas left
+///         .gas_fraction(1, 2)             // limit to half the gas left
 ///         .value(msg::value());           // set the callvalue
 ///
 ///     account.make_payment(config, user)  // note the snake case
@@ -162,6 +293,15 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 /// Observe the casing change. [`sol_interface!`] computes the selector based on the exact name passed in,
 /// which should almost always be `CamelCase`. For aesthetics, the rust functions will instead use `snake_case`.
 ///
+/// Calls to `pure` and `view` methods are made in a static context. For these, prefer
+/// `Call::new_static()`, which can't be given value or passed to methods that might modify state.
+///
+/// ```ignore
+/// pub fn get_constant(account: IService) -> Result<B256, Error> {
+///     account.get_constant(Call::new_static().gas_fraction(1, 2))
+/// }
+/// ```
+///
 /// # Reentrant calls
 ///
 /// Contracts that opt into reentrancy via the `reentrant` feature flag require extra care.
@@ -217,7 +357,7 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 /// ) -> Result<String, Error> {
 ///
 ///     let config = Call::new_in(storage)
-///         .gas(evm::gas_left() / 2)        // limit to half the gas left
+///         .gas_fraction(1, 2)              // limit to half the gas left
 ///         .value(msg::value());            // set the callvalue
 ///
 ///     account.make_payment(config, user)   // note the snake case
@@ -228,6 +368,23 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 /// distinguish the method as being `write` or `payable`. This means you can write library code that will
 /// work regardless of whether the `reentrant` feature flag is enabled.
 ///
+/// # Handling reverts
+///
+/// Each method returns a `Result` whose `Ok` value is the decoded return value. If the callee
+/// reverts, the `Err` is a [`call::Error::Revert`][Revert] holding its revert data, which
+/// [`decode_revert`] decodes as one of the callee's errors. This works for errors declared with
+/// `sol!` and enums deriving [`SolidityError`].
+///
+/// ```ignore
+/// match token.transfer(&mut *self, to, amount) {
+///     Ok(_) => Ok(()),
+///     Err(err) => match err.decode_revert() {
+///         Some(Erc20Error::InsufficientBalance(_)) => Err(b"token balance too low".to_vec()),
+///         _ => Err(err.into()),   // bubble up the original revert
+///     },
+/// }
+/// ```
+///
 /// [sol_interface]: macro@sol_interface
 /// [public]: macro@public
 /// [`TopLevelStorage`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.TopLevelStorage.html
@@ -237,18 +394,25 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 /// [`Address`]: https://docs.rs/alloy-primitives/latest/alloy_primitives/struct.Address.html
 /// [`B256`]: https://docs.rs/alloy-primitives/latest/alloy_primitives/aliases/type.B256.html
 /// [`Call`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/struct.Call.html
+/// [Revert]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/enum.Error.html#variant.Revert
+/// [`decode_revert`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/enum.Error.html#method.decode_revert
+/// [`SolidityError`]: macro@SolidityError
 #[proc_macro]
 pub fn sol_interface(input: TokenStream) -> TokenStream {
     calls::sol_interface(input)
 }
 
 /// Some [`StorageType`] values implement [`Erase`], which provides an [`erase()`] method for clearing state.
-/// [The Stylus SDK][sdk] implements [`Erase`] for all primitives, and for vectors of primitives, but not for maps.
+/// [The Stylus SDK][sdk] implements [`Erase`] for all primitives, and for vectors of erasable elements, but not for maps.
 /// This is because a Solidity mapping does not provide iteration, and so it's generally impossible to
 /// know which slots to clear.
 ///
 /// Structs may also be [`Erase`] if all of the fields are. `#[derive(Erase)]`
-/// lets you do this automatically.
+/// lets you do this automatically, and reports any field that can't be erased at compile time.
+/// Vectors of such structs can then be erased too, which erases each element before resetting the length.
+/// Erasing a struct also lets the defaults of its `#[default]` fields be applied again.
+///
+/// Structs with maps can derive [`StorageReset`](macro@StorageReset) instead.
 ///
 /// ```ignore
 /// sol_storage! {
@@ -256,6 +420,13 @@ pub fn sol_interface(input: TokenStream) -> TokenStream {
 ///    pub struct Contract {
 ///        address owner;              // can erase primitive
 ///        uint256[] hashes;           // can erase vector of primitive
+///        Dog[] dogs;                 // can erase vector of erasable struct
+///    }
+///
+///    #[derive(Erase)]
+///    pub struct Dog {
+///        string name;
+///        uint64 age;
 ///    }
 ///
 ///    pub struct NotErase {
@@ -281,6 +452,71 @@ pub fn derive_erase(input: TokenStream) -> TokenStream {
     storage::derive_erase(input)
 }
 
+/// Implements [`StorageReset`] for a struct, which erases every field but maps, since those can't
+/// be erased. Unlike [`Erase`](macro@Erase), this works for structs that have maps, or that nest
+/// structs with maps, as long as the nested structs implement [`StorageReset`] too. Fields that are
+/// [`Erase`][erase] are erased, and the documentation of the implementation lists the maps left in
+/// place.
+///
+/// ```ignore
+/// sol_storage! {
+///    #[derive(StorageReset)]
+///    pub struct Ledger {
+///        uint256 total;                      // erased
+///        mapping(address => uint) balances;  // left in place
+///    }
+///
+///    #[derive(StorageReset)]
+///    pub struct Vault {
+///        #[borrow]
+///        Ledger ledger;                      // reset
+///        address owner;                      // erased
+///    }
+/// }
+/// ```
+///
+/// Resetting a struct also lets the defaults of its `#[default]` fields be applied again.
+///
+/// [`StorageReset`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.StorageReset.html
+/// [erase]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.Erase.html
+#[proc_macro_derive(StorageReset)]
+pub fn derive_storage_reset(input: TokenStream) -> TokenStream {
+    storage::derive_storage_reset(input)
+}
+
+/// Allows a fieldless `enum` to be stored and passed around as a Solidity `uint8`, like a Solidity enum.
+///
+/// ```ignore
+/// #[derive(StorageEnum, Clone, Copy)]
+/// pub enum AuctionState {
+///     Open,
+///     Closed,
+///     Settled,
+/// }
+///
+/// #[storage]
+/// pub struct Auction {
+///     state: StorageEnumU8<AuctionState>,
+/// }
+/// ```
+///
+/// Variants are numbered from zero in declaration order, so they can't have fields or explicit
+/// discriminants. The derive implements [`StorageEnum`], which [`StorageEnumU8`] uses to store the
+/// enum in a single byte, as well as [`AbiType`] so that the enum may appear in method signatures.
+///
+/// Reading a discriminant that doesn't correspond to a variant returns an error, which reverts
+/// with Solidity's `Panic(0x21)` when returned from a method. Likewise, calldata with an out-of-range
+/// value fails to decode. Decoding without validation can't fail, so it clamps such values to the
+/// last variant instead.
+///
+/// [`StorageEnum`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.StorageEnum.html
+/// [`StorageEnumU8`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageEnumU8.html
+/// [`AbiType`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/abi/trait.AbiType.html
+#[proc_macro_derive(StorageEnum)]
+pub fn derive_storage_enum(input: TokenStream) -> TokenStream {
+    storage::derive_storage_enum(input)
+}
+
 /// Allows an error `enum` to be used in method signatures.
 ///
 /// ```ignore
@@ -305,6 +541,13 @@ pub fn derive_erase(input: TokenStream) -> TokenStream {
 ///
 /// Under the hood, the above macro works by implementing `From<Erc20Error>` for `Vec<u8>`
 /// along with printing code for abi-export.
+///
+/// It also implements [`FromRevert`], so that callers can decode the error when a call to the
+/// method reverts. Variants are tried in order, and a variant holding a [`call::Error`] matches
+/// any revert, which makes it useful as a final catch-all.
+///
+/// [`FromRevert`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/trait.FromRevert.html
+/// [`call::Error`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/call/enum.Error.html
 #[proc_macro_derive(SolidityError)]
 pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
     methods::error::derive_solidity_error(input)
@@ -363,6 +606,9 @@ pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
 /// of the battle in the fight against exploits. You can tell if a call is reentrant via
 /// [`msg::reentrant`][reentrant], and condition your business logic accordingly.
 ///
+/// Individual methods of a [`#[public]`][public] contract may override the flag with the
+/// `#[allow_reentrant]` and `#[non_reentrant]` attributes. See [`#[public]`][public] for details.
+///
 /// # [`TopLevelStorage`]
 ///
 /// The [`#[entrypoint]`][entrypoint] macro will automatically implement the [`TopLevelStorage`] `trait`
@@ -370,8 +616,35 @@ pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
 /// mutable access to it represents mutable access to the entire program's state.
 /// This has implications for calls via [`sol_interface`].
 ///
+/// # Resetting storage
+///
+/// If the `struct` also derives [`Erase`][erase] or [`StorageReset`][reset], a `reset()` method is
+/// generated that erases every field, which is useful in test fixtures and for guarded "factory
+/// reset" operations. Maps can't be erased, so `reset()` leaves them in place, including those of
+/// nested structs, and its documentation lists the ones skipped. Since the contract needn't be
+/// erasable itself, deriving `Erase` derives `StorageReset` instead, so it may have maps. Fields
+/// marked `#[default]` may be initialized again afterward.
+///
+/// ```ignore
+/// sol_storage! {
+///     #[entrypoint]
+///     #[derive(Erase)]
+///     pub struct Contract {
+///         #[borrow]
+///         Settings settings;                  // reset, so must derive `Erase` or `StorageReset`
+///         address[] holders;                  // erased
+///         mapping(address => uint) balances;  // left in place
+///     }
+/// }
+/// ```
+///
+/// The method is not exposed to callers, so a public method should guard it before erasing the
+/// contract's state.
+///
 /// [`TopLevelStorage`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/trait.TopLevelStorage.html
 /// [`sol_interface`]: macro@sol_interface
+/// [erase]: macro@Erase
+/// [reset]: macro@StorageReset
 /// [entrypoint]: macro@entrypoint
 /// [reentrant]: https://docs.rs/stylus-sdk/latest/stylus_sdk/msg/fn.reentrant.html
 /// [public]: macro@public
@@ -406,6 +679,30 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// In is example, [`Vec<u8>`] becomes the program's revert data.
 ///
+/// # Return types
+///
+/// Methods may return any ABI type `T`, or a `Result<T, E>` whose errors are either a [`Vec<u8>`]
+/// or a type deriving [`SolidityError`]. `Ok` values are ABI-encoded and errors become the revert
+/// data, as described by [`IntoReturnData`]. Methods marked `#[raw]` must return an [`ArbResult`],
+/// whose bytes become the return data directly, without encoding. These are exported without a
+/// `returns` clause.
+///
+/// ```ignore
+/// #[public]
+/// impl Contract {
+///     // the bytes are returned as is
+///     #[raw]
+///     pub fn raw(&self) -> ArbResult {
+///         Ok(self.blob.get_bytes())
+///     }
+///
+///     // the balance is ABI-encoded as a uint256
+///     pub fn balance(&self, owner: Address) -> Result<U256, Erc20Error> {
+///         ...
+///     }
+/// }
+/// ```
+///
 /// # [`#[payable]`][payable]
 ///
 /// As in Solidity, methods may accept ETH as call value.
@@ -425,6 +722,117 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// with [`#[payable]`][payable], or else calls to it will revert. This is required as a safety measure
 /// to prevent users losing funds to methods that didn't intend to accept ether.
 ///
+/// # `#[max_calldata]`
+///
+/// Methods with inherently bounded inputs may cap the size of the calldata they accept, selector included.
+/// Oversized calls revert with a `CalldataTooLarge(uint256 limit, uint256 length)` error before any
+/// arguments are decoded.
+///
+/// ```ignore
+/// #[public]
+/// impl Contract {
+///     #[max_calldata(68)]
+///     pub fn set_value(&mut self, key: U256, value: U256) {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// # Aliases, `#[alias]`
+///
+/// When a method is renamed, callers of the old name can keep working by listing it with
+/// `#[alias]`, which may be repeated. Each alias is routed to the method with the same arguments,
+/// while only the method's own name, which `#[selector]` may set, is exported in the ABI.
+///
+/// ```ignore
+/// #[public]
+/// impl Token {
+///     #[alias("getBalance")]
+///     pub fn balance_of(&self, owner: Address) -> U256 {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// Aliases are listed by `selectors()` under the method's name, and collide with other methods
+/// like any selector would. Two methods of the same type answering to a selector is a compile error,
+/// and shadowing a differently named method that the type inherits requires `#[overrides]`.
+///
+/// # Borrowing calldata
+///
+/// Arguments of type `&[u8]` and `&str` are decoded as `bytes` and `string`, but borrow directly
+/// from the calldata instead of copying into a [`Bytes`] or [`String`]. This saves an allocation
+/// and a copy for methods processing large inputs.
+///
+/// ```ignore
+/// #[public]
+/// impl Contract {
+///     pub fn checksum(&self, data: &[u8], label: &str) -> U256 {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// The references only live for the duration of the call, so they can't be stored or returned.
+/// Copy the data, such as with `to_vec`, to keep it around. Only top-level arguments may borrow, so
+/// types like `Vec<&[u8]>` aren't supported. Strings that aren't valid UTF-8 fail to decode.
+///
+/// # Generic methods, `#[instances]`
+///
+/// A method may be generic over one type parameter if it lists the concrete types to export with
+/// `#[instances]`. Each instance is routed as its own overload of the method, with the selector
+/// computed from the instantiated arguments.
+///
+/// ```ignore
+/// #[public]
+/// impl Contract {
+///     #[instances(U256, Address, Bytes)]
+///     pub fn hash<T: AbiType>(&self, value: T) -> B256 {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// Since instances are told apart by their selectors, the type parameter must appear in the
+/// arguments, and no two instances may share an ABI type. The parameter of a method taking
+/// storage via `&S` or `&mut S` isn't counted, since it's filled in by the router. Instances are
+/// listed by the generated `selectors()` as, for example, `Contract::hash::<U256>`.
+///
+/// # `#[allow_reentrant]` and `#[non_reentrant]`
+///
+/// Whether a method may be called reentrantly defaults to the crate-wide `reentrant` feature flag.
+/// Either attribute overrides it for a single method. Before routing, the [`#[entrypoint]`][entrypoint]
+/// checks [`msg::reentrant`][reentrant] and reverts if the selected method doesn't allow it.
+///
+/// ```ignore
+/// #[public]
+/// impl Contract {
+///     #[allow_reentrant]
+///     pub fn balance_of(&self, owner: Address) -> U256 {
+///         self.balances.get(owner)
+///     }
+///
+///     #[non_reentrant]
+///     pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// The attributes only decide whether reentrant calls revert. Whether the storage cache is flushed
+/// around outgoing calls is still up to the feature flag. Without it, an outer call's pending writes
+/// aren't visible to an `#[allow_reentrant]` method, so reserve the attribute for methods that don't
+/// depend on them, or [`flush`] the [`StorageCache`] before making calls. Within such a method,
+/// [`msg::reentrant`][reentrant] tells whether the current call is reentrant.
+///
+/// Nor could a reentrant call's writes survive without the feature: the outer call flushes its own
+/// cache once it returns, overwriting them. So methods that write storage, such as those taking
+/// `&mut self`, may only be `#[allow_reentrant]` with the `reentrant` feature, and are otherwise a
+/// compile error.
+///
+/// Overriding methods without either attribute take that of the method they override, so that
+/// overriding a `#[non_reentrant]` method can't silently make it reentrant.
+///
 /// # [`pure`][pure] [`view`][view], and `write`
 ///
 /// For non-payable methods the [`#[public]`][public] macro can figure state mutability out for you based
@@ -433,6 +841,23 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// `pure` and `view` functions may change the state of other contracts by calling into them, or
 /// even this one if the `reentrant` feature is enabled.
 ///
+/// The inferred mutability may be overridden with a `#[pure]`, `#[view]`, or `#[write]` attribute,
+/// which changes the exported ABI and how the method may be overridden by inheritors. This is useful
+/// when a `&self` method modifies state by other means, such as writing to storage slots directly.
+///
+/// ```ignore
+/// #[public]
+/// impl Contract {
+///     #[write]
+///     pub fn set_slot(&self, value: U256) {
+///         unsafe { StorageCache::set_word(SLOT, value.into()) };
+///     }
+/// }
+/// ```
+///
+/// A method can't claim less mutability than its arguments allow, so `#[pure]` is an error on methods
+/// taking `&self` or `&mut self`, as is `#[view]` on methods taking `&mut self`.
+///
 /// Please refer to the [SDK Feature Overview][overview] for more information on defining methods.
 ///
 /// # Inheritance, `#[inherit]`, and `#[borrow]`
@@ -470,6 +895,35 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// marking override functions. It is important, therefore, to carefully ensure that contracts are
 /// only overriding the functions.
 ///
+/// Overriding is assumed to be intended when the methods share a Rust name. A method that shadows one
+/// with a different name, such as through `#[selector]`, is instead a compile error in contracts
+/// with an [`#[entrypoint]`][entrypoint]. If the override is intended, mark the shadowing method
+/// `#[overrides]`.
+///
+/// ```ignore
+/// #[public]
+/// #[inherit(Erc20)]
+/// impl Token {
+///     #[selector(name = "name")]
+///     #[overrides]
+///     pub fn brand_name() -> String {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// To see which methods win, [`#[public]`][public] generates a `selectors()` function listing each
+/// routable selector and the method it belongs to, in the order they're checked. A selector listed
+/// twice is shadowed, which tests can check for.
+///
+/// ```ignore
+/// let selectors = Token::selectors();
+/// for (i, (selector, method)) in selectors.iter().enumerate() {
+///     let first = selectors.iter().position(|(s, _)| s == selector).unwrap();
+///     assert!(first == i || *method == "Erc20::name", "{method} is shadowed");
+/// }
+/// ```
+///
 /// Inheritance can also be chained. `#[inherit(Erc20, Erc721)]` will inherit both `Erc20` and `Erc721`, checking
 /// for methods in that order. `Erc20` and `Erc721` may also inherit other types themselves. Method resolution
 /// finds the first matching method by [`Depth First Search`][dfs].
@@ -498,6 +952,114 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// [`Router`] composition is more configurable. The motivation for this becomes clearer in complex
 /// cases of multi-level inheritance, which we intend to improve.
 ///
+/// # Advertising interfaces, `#[supports_interfaces]` and `#[implements]`
+///
+/// The `#[supports_interfaces]` attribute implements [ERC-165]'s `supportsInterface(bytes4)` for
+/// the contract, returning `true` for `0x01ffc9a7` and for each listed id. The ids are `u32`
+/// constant expressions, so besides standard function interfaces they may be ids of custom errors
+/// or arbitrary capability flags the contract wishes to advertise.
+///
+/// ```ignore
+/// const IERC721: u32 = 0x80ac58cd;
+/// const ERRORS: u32 = interface_id(&[function_selector!("Unauthorized", Address)]);
+/// const FLASH_LOANS: u32 = 0x2f0a18c5;
+///
+/// #[public]
+/// #[supports_interfaces(IERC721, ERRORS, FLASH_LOANS)]
+/// impl Token {
+///     ...
+/// }
+/// ```
+///
+/// Only one router may answer a given selector, so the attribute belongs on the type with the
+/// [`#[entrypoint]`][entrypoint], listing the ids of everything it inherits too. Defining a
+/// `supports_interface` method alongside the attribute is a compile error.
+///
+/// Rather than computing the ids of standard interfaces by hand, `#[implements]` lists types with
+/// `#[public]` methods, advertising the interface id of each. A type's id is the XOR of the selectors
+/// of the methods it defines itself, so aliases, fallbacks, and inherited methods aren't included.
+/// The two attributes may be combined to advertise further ids, and leaving both out opts out of
+/// ERC-165 entirely.
+///
+/// ```ignore
+/// #[public]
+/// #[inherit(Erc721, Ownable)]
+/// #[implements(Erc721, Ownable)]
+/// #[supports_interfaces(FLASH_LOANS)]
+/// impl Token {
+///     ...
+/// }
+/// ```
+///
+/// Some standards define their id over only part of a type's methods, or split its methods across
+/// several interfaces, as [ERC-1155] does with its metadata extension. Such a type lists the ids it
+/// implements with `#[interface_ids]`, which `#[implements]` then advertises instead of the XOR of
+/// all its selectors.
+///
+/// ```ignore
+/// #[public]
+/// #[interface_ids(IERC1155, IERC1155_METADATA_URI)]
+/// impl Erc1155 {
+///     ...
+/// }
+/// ```
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+/// [ERC-1155]: https://eips.ethereum.org/EIPS/eip-1155
+///
+/// # Guarding every method, `#[before_dispatch]`
+///
+/// The `#[before_dispatch(hook)]` attribute runs `hook` ahead of every call the router handles,
+/// including calls to methods it inherits and to its fallback. The hook receives the selector and
+/// the calldata that follows it, and returning an error reverts the call with that data without
+/// running the method. This makes contract-wide checks like pausing or allowlists a single
+/// function.
+///
+/// ```ignore
+/// #[public]
+/// #[inherit(Erc20)]
+/// #[before_dispatch(when_not_paused)]
+/// impl Token {
+///     ...
+/// }
+///
+/// impl Token {
+///     fn when_not_paused(&mut self, selector: u32, calldata: &[u8]) -> Result<(), Vec<u8>> {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// Calls with a selector no method matches only run the hook if there's a fallback to handle
+/// them, in which case the hook gets the first 4 bytes of the calldata as the selector, padded
+/// with zeros like [`msg::sig`][sig] when shorter, and the rest as the calldata.
+///
+/// Since every method of a [`#[public]`][public] impl is exported, the hook belongs in a separate
+/// `impl` block. Hooks only apply to the routers that declare them and those they inherit, so
+/// a hook on an inherited type doesn't guard the methods of the types inheriting it.
+///
+/// # Handling unknown calls, `#[fallback]`
+///
+/// A method marked `#[fallback]` runs when no selector matches, including for calldata shorter
+/// than a selector. Like Solidity's `fallback`, it receives the entire calldata and returns the
+/// raw bytes to respond with, or an error whose encoding becomes the revert data.
+///
+/// ```ignore
+/// #[public]
+/// impl Proxy {
+///     #[fallback]
+///     #[payable]
+///     pub fn fallback(&mut self, calldata: &[u8]) -> Result<Vec<u8>, ProxyError> {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// The fallback isn't part of the exported ABI and has no selector. It rejects value unless
+/// marked `#[payable]`, in which case [`msg::value`][value] works as usual, and runs after any
+/// `#[before_dispatch]` hook. A router without a fallback of its own uses the first one it
+/// inherits, so each type may define at most one.
+///
 /// # Exporting a Solidity interface
 ///
 /// Recall that Stylus contracts are fully interoperable across all languages, including Solidity.
@@ -549,6 +1111,14 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// [Borrow]: https://doc.rust-lang.org/std/borrow/trait.Borrow.html
 /// [BorrowMut]: https://doc.rust-lang.org/std/borrow/trait.BorrowMut.html
 /// [value]: https://docs.rs/stylus-sdk/latest/stylus_sdk/msg/fn.value.html
+/// [sig]: https://docs.rs/stylus-sdk/latest/stylus_sdk/msg/fn.sig.html
+/// [reentrant]: https://docs.rs/stylus-sdk/latest/stylus_sdk/msg/fn.reentrant.html
+/// [`StorageCache`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageCache.html
+/// [`flush`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/storage/struct.StorageCache.html#method.flush
+/// [`Bytes`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/abi/struct.Bytes.html
+/// [`SolidityError`]: derive@SolidityError
+/// [`IntoReturnData`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/abi/trait.IntoReturnData.html
+/// [`ArbResult`]: https://docs.rs/stylus-sdk/latest/stylus_sdk/type.ArbResult.html
 /// [payable]: https://docs.alchemy.com/docs/solidity-payable-functions
 /// [view]: https://docs.soliditylang.org/en/develop/contracts.html#view-functions
 /// [pure]: https://docs.soliditylang.org/en/develop/contracts.html#pure-functions
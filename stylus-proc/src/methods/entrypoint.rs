@@ -1,20 +1,28 @@
 // Copyright 2023-2024, Offchain Labs, Inc.
 // For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
 
-use cfg_if::cfg_if;
+use crate::storage;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::{parse_macro_input, Item};
 
 pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
-    let input: Item = parse_macro_input!(input);
+    let mut input: Item = parse_macro_input!(input);
 
     if !attr.is_empty() {
         error!(Span::mixed_site(), "this macro is not configurable");
     }
 
-    let mut output = quote! { #input };
+    let reset = match &mut input {
+        Item::Struct(input) => storage::entrypoint_reset(input),
+        _ => quote! {},
+    };
+    let mut output = quote! { #input #reset };
+
+    // revert on reentrancy unless explicitly enabled
+    let allow_reentrant = cfg!(feature = "reentrant");
+    let mut deny_reentrant = quote! {};
 
     let user = match input {
         Item::Struct(input) => {
@@ -29,19 +37,32 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
                     use core::convert::TryInto;
                     use alloc::vec;
 
-                    if input.len() < 4 {
-                        console!("calldata too short: {}", hex::encode(input));
+                    let selector = input.get(..4).map(|selector| {
+                        u32::from_be_bytes(TryInto::try_into(selector).unwrap())
+                    });
+                    let allow_reentrant = selector.and_then(<#name>::__stylus_allow_reentrant);
+                    if !allow_reentrant.unwrap_or(#allow_reentrant) && stylus_sdk::msg::reentrant() {
                         return Err(vec![]);
                     }
-                    let selector = u32::from_be_bytes(TryInto::try_into(&input[..4]).unwrap());
                     let mut storage = unsafe { <#name as StorageType>::new(U256::ZERO, 0) };
-                    match <#name as Router<_>>::route(&mut storage, selector, &input[4..]) {
-                        Some(res) => res,
-                        None => {
+                    if let Some(selector) = selector {
+                        if let Some(res) = <#name as Router<_>>::route(&mut storage, selector, &input[4..]) {
+                            return res;
+                        }
+                    }
+                    if let Some(res) = <#name as Router<_>>::fallback(&mut storage, &input) {
+                        return res;
+                    }
+                    #[allow(unused_variables)]
+                    match selector {
+                        Some(selector) => {
                             console!("unknown method selector: {selector:08x}");
-                            Err(vec![])
-                        },
+                        }
+                        None => {
+                            console!("calldata too short: {}", hex::encode(&input));
+                        }
                     }
+                    Err(vec![])
                 }
 
                 const _: () = {
@@ -59,23 +80,19 @@ pub fn entrypoint(attr: TokenStream, input: TokenStream) -> TokenStream {
 
             Ident::new("entrypoint", name.span())
         }
-        Item::Fn(input) => input.sig.ident.clone(),
+        Item::Fn(input) => {
+            if !allow_reentrant {
+                deny_reentrant = quote! {
+                    if stylus_sdk::msg::reentrant() {
+                        return 1; // revert
+                    }
+                };
+            }
+            input.sig.ident.clone()
+        }
         _ => error!(input, "not a struct or fn"),
     };
 
-    // revert on reentrancy unless explicitly enabled
-    cfg_if! {
-        if #[cfg(feature = "reentrant")] {
-            let deny_reentrant = quote! {};
-        } else {
-            let deny_reentrant = quote! {
-                if stylus_sdk::msg::reentrant() {
-                    return 1; // revert
-                }
-            };
-        }
-    }
-
     output.extend(quote! {
         #[no_mangle]
         pub unsafe fn mark_used() {
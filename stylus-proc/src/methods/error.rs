@@ -9,6 +9,7 @@ pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemEnum);
     let name = &input.ident;
     let mut match_arms = quote!();
+    let mut decode_variants = quote!();
     let mut errors = vec![];
     let mut output = quote!();
     for variant in input.variants {
@@ -21,6 +22,11 @@ pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
         match_arms.extend(quote! {
             #name::#variant_name(e) => ::stylus_sdk::call::MethodError::encode(e),
         });
+        decode_variants.extend(quote! {
+            if let Some(err) = <#ty as ::stylus_sdk::call::FromRevert>::from_revert(data) {
+                return Some(#name::#variant_name(err));
+            }
+        });
         output.extend(quote! {
             impl From<#ty> for #name {
                 fn from(value: #ty) -> Self {
@@ -38,25 +44,25 @@ pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
                 }
             }
         }
+
+        impl ::stylus_sdk::call::FromRevert for #name {
+            fn from_revert(data: &[u8]) -> Option<Self> {
+                #decode_variants
+                None
+            }
+        }
     });
 
     if cfg!(feature = "export-abi") {
+        let count = errors.len();
         output.extend(quote! {
             impl stylus_sdk::abi::export::internal::InnerTypes for #name {
                 fn inner_types() -> alloc::vec::Vec<stylus_sdk::abi::export::internal::InnerType> {
-                    use alloc::{format, vec};
-                    use core::any::TypeId;
-                    use stylus_sdk::abi::export::internal::InnerType;
-                    use stylus_sdk::alloy_sol_types::SolError;
+                    use stylus_sdk::abi::export::internal::{ErrorType, InnerType};
 
-                    vec![
-                        #(
-                            InnerType {
-                                name: format!("error {};", <#errors as SolError>::SIGNATURE.replace(',', ", ")),
-                                id: TypeId::of::<#errors>(),
-                            }
-                        ),*
-                    ]
+                    let errors: [Option<InnerType>; #count] =
+                        [#( <#errors as ErrorType>::error_type() ),*];
+                    IntoIterator::into_iter(errors).flatten().collect()
                 }
             }
         });
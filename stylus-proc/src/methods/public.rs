@@ -4,16 +4,17 @@
 use crate::types::Purity;
 use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span};
-use quote::{quote, quote_spanned};
-use std::mem;
+use proc_macro2::{Group, Ident, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use std::{collections::HashSet, mem};
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
     spanned::Spanned,
-    FnArg, ImplItem, Index, ItemImpl, LitStr, Pat, PatType, Result, ReturnType, Token, Type,
+    Expr, FnArg, ImplItem, Index, ItemImpl, LitInt, LitStr, Pat, PatType, Result, ReturnType,
+    Token, Type,
 };
 
 pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -27,7 +28,18 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
     let mut abi = quote!();
     let mut types = vec![];
     let mut override_selectors = quote!();
+    let mut reentrancy_selectors = quote!();
     let mut selector_consts = vec![];
+    let mut interface_selectors = vec![];
+    let mut selector_names = vec![];
+    let mut routed_methods = vec![];
+    let mut fallback_route = None;
+
+    // name the methods by their type for selector listings
+    let type_name = match &*input.self_ty {
+        Type::Path(path) => path.path.segments.last().unwrap().ident.to_string(),
+        ty => quote!(#ty).to_string(),
+    };
 
     for item in input.items.iter_mut() {
         let ImplItem::Method(method) = item else {
@@ -37,19 +49,26 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
         // see if user chose a purity or selector
         let mut purity = None;
         let mut override_name = None;
+        let mut max_calldata = None;
+        let mut allow_reentrant = None;
+        let mut overrides = false;
+        let mut instances = None;
+        let mut fallback = None;
+        let mut raw = None;
+        let mut aliases: Vec<LitStr> = vec![];
         for attr in mem::take(&mut method.attrs) {
             let Some(ident) = attr.path.get_ident() else {
                 method.attrs.push(attr);
                 continue;
             };
-            if *ident == "payable" {
+            if let Ok(explicit) = ident.to_string().parse::<Purity>() {
                 if !attr.tokens.is_empty() {
                     error!(attr.tokens, "attribute does not take parameters");
                 }
                 if purity.is_some() {
-                    error!(attr.path, "more than one payable attribute");
+                    error!(attr.path, "more than one purity attribute");
                 }
-                purity = Some(Purity::Payable);
+                purity = Some(explicit);
                 continue;
             }
             if *ident == "selector" {
@@ -63,6 +82,82 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                 override_name = Some(args.name);
                 continue;
             }
+            if *ident == "max_calldata" {
+                if max_calldata.is_some() {
+                    error!(attr.path, "more than one max_calldata attribute");
+                }
+                let limit = match attr.parse_args::<LitInt>() {
+                    Ok(limit) => limit,
+                    Err(error) => error!(ident, "{}", error),
+                };
+                if let Err(error) = limit.base10_parse::<usize>() {
+                    error!(limit, "{}", error);
+                }
+                max_calldata = Some(limit);
+                continue;
+            }
+            if *ident == "allow_reentrant" || *ident == "non_reentrant" {
+                if !attr.tokens.is_empty() {
+                    error!(attr.tokens, "attribute does not take parameters");
+                }
+                if allow_reentrant.is_some() {
+                    error!(attr.path, "more than one reentrancy attribute");
+                }
+                allow_reentrant = Some((*ident == "allow_reentrant", attr.path));
+                continue;
+            }
+            if *ident == "overrides" {
+                if !attr.tokens.is_empty() {
+                    error!(attr.tokens, "attribute does not take parameters");
+                }
+                if overrides {
+                    error!(attr.path, "more than one overrides attribute");
+                }
+                overrides = true;
+                continue;
+            }
+            if *ident == "alias" {
+                let alias = match attr.parse_args::<LitStr>() {
+                    Ok(alias) => alias,
+                    Err(error) => error!(ident, "{}", error),
+                };
+                if aliases.iter().any(|other| other.value() == alias.value()) {
+                    error!(alias, "duplicate alias");
+                }
+                aliases.push(alias);
+                continue;
+            }
+            if *ident == "fallback" {
+                if !attr.tokens.is_empty() {
+                    error!(attr.tokens, "attribute does not take parameters");
+                }
+                if fallback.is_some() {
+                    error!(attr.path, "more than one fallback attribute");
+                }
+                fallback = Some(attr.path);
+                continue;
+            }
+            if *ident == "raw" {
+                if !attr.tokens.is_empty() {
+                    error!(attr.tokens, "attribute does not take parameters");
+                }
+                if raw.is_some() {
+                    error!(attr.path, "more than one raw attribute");
+                }
+                raw = Some(attr.path);
+                continue;
+            }
+            if *ident == "instances" {
+                if instances.is_some() {
+                    error!(attr.path, "more than one instances attribute");
+                }
+                match attr.parse_args_with(Punctuated::<Type, Token![,]>::parse_terminated) {
+                    Ok(types) if types.is_empty() => error!(attr.tokens, "expected a type"),
+                    Ok(types) => instances = Some(types),
+                    Err(error) => error!(ident, "{}", error),
+                }
+                continue;
+            }
             method.attrs.push(attr);
         }
 
@@ -77,6 +172,7 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                 recv.mutability.into()
             }
             Some(FnArg::Typed(PatType { ty, .. })) => match &**ty {
+                Type::Reference(_) if Borrowed::of(ty).is_some() => Pure,
                 Type::Reference(ty) => ty.mutability.into(),
                 _ => Pure,
             },
@@ -95,6 +191,42 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             args.next(); // drop first arg
         }
 
+        // without the feature, the outer call's storage cache isn't flushed before it calls out,
+        // so its later flush would clobber whatever a reentrant call wrote
+        if let Some((true, attr)) = &allow_reentrant {
+            if purity > View && !cfg!(feature = "reentrant") {
+                error!(
+                    attr,
+                    "methods that write storage can only #[allow_reentrant] with the `reentrant` feature"
+                );
+            }
+        }
+
+        // generic methods are routed once for each of their instances
+        let storage_arg = match method.sig.inputs.first() {
+            Some(FnArg::Typed(arg)) if needed_purity > Pure => Some(&arg.ty),
+            _ => None,
+        };
+        let params: Vec<_> = method
+            .sig
+            .generics
+            .type_params()
+            .filter(|param| !storage_arg.is_some_and(|ty| mentions(ty, &param.ident)))
+            .collect();
+        let generic = match (params.as_slice(), instances) {
+            ([], None) => None,
+            ([], Some(_)) => error!(method.sig.ident, "instances given for a non-generic method"),
+            ([param], Some(types)) => Some((param.ident.clone(), types)),
+            ([_], None) => error!(
+                method.sig.generics,
+                "generic methods must list their #[instances]"
+            ),
+            _ => error!(
+                method.sig.generics,
+                "methods may have at most one type parameter"
+            ),
+        };
+
         /// finds the root type for a given arg
         fn pattern_ident(pat: Pat) -> Option<Ident> {
             match pat {
@@ -110,8 +242,51 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             })
             .collect();
 
+        // the type parameter must be an argument, giving each instance its own selector
+        if let Some((param, types)) = &generic {
+            if !args.iter().any(|(_, ty)| mentions(ty, param)) {
+                error!(param, "type parameter must be used by an argument");
+            }
+            let mut seen = HashSet::new();
+            for ty in types {
+                if !seen.insert(quote!(#ty).to_string()) {
+                    error!(ty, "duplicate instance");
+                }
+            }
+        }
+
+        // the fallback handles calls no method matches, so it has no selector of its own
+        if let Some(attr) = &fallback {
+            if fallback_route.is_some() {
+                error!(attr, "more than one fallback method");
+            }
+            let has_selector = override_name.is_some() || !aliases.is_empty();
+            let has_selector = has_selector || max_calldata.is_some() || overrides;
+            let has_encoding = raw.is_some() || generic.is_some();
+            if has_selector || allow_reentrant.is_some() || has_encoding {
+                error!(attr, "fallback methods only support purity attributes");
+            }
+            if !matches!(args.as_slice(), [(_, ty)] if matches!(Borrowed::of(ty), Some(Borrowed::Bytes)))
+            {
+                error!(
+                    method.sig.inputs,
+                    "fallback methods take the calldata as a single &[u8]"
+                );
+            }
+        }
+
+        // raw methods produce their return data themselves
+        if let Some(attr) = &raw {
+            if matches!(method.sig.output, ReturnType::Default) {
+                error!(attr, "raw methods must return an ArbResult");
+            }
+        }
+
         let name = &method.sig.ident;
         let sol_name = override_name.unwrap_or(name.to_string().to_case(Case::Camel));
+        if let Some(alias) = aliases.iter().find(|alias| alias.value() == sol_name) {
+            error!(alias, "alias matches the method's own name");
+        }
 
         // deny value when method isn't payable
         let mut deny_value = quote!();
@@ -124,6 +299,16 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             };
         };
 
+        // reject oversized calldata before decoding it
+        let mut check_calldata = quote!();
+        if let Some(limit) = max_calldata {
+            check_calldata = quote! {
+                if let Err(err) = internal::check_calldata_size(#limit, input) {
+                    return Some(Err(err));
+                }
+            };
+        }
+
         // get the needed storage
         let storage = if needed_purity == Pure {
             quote!()
@@ -133,98 +318,260 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             quote! { storage, }
         };
 
-        // get the solidity args
-        let mut expand_args = vec![];
-        for (index, (_, ty)) in args.iter().enumerate() {
-            let index = Index {
-                index: index as u32,
-                span: ty.span(),
-            };
-            expand_args.push(quote! { args.#index });
+        if fallback.is_some() {
+            fallback_route = Some(quote! {
+                #deny_value
+                Some(Self::#name(#storage input).map_err(Into::into))
+            });
+            continue;
         }
 
-        // calculate selector
-        let constant = Ident::new(&format!("SELECTOR_{name}"), name.span());
-        let arg_types: &Vec<_> = &args.iter().map(|a| &a.1).collect();
-
-        let selector =
-            quote! { u32::from_be_bytes(function_selector!(#sol_name #(, #arg_types )*)) };
-        selectors.extend(quote! {
-            #[allow(non_upper_case_globals)]
-            const #constant: u32 = #selector;
-        });
-
-        let sdk_purity = purity.as_tokens();
-        override_selectors.extend(quote! {
-            #[allow(non_upper_case_globals)]
-            #constant => #sdk_purity.allow_override(purity),
-        });
-        let error_msg = format!(
-            "function {} cannot be overriden with function marked {:?}",
-            name, purity
-        );
-        selector_consts.push((constant.clone(), sdk_purity, error_msg));
-
-        let in_span = method.sig.inputs.span();
-        let decode_inputs = quote_spanned! { in_span => <(#( #arg_types, )*) as AbiType>::SolType };
-
-        let ret_span = match &method.sig.output {
-            x @ ReturnType::Default => x.span(),
-            ReturnType::Type(_, ty) => ty.span(), // right of arrow
+        let instances: Vec<_> = match &generic {
+            Some((_, types)) => types.iter().map(Some).collect(),
+            None => vec![None],
         };
-        let encode_result = quote_spanned! { ret_span => EncodableReturnType::encode(result) };
+        for (instance_index, instance) in instances.into_iter().enumerate() {
+            let instantiate = |ty: &Type| match (&generic, instance) {
+                (Some((param, _)), Some(concrete)) => substitute(ty, param, concrete),
+                _ => ty.clone(),
+            };
+            let output = match &method.sig.output {
+                ReturnType::Type(arrow, ty) => ReturnType::Type(*arrow, Box::new(instantiate(ty))),
+                output => output.clone(),
+            };
+            if let ReturnType::Type(_, ty) = &output {
+                types.push(ty.clone());
+            }
 
-        // match against the selector
-        match_selectors.extend(quote! {
-            #[allow(non_upper_case_globals)]
-            #constant => {
-                #deny_value
-                let args = match <#decode_inputs as SolType>::abi_decode_params(input, true) {
-                    Ok(args) => args,
-                    Err(err) => {
-                        internal::failed_to_decode_arguments(err);
-                        return Some(Err(vec![]));
-                    }
+            // slices borrowed from calldata are typed as their owned equivalents
+            let borrowed: Vec<_> = args
+                .iter()
+                .map(|(_, ty)| Borrowed::of(&instantiate(ty)))
+                .collect();
+            let args: Vec<_> = args
+                .iter()
+                .zip(&borrowed)
+                .map(|((ident, ty), borrowed)| match borrowed {
+                    Some(borrowed) => (ident, Box::new(borrowed.owned_type())),
+                    None => (ident, Box::new(instantiate(ty))),
+                })
+                .collect();
+
+            // get the solidity args
+            let mut expand_args = vec![];
+            let mut detokenize_args = vec![];
+            for (index, (_, ty)) in args.iter().enumerate() {
+                let index = Index {
+                    index: index as u32,
+                    span: ty.span(),
                 };
-                let result = Self::#name(#storage #(#expand_args, )* );
-                Some(#encode_result)
+                expand_args.push(quote! { args.#index });
+                detokenize_args.push(match borrowed[index.index as usize] {
+                    Some(Borrowed::Bytes) => quote! { tokens.#index.0 },
+                    Some(Borrowed::Str) => quote! { internal::borrow_str(tokens.#index)? },
+                    None => quote! {
+                        <<#ty as AbiType>::SolType as SolType>::detokenize(tokens.#index)
+                    },
+                });
             }
-        });
 
-        // only collect abi info if enabled
-        if cfg!(not(feature = "export-abi")) {
-            continue;
-        }
+            // calculate selector
+            let (constant, display_name, turbofish) = match instance {
+                Some(ty) => {
+                    let display = format!("{name}::<{}>", quote!(#ty)).replace(' ', "");
+                    let constant = format!("SELECTOR_{name}_{instance_index}");
+                    (Ident::new(&constant, name.span()), display, quote!(::<#ty>))
+                }
+                None => {
+                    let constant = Ident::new(&format!("SELECTOR_{name}"), name.span());
+                    (constant, name.to_string(), quote!())
+                }
+            };
+            let arg_types: &Vec<_> = &args.iter().map(|a| &a.1).collect();
+
+            let selector =
+                quote! { u32::from_be_bytes(function_selector!(#sol_name #(, #arg_types )*)) };
+            selectors.extend(quote! {
+                #[allow(non_upper_case_globals)]
+                const #constant: u32 = #selector;
+            });
+
+            // only a method's own name counts toward the interface id
+            interface_selectors.push(constant.clone());
+
+            // aliases route to the same method under other names
+            let mut constants = vec![constant.clone()];
+            for (index, alias) in aliases.iter().enumerate() {
+                let alias_constant = format_ident!("{constant}_alias_{index}");
+                selectors.extend(quote! {
+                    #[allow(non_upper_case_globals)]
+                    const #alias_constant: u32 =
+                        u32::from_be_bytes(function_selector!(#alias #(, #arg_types )*));
+                });
+                constants.push(alias_constant);
+            }
+
+            let sdk_purity = purity.as_tokens();
+            override_selectors.extend(quote! {
+                #[allow(non_upper_case_globals)]
+                #(#constants)|* => #sdk_purity.allow_override(purity),
+            });
+            // without an attribute, overrides take that of the method they override
+            let reentrancy = match &allow_reentrant {
+                Some((allow, _)) => quote!(return Some(#allow)),
+                None => {
+                    let allow = cfg!(feature = "reentrant");
+                    quote!(own = Some(#allow))
+                }
+            };
+            reentrancy_selectors.extend(quote! {
+                #[allow(non_upper_case_globals)]
+                #(#constants)|* => #reentrancy,
+            });
+            let error_msg = format!(
+                "function {} cannot be overriden with function marked {:?}",
+                name, purity
+            );
+            for constant in &constants {
+                selector_names.push((constant.clone(), format!("{type_name}::{display_name}")));
+                routed_methods.push(routed_method(
+                    constant,
+                    &type_name,
+                    &display_name,
+                    overrides,
+                ));
+                selector_consts.push((constant.clone(), sdk_purity.clone(), error_msg.clone()));
+            }
 
-        let sol_args = args.iter().enumerate().map(|(i, (ident, ty))| {
-            let comma = (i > 0).then_some(", ").unwrap_or_default();
+            let in_span = method.sig.inputs.span();
+            let decode_inputs =
+                quote_spanned! { in_span => <(#( #arg_types, )*) as AbiType>::SolType };
+
+            let ret_span = match &output {
+                x @ ReturnType::Default => x.span(),
+                ReturnType::Type(_, ty) => ty.span(), // right of arrow
+            };
+            let encode_result = match raw {
+                Some(_) => quote_spanned! { ret_span =>
+                    { let result: stylus_sdk::ArbResult = result; result }
+                },
+                None => quote_spanned! { ret_span => IntoReturnData::into_return_data(result) },
+            };
+
+            // decode borrowed args from the tokens, which point into the calldata
+            let decode_args = match borrowed.iter().any(Option::is_some) {
+                true => quote! {
+                    internal::decode_tokens::<#decode_inputs>(input).and_then(|tokens| {
+                        Ok((#( #detokenize_args, )*))
+                    })
+                },
+                false => quote! { <#decode_inputs as SolType>::abi_decode_params(input, true) },
+            };
+
+            // match against the selector
+            match_selectors.extend(quote! {
+                #[allow(non_upper_case_globals)]
+                #(#constants)|* => {
+                    #check_calldata
+                    #deny_value
+                    let args = match #decode_args {
+                        Ok(args) => args,
+                        Err(err) => {
+                            internal::failed_to_decode_arguments(err);
+                            return Some(Err(vec![]));
+                        }
+                    };
+                    let result = Self::#name #turbofish(#storage #(#expand_args, )* );
+                    Some(#encode_result)
+                }
+            });
+
+            // only collect abi info if enabled
+            if cfg!(not(feature = "export-abi")) {
+                continue;
+            }
+
+            let sol_args = args.iter().enumerate().map(|(i, (ident, ty))| {
+            let comma = if i > 0 { ", " } else { "" };
             let name = ident.as_ref().map(ToString::to_string).unwrap_or_default();
             quote! {
                 write!(f, "{}{}{}", #comma, <#ty as AbiType>::EXPORT_ABI_ARG, underscore_if_sol(#name))?;
             }
         });
-        let sol_outs = match &method.sig.output {
-            ReturnType::Default => quote!(),
-            ReturnType::Type(_, ty) => quote! { write_solidity_returns::<#ty>(f)?; },
-        };
-        let sol_purity = match purity {
-            Write => "".to_string(),
-            x => format!(" {x}"),
-        };
+            let sol_outs = match &output {
+                // raw return data has no declared type
+                _ if raw.is_some() => quote!(),
+                ReturnType::Default => quote!(),
+                ReturnType::Type(_, ty) => quote! { write_solidity_returns::<#ty>(f)?; },
+            };
+            let sol_purity = match purity {
+                Write => "".to_string(),
+                x => format!(" {x}"),
+            };
 
-        abi.extend(quote! {
-            write!(f, "\n    function {}(", #sol_name)?;
-            #(#sol_args)*
-            write!(f, ") external")?;
-            write!(f, #sol_purity)?;
-            #sol_outs
-            writeln!(f, ";")?;
-        });
+            abi.extend(quote! {
+                write!(f, "\n    function {}(", #sol_name)?;
+                #(#sol_args)*
+                write!(f, ") external")?;
+                write!(f, #sol_purity)?;
+                #sol_outs
+                writeln!(f, ";")?;
+            });
+        }
     }
 
-    // collect inherits
+    // collect inherits and advertised interfaces
     let mut inherits = vec![];
+    let mut interfaces = None;
+    let mut implements = vec![];
+    let mut interface_ids = None;
+    let mut before_dispatch = None;
     for attr in mem::take(&mut input.attrs) {
+        if attr.path.is_ident("implements") {
+            let contents: InheritsAttr = match attr.parse_args() {
+                Ok(contents) => contents,
+                Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+            };
+            implements.extend(contents.types);
+            continue;
+        }
+        if attr.path.is_ident("before_dispatch") {
+            if before_dispatch.is_some() {
+                error!(attr.path, "more than one before_dispatch attribute");
+            }
+            let hook: Ident = match attr.parse_args() {
+                Ok(hook) => hook,
+                Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+            };
+            before_dispatch = Some(hook);
+            continue;
+        }
+        if attr.path.is_ident("interface_ids") {
+            if interface_ids.is_some() {
+                error!(attr.path, "more than one interface_ids attribute");
+            }
+            match attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+                Ok(ids) => interface_ids = Some(ids),
+                Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+            }
+            continue;
+        }
+        if attr.path.is_ident("supports_interfaces") {
+            if interfaces.is_some() {
+                error!(attr.path, "more than one supports_interfaces attribute");
+            }
+            let ids = match attr.tokens.is_empty() {
+                true => Punctuated::new(),
+                false => {
+                    match attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+                        Ok(ids) => ids,
+                        Err(err) => return proc_macro::TokenStream::from(err.to_compile_error()),
+                    }
+                }
+            };
+            interfaces = Some(ids);
+            continue;
+        }
         if !attr.path.is_ident("inherit") {
             input.attrs.push(attr);
             continue;
@@ -238,6 +585,102 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     }
 
+    // types list the interfaces they implement, or implement that of their own methods
+    let implemented_ids = match &interface_ids {
+        Some(ids) => {
+            let ids = ids.iter();
+            quote! { &[#(#ids),*] }
+        }
+        None => quote! { &[Self::__stylus_interface_id()] },
+    };
+
+    // implement ERC-165's supportsInterface for the declared ids and implemented types
+    if interfaces.is_some() || !implements.is_empty() {
+        let ids = interfaces.iter().flatten().map(|id| quote!(#id));
+        let implemented = implements
+            .iter()
+            .map(|ty| quote!(<#ty>::__STYLUS_INTERFACE_IDS));
+        selector_names.push((
+            Ident::new("SELECTOR_supportsInterface", Span::call_site()),
+            format!("{type_name}::supports_interface"),
+        ));
+        routed_methods.push(routed_method(
+            &Ident::new("SELECTOR_supportsInterface", Span::call_site()),
+            &type_name,
+            "supports_interface",
+            false,
+        ));
+        selectors.extend(quote! {
+            #[allow(non_upper_case_globals)]
+            const SELECTOR_supportsInterface: u32 =
+                u32::from_be_bytes(function_selector!("supportsInterface", stylus_sdk::alloy_primitives::FixedBytes<4>));
+        });
+        override_selectors.extend(quote! {
+            #[allow(non_upper_case_globals)]
+            SELECTOR_supportsInterface => stylus_sdk::methods::Purity::View.allow_override(purity),
+        });
+        let allow_reentrant = cfg!(feature = "reentrant");
+        reentrancy_selectors.extend(quote! {
+            #[allow(non_upper_case_globals)]
+            SELECTOR_supportsInterface => own = Some(#allow_reentrant),
+        });
+        match_selectors.extend(quote! {
+            #[allow(non_upper_case_globals)]
+            SELECTOR_supportsInterface => {
+                if let Err(err) = internal::deny_value("supports_interface") {
+                    return Some(Err(err));
+                }
+                type Args = <(stylus_sdk::alloy_primitives::FixedBytes<4>,) as AbiType>::SolType;
+                let args = match <Args as SolType>::abi_decode_params(input, true) {
+                    Ok(args) => args,
+                    Err(err) => {
+                        internal::failed_to_decode_arguments(err);
+                        return Some(Err(vec![]));
+                    }
+                };
+                let declared: &[u32] = &[#(#ids),*];
+                let implemented: &[&[u32]] = &[#(#implemented),*];
+                let supported = internal::supports_interface(args.0, declared, implemented);
+                Some(IntoReturnData::into_return_data(supported))
+            }
+        });
+        abi.extend(quote! {
+            writeln!(f, "\n    function supportsInterface(bytes4 interface_id) external view returns (bool);")?;
+        });
+    }
+
+    // run the user's hook before routing to any method, including inherited ones, but not for
+    // unknown selectors, which are left to the fallback
+    let before_route = before_dispatch.as_ref().map(|hook| {
+        quote! {
+            if Self::__stylus_allow_reentrant(selector).is_some() {
+                if let Err(err) = Self::#hook(core::borrow::BorrowMut::borrow_mut(storage), selector, input) {
+                    return Some(Err(err));
+                }
+            }
+        }
+    });
+
+    // and before the fallback, if there is one, giving it the selector as `msg::sig` would
+    let before_fallback = before_dispatch.as_ref().map(|hook| {
+        quote! {
+            if Self::__stylus_has_fallback() {
+                let mut selector = [0; 4];
+                let prefix = &input[..input.len().min(4)];
+                selector[..prefix.len()].copy_from_slice(prefix);
+                let calldata = input.get(4..).unwrap_or_default();
+                let selector = u32::from_be_bytes(selector);
+                if let Err(err) = Self::#hook(core::borrow::BorrowMut::borrow_mut(storage), selector, calldata) {
+                    return Some(Err(err));
+                }
+            }
+        }
+    });
+    let has_fallback = fallback_route.is_some();
+    let inherit_has_fallback = inherits.iter().map(|ty| {
+        quote! { || <#ty>::__stylus_has_fallback() }
+    });
+
     // try to match against each inherited router
     let inherit_routes = inherits.iter().map(|ty| {
         quote! {
@@ -247,6 +690,21 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     });
 
+    // fall back to the first inherited fallback if there's no fallback of our own
+    let fallback_route = fallback_route.unwrap_or_else(|| {
+        let inherit_fallbacks = inherits.iter().map(|ty| {
+            quote! {
+                if let Some(result) = <#ty as Router<S>>::fallback(storage, input) {
+                    return Some(result);
+                }
+            }
+        });
+        quote! {
+            #(#inherit_fallbacks)*
+            None
+        }
+    });
+
     // ensure we can actually borrow the things we inherit
     let borrow_clauses = inherits.iter().map(|ty| {
         quote! {
@@ -275,6 +733,37 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                 <#ty>::__stylus_assert_overrides();
             }
         }));
+    let own_selectors = selector_names.iter().map(|(selector, name)| {
+        quote! {
+            (FixedBytes(#selector.to_be_bytes()), #name)
+        }
+    });
+    let inherit_selectors = inherits.iter().map(|ty| {
+        quote! {
+            selectors.extend(<#ty>::selectors());
+        }
+    });
+
+    let routed_count = routed_methods.len();
+    let inherit_counts = inherits.iter().map(|ty| {
+        quote! { + <#ty>::__stylus_routed_count() }
+    });
+    let inherit_routed = inherits.iter().map(|ty| {
+        quote! {
+            if index < <#ty>::__stylus_routed_count() {
+                return <#ty>::__stylus_routed_method(index);
+            }
+            index -= <#ty>::__stylus_routed_count();
+        }
+    });
+
+    let inherit_reentrancy = inherits.iter().map(|ty| {
+        quote! {
+            if let Some(allow) = <#ty>::__stylus_allow_reentrant(selector) {
+                return Some(allow);
+            }
+        }
+    });
     let inherit_overrides = inherits.iter().map(|ty| {
         quote! {
             if !<#ty>::__stylus_allow_override(selector, purity) {
@@ -299,13 +788,14 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
             #[deny(unreachable_patterns)]
             fn route(storage: &mut S, selector: u32, input: &[u8]) -> Option<stylus_sdk::ArbResult> {
                 use stylus_sdk::{function_selector, alloy_sol_types::SolType};
-                use stylus_sdk::abi::{internal, internal::EncodableReturnType, AbiType, Router};
+                use stylus_sdk::abi::{internal, AbiType, IntoReturnData, Router};
                 use alloc::vec;
 
                 #[cfg(feature = "export-abi")]
                 use stylus_sdk::abi::export;
 
                 #selectors
+                #before_route
                 match selector {
                     #match_selectors
                     _ => {
@@ -314,6 +804,19 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                     }
                 }
             }
+
+            #[inline(always)]
+            fn fallback(storage: &mut S, input: &[u8]) -> Option<stylus_sdk::ArbResult> {
+                #[allow(unused_imports)]
+                use stylus_sdk::abi::{internal, Router};
+                #before_fallback
+                #fallback_route
+            }
+
+            #[inline(always)]
+            fn allow_reentrant(selector: u32) -> Option<bool> {
+                Self::__stylus_allow_reentrant(selector)
+            }
         }
 
         // implement checks for method overriding.
@@ -334,12 +837,105 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
                 true
             }
 
+            /// Lists every selector the contract routes, along with the method it belongs to.
+            ///
+            /// Selectors are listed in the order they're checked, so a selector appearing more than
+            /// once is routed to its first method, shadowing the rest.
+            pub fn selectors() -> alloc::vec::Vec<(stylus_sdk::alloy_primitives::FixedBytes<4>, &'static str)> {
+                use stylus_sdk::{alloy_primitives::FixedBytes, function_selector};
+                #selectors
+                let mut selectors = alloc::vec![#(#own_selectors),*];
+                #(#inherit_selectors)*
+                selectors
+            }
+
+            #[doc(hidden)]
+            /// Whether the method routed to by a selector may be called reentrantly, or `None` if
+            /// no method matches it. Like routing, this checks inherited types in order, but a
+            /// method without a reentrancy attribute takes that of any method it overrides.
+            pub const fn __stylus_allow_reentrant(selector: u32) -> Option<bool> {
+                use stylus_sdk::function_selector;
+                #selectors
+                #[allow(unused_mut)]
+                let mut own = None;
+                match selector {
+                    #reentrancy_selectors
+                    _ => {}
+                }
+                #(#inherit_reentrancy)*
+                own
+            }
+
+            #[doc(hidden)]
+            /// The ERC-165 interface id of the methods the type defines, excluding aliases, the
+            /// fallback, and any methods it inherits.
+            pub const fn __stylus_interface_id() -> u32 {
+                use stylus_sdk::function_selector;
+                #selectors
+                0 #(^ #interface_selectors)*
+            }
+
+            #[doc(hidden)]
+            /// The ERC-165 interface ids `#[implements]` advertises for the type.
+            pub const __STYLUS_INTERFACE_IDS: &'static [u32] = #implemented_ids;
+
+            #[doc(hidden)]
+            /// Whether the type has a fallback, of its own or inherited.
+            pub const fn __stylus_has_fallback() -> bool {
+                #has_fallback #(#inherit_has_fallback)*
+            }
+
+            #[doc(hidden)]
+            /// The number of methods routed, including shadowed ones.
+            pub const fn __stylus_routed_count() -> usize {
+                #routed_count #(#inherit_counts)*
+            }
+
+            #[doc(hidden)]
+            /// The routed method at the given index, in the order methods are checked.
+            pub const fn __stylus_routed_method(index: usize) -> Option<stylus_sdk::abi::internal::RoutedMethod> {
+                use stylus_sdk::{abi::internal::RoutedMethod, function_selector};
+                #selectors
+                let routed: [RoutedMethod; #routed_count] = [#(#routed_methods),*];
+                if index < #routed_count {
+                    return Some(routed[index]);
+                }
+                let mut index = index - #routed_count;
+                #(#inherit_routed)*
+                None
+            }
+
+            #[doc(hidden)]
+            /// Finds a method shadowed by one with a different name that isn't marked `#[overrides]`,
+            /// returning the error to report.
+            pub const fn __stylus_selector_collision() -> Option<&'static str> {
+                use stylus_sdk::abi::internal::str_eq;
+                let mut index = 0;
+                while let Some(method) = Self::__stylus_routed_method(index) {
+                    let mut first = 0;
+                    while let Some(other) = Self::__stylus_routed_method(first) {
+                        if other.selector == method.selector {
+                            if !other.overrides && !str_eq(other.name, method.name) {
+                                return Some(method.shadowed);
+                            }
+                            break;
+                        }
+                        first += 1;
+                    }
+                    index += 1;
+                }
+                None
+            }
+
             #[doc(hidden)]
             /// Check the functions defined in an entrypoint for valid overrides.
             pub const fn __stylus_assert_overrides() {
                 use stylus_sdk::function_selector;
                 #selectors
                 #(#check_overrides)*
+                if let Some(msg) = Self::__stylus_selector_collision() {
+                    panic!("{}", msg);
+                }
             }
         }
     };
@@ -349,15 +945,6 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
         return router.into();
     }
 
-    for item in input.items.iter_mut() {
-        let ImplItem::Method(method) = item else {
-            continue;
-        };
-        if let ReturnType::Type(_, ty) = &method.sig.output {
-            types.push(ty);
-        }
-    }
-
     let type_decls = quote! {
         let mut seen = HashSet::new();
         for item in ([] as [InnerType; 0]).iter() #(.chain(&<#types as InnerTypes>::inner_types()))* {
@@ -385,7 +972,7 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
         false => quote! { write!(f, " is ")?; },
     };
     is_clause.extend(inherits.iter().enumerate().map(|(i, ty)| {
-        let comma = (i > 0).then_some(", ").unwrap_or_default();
+        let comma = if i > 0 { ", " } else { "" };
         quote! {
             write!(f, "{}I{}", #comma, <#ty as GenerateAbi>::NAME)?;
         }
@@ -415,6 +1002,96 @@ pub fn public(attr: TokenStream, input: TokenStream) -> TokenStream {
     router.into()
 }
 
+/// Describes a method for the selector collision checks.
+fn routed_method(
+    constant: &Ident,
+    type_name: &str,
+    name: &str,
+    overrides: bool,
+) -> proc_macro2::TokenStream {
+    let shadowed = format!(
+        "`{type_name}::{name}` is shadowed by a method with a different name but the same \
+         selector. Mark the shadowing method #[overrides] if this is intended"
+    );
+    quote! {
+        RoutedMethod {
+            selector: #constant,
+            name: #name,
+            overrides: #overrides,
+            shadowed: #shadowed,
+        }
+    }
+}
+
+/// Whether a type refers to the given type parameter.
+fn mentions(ty: &Type, param: &Ident) -> bool {
+    fn search(tokens: TokenStream2, param: &Ident) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == *param,
+            TokenTree::Group(group) => search(group.stream(), param),
+            _ => false,
+        })
+    }
+    search(quote!(#ty), param)
+}
+
+/// Replaces a type parameter with a concrete type.
+fn substitute(ty: &Type, param: &Ident, concrete: &Type) -> Type {
+    fn replace(tokens: TokenStream2, param: &Ident, concrete: &Type) -> TokenStream2 {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Ident(ident) if ident == *param => quote!(#concrete),
+                TokenTree::Group(group) => {
+                    let stream = replace(group.stream(), param, concrete);
+                    let mut replaced = Group::new(group.delimiter(), stream);
+                    replaced.set_span(group.span());
+                    TokenTree::Group(replaced).into()
+                }
+                token => token.into(),
+            })
+            .collect()
+    }
+    syn::parse2(replace(quote!(#ty), param, concrete)).expect("substituted type should parse")
+}
+
+/// Argument types that borrow from calldata rather than copying it.
+#[derive(Clone, Copy)]
+enum Borrowed {
+    /// `&[u8]`, decoded as `bytes`.
+    Bytes,
+    /// `&str`, decoded as `string`.
+    Str,
+}
+
+impl Borrowed {
+    fn of(ty: &Type) -> Option<Self> {
+        let Type::Reference(reference) = ty else {
+            return None;
+        };
+        if reference.mutability.is_some() {
+            return None;
+        }
+        match &*reference.elem {
+            Type::Slice(slice) if is_path(&slice.elem, "u8") => Some(Self::Bytes),
+            elem if is_path(elem, "str") => Some(Self::Str),
+            _ => None,
+        }
+    }
+
+    /// The owned type with the same Solidity representation.
+    fn owned_type(self) -> Type {
+        match self {
+            Self::Bytes => syn::parse_quote!(stylus_sdk::abi::Bytes),
+            Self::Str => syn::parse_quote!(alloc::string::String),
+        }
+    }
+}
+
+fn is_path(ty: &Type, name: &str) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident(name))
+}
+
 struct InheritsAttr {
     types: Punctuated<Type, Token![,]>,
 }
@@ -2,14 +2,25 @@
 // For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
 
 use crate::storage::proc::{SolidityField, SolidityFields, SolidityStruct, SolidityStructs};
+use alloy_primitives::U256;
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
+use proc_macro2::TokenTree;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use sha3::{Digest, Keccak256};
 use std::mem;
-use syn::{parse_macro_input, punctuated::Punctuated, Index, ItemStruct, Token, Type};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Expr, Field, Fields, FieldsNamed, Ident, Index, ItemEnum, ItemStruct, LitStr, Meta, NestedMeta,
+    Token, Type,
+};
 
 mod proc;
 
-pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn storage(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as StorageArgs);
     let mut input = parse_macro_input!(input as ItemStruct);
 
     let name = &input.ident;
@@ -18,6 +29,10 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
     let mut init = quote! {};
     let mut size = quote! {};
     let mut borrows = quote! {};
+    let mut split_fields = vec![];
+    let mut explicit_slots = vec![];
+    let mut field_types = vec![];
+    let mut defaults = vec![];
 
     for (field_index, field) in input.fields.iter_mut().enumerate() {
         // deny complex types
@@ -25,9 +40,31 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
             error!(&field, "Type not supported for EVM state storage");
         };
 
-        // implement borrows
+        // implement borrows and find explicit slots
+        let mut slot = None;
+        let mut default = None;
         let attrs = mem::take(&mut field.attrs);
         for attr in attrs {
+            if attr.path.is_ident("default") {
+                if default.is_some() {
+                    error!(attr.path, "more than one default attribute");
+                }
+                default = match attr.parse_args::<Expr>() {
+                    Ok(expr) => Some(expr),
+                    Err(error) => error!(attr.path, "{}", error),
+                };
+                continue;
+            }
+            if attr.path.is_ident("slot") {
+                if slot.is_some() {
+                    error!(attr.path, "more than one slot attribute");
+                }
+                slot = match attr.parse_args::<Expr>() {
+                    Ok(expr) => Some(expr),
+                    Err(error) => error!(attr.path, "{}", error),
+                };
+                continue;
+            }
             if !attr.path.is_ident("borrow") {
                 field.attrs.push(attr);
                 continue;
@@ -54,6 +91,12 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
             });
         }
 
+        let field_name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => field_index.to_string(),
+        };
+        field_types.push((field_name, field.ty.clone()));
+
         let path = &ty.path.segments.last().unwrap().ident;
         let not_supported = format!("Type `{path}` not supported for EVM state storage");
 
@@ -75,8 +118,31 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
         }
 
         let Some(ident) = &field.ident else {
+            if let Some(slot) = slot {
+                error!(slot, "slot attribute requires a named field");
+            }
+            if let Some(default) = default {
+                error!(default, "default attribute requires a named field");
+            }
             continue;
         };
+        split_fields.push((field.vis.clone(), ident.clone(), field.ty.clone()));
+        if let Some(default) = default {
+            defaults.push((ident.clone(), default));
+        }
+
+        // fields at explicit slots are placed apart from the others
+        if let Some(slot) = slot {
+            init.extend(quote! {
+                #ident: {
+                    const SLOT: alloy_primitives::U256 = #slot;
+                    let bytes = <#ty as storage::StorageType>::SLOT_BYTES;
+                    <#ty as storage::StorageType>::new(SLOT, (32 - bytes) as u8)
+                },
+            });
+            explicit_slots.push((ident.clone(), field.ty.clone(), slot));
+            continue;
+        }
 
         init.extend(quote! {
             #ident: {
@@ -115,6 +181,190 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
         });
     }
 
+    // namespaced structs live at a slot derived from the namespace, per ERC-7201
+    let mut base = quote! { U256::ZERO };
+    let mut namespace = quote! {};
+    let mut set_root = quote! {};
+    if let Some(id) = &args.namespace {
+        let [l0, l1, l2, l3] = *erc7201_slot(&id.value()).as_limbs();
+        let vis = &input.vis;
+        let doc = format!(
+            "The root slot of the `{}` namespace, as computed by ERC-7201.",
+            id.value()
+        );
+        namespace = quote! {
+            #[doc = #doc]
+            #vis const NAMESPACE_SLOT: stylus_sdk::alloy_primitives::U256 =
+                stylus_sdk::alloy_primitives::U256::from_limbs([#l0, #l1, #l2, #l3]);
+        };
+        base = quote! { Self::NAMESPACE_SLOT };
+        set_root = quote! { root = Self::NAMESPACE_SLOT; };
+    }
+
+    // ensure explicit slots don't overlap each other or the rest of the struct
+    let mut check_slots = quote! {};
+    if !explicit_slots.is_empty() {
+        let mut checks = quote! {};
+        for (i, (ident, ty, slot)) in explicit_slots.iter().enumerate() {
+            let msg = format!("field `{ident}` of `{name}` overlaps its other fields");
+            let var = format_ident!("slot_{i}");
+            checks.extend(quote! {
+                let #var = (#slot, field_words::<#ty>());
+                assert!(!slots_overlap(base, sequential, #var.0, #var.1), #msg);
+            });
+            for (j, (other, _, _)) in explicit_slots[..i].iter().enumerate() {
+                let msg = format!("fields `{other}` and `{ident}` of `{name}` overlap");
+                let other_var = format_ident!("slot_{j}");
+                checks.extend(quote! {
+                    assert!(!slots_overlap(#other_var.0, #other_var.1, #var.0, #var.1), #msg);
+                });
+            }
+        }
+
+        check_slots = quote! {
+            #[doc(hidden)]
+            const __STYLUS_CHECK_SLOTS: () = {
+                use stylus_sdk::{abi::internal::slots_overlap, alloy_primitives::U256, storage};
+
+                const fn field_words<T: storage::StorageType>() -> usize {
+                    match T::REQUIRED_SLOTS {
+                        0 => 1,
+                        words => words,
+                    }
+                }
+
+                let mut total: usize = 0;
+                let mut space: usize = 32;
+                #size
+                if space != 32 {
+                    total += 1;
+                }
+                let sequential = total;
+                let base = #base;
+                #checks
+            };
+        };
+    }
+
+    // every instance of a type with fixed slots shares them, so it can't be held twice
+    let mut fixed_checks = quote! {};
+    for (i, (field, ty)) in field_types.iter().enumerate() {
+        let ty_string = quote!(#ty).to_string();
+        for (other, other_ty) in &field_types[..i] {
+            if quote!(#other_ty).to_string() != ty_string {
+                continue;
+            }
+            let msg = format!(
+                "fields `{other}` and `{field}` of `{name}` share the fixed slots of their type"
+            );
+            fixed_checks.extend(quote! {
+                assert!(!<#ty as stylus_sdk::storage::StorageType>::FIXED_SLOTS, #msg);
+            });
+        }
+    }
+    let mut check_fixed = quote! {};
+    if !fixed_checks.is_empty() {
+        check_fixed = quote! {
+            #[doc(hidden)]
+            const __STYLUS_CHECK_FIXED_SLOTS: () = {
+                #fixed_checks
+            };
+        };
+    }
+
+    let mut use_check = quote! {};
+    if !explicit_slots.is_empty() {
+        use_check.extend(quote! { let () = Self::__STYLUS_CHECK_SLOTS; });
+    }
+    if !fixed_checks.is_empty() {
+        use_check.extend(quote! { let () = Self::__STYLUS_CHECK_FIXED_SLOTS; });
+    }
+
+    // the struct has fixed slots if it's namespaced, or any of its fields do or are at explicit slots
+    let has_explicit = args.namespace.is_some() || !explicit_slots.is_empty();
+    let field_fixed = field_types.iter().map(|(_, ty)| {
+        quote! { || <#ty as stylus_sdk::storage::StorageType>::FIXED_SLOTS }
+    });
+
+    // apply default values once, tracking whether they have been at a slot derived from the root
+    let mut init_defaults = quote! {};
+    if !defaults.is_empty() {
+        let Fields::Named(fields) = &mut input.fields else {
+            unreachable!("defaults require named fields");
+        };
+        let root: FieldsNamed = parse_quote! {{
+            #[doc(hidden)]
+            __stylus_root: stylus_sdk::alloy_primitives::U256
+        }};
+        fields.named.extend(root.named);
+        init.extend(quote! { __stylus_root: root, });
+
+        let vis = &input.vis;
+        let assignments = defaults.iter().map(|(ident, expr)| {
+            quote! {
+                storage::SimpleStorageType::set_by_wrapped(&mut self.#ident, #expr);
+            }
+        });
+        init_defaults = quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Sets the fields marked `#[default]` to their default values, unless this has
+                /// been done before. Returns whether the defaults were applied.
+                #vis fn init_defaults(&mut self) -> bool {
+                    use stylus_sdk::{abi::internal, storage};
+
+                    let mut applied = internal::defaults_flag(self.__stylus_root);
+                    if applied.get() {
+                        return false;
+                    }
+                    applied.set(true);
+                    #(#assignments)*
+                    true
+                }
+            }
+        };
+    }
+
+    // borrow each field separately to get around the borrow checker
+    let mut split = quote! {};
+    if let Some(split_arg) = &args.split {
+        if !matches!(input.fields, Fields::Named(_)) {
+            error!(split_arg, "split requires named fields");
+        }
+        let vis = &input.vis;
+        let split_name = format_ident!("{}SplitMut", name);
+        let mut split_generics = input.generics.clone();
+        split_generics.params.insert(0, parse_quote!('a));
+        let (_, split_ty_generics, _) = split_generics.split_for_impl();
+        let split_where = &split_generics.where_clause;
+        let split_doc =
+            format!("Disjoint, simultaneously-usable mutable borrows of each field of [`{name}`].");
+
+        let decls = split_fields.iter().map(|(vis, ident, ty)| {
+            quote! {
+                #[doc = "Mutable borrow of the field of the same name."]
+                #vis #ident: &'a mut #ty
+            }
+        });
+        let idents = split_fields.iter().map(|(_, ident, _)| ident);
+
+        split = quote! {
+            #[doc = #split_doc]
+            #vis struct #split_name #split_generics #split_where {
+                #(#decls,)*
+            }
+
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Mutably borrows every field at once, so that accessors into one field can be held
+                /// while another is modified.
+                #vis fn split_mut<'a>(&'a mut self) -> #split_name #split_ty_generics {
+                    #split_name {
+                        #(#idents: &mut self.#idents,)*
+                    }
+                }
+            }
+        };
+    }
+
     let expanded = quote! {
         #input
 
@@ -129,6 +379,10 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
                 }
                 total
             }
+
+            #namespace
+            #check_slots
+            #check_fixed
         }
 
         impl #impl_generics stylus_sdk::storage::StorageType for #name #ty_generics #where_clause {
@@ -138,10 +392,13 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
             // start a new word
             const SLOT_BYTES: usize = 32;
             const REQUIRED_SLOTS: usize = Self::required_slots();
+            const FIXED_SLOTS: bool = #has_explicit #(#field_fixed)*;
 
             unsafe fn new(mut root: stylus_sdk::alloy_primitives::U256, offset: u8) -> Self {
                 use stylus_sdk::{storage, alloy_primitives};
                 debug_assert!(offset == 0);
+                #use_check
+                #set_root
 
                 let mut space: usize = 32;
                 let mut slot: usize = 0;
@@ -161,10 +418,62 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
         }
 
         #borrows
+        #split
+        #init_defaults
     };
     expanded.into()
 }
 
+/// Arguments to the [`storage`] attribute.
+struct StorageArgs {
+    namespace: Option<LitStr>,
+    split: Option<Ident>,
+}
+
+impl Parse for StorageArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut namespace = None;
+        let mut split = None;
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "split" {
+                if split.is_some() {
+                    error!(@ident, "more than one split");
+                }
+                split = Some(ident);
+                if !input.is_empty() {
+                    let _: Token![,] = input.parse()?;
+                }
+                continue;
+            }
+            let _: Token![=] = input.parse()?;
+            match ident.to_string().as_str() {
+                "namespace" => {
+                    let lit: LitStr = input.parse()?;
+                    if namespace.is_some() {
+                        error!(@lit, "more than one namespace");
+                    }
+                    namespace = Some(lit);
+                }
+                _ => error!(@ident, "unknown storage attribute"),
+            }
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        Ok(Self { namespace, split })
+    }
+}
+
+/// Computes `keccak256(abi.encode(uint256(keccak256(id)) - 1)) & ~bytes32(uint256(0xff))`.
+fn erc7201_slot(id: &str) -> U256 {
+    let hash = Keccak256::digest(id);
+    let location = U256::from_be_slice(&hash) - U256::from(1);
+    let mut slot = Keccak256::digest(location.to_be_bytes::<32>());
+    slot[31] = 0;
+    U256::from_be_slice(&slot)
+}
+
 pub fn sol_storage(input: TokenStream) -> TokenStream {
     let SolidityStructs(decls) = parse_macro_input!(input as SolidityStructs);
     let mut out = quote!();
@@ -201,17 +510,23 @@ pub fn sol_storage(input: TokenStream) -> TokenStream {
 }
 
 pub fn derive_erase(input: TokenStream) -> TokenStream {
-    let mut input = parse_macro_input!(input as ItemStruct);
+    let input = parse_macro_input!(input as ItemStruct);
     let name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let mut erase_fields = quote! {};
-    for field in &mut input.fields {
-        let ident = &field.ident;
-        erase_fields.extend(quote! {
-            self.#ident.erase();
+    for (index, field) in input.fields.iter().enumerate() {
+        if is_root(field) {
+            continue;
+        }
+        // call through the trait so that a field that can't be erased is reported at its type
+        let ty = &field.ty;
+        let accessor = field_accessor(field, index);
+        erase_fields.extend(quote_spanned! { ty.span() =>
+            <#ty as stylus_sdk::storage::Erase>::erase(&mut self.#accessor);
         });
     }
+    erase_fields.extend(clear_defaults(&input));
     quote! {
         impl #impl_generics stylus_sdk::storage::Erase for #name #ty_generics #where_clause {
             fn erase(&mut self) {
@@ -221,3 +536,276 @@ pub fn derive_erase(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+pub fn derive_storage_reset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemStruct);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut reset_fields = quote! {};
+    for (index, field) in input.fields.iter().enumerate() {
+        let ty = &field.ty;
+        if is_root(field) || contains_map(ty.to_token_stream()) {
+            continue;
+        }
+        let accessor = field_accessor(field, index);
+        reset_fields.extend(quote_spanned! { ty.span() =>
+            <#ty as stylus_sdk::storage::StorageReset>::reset(&mut self.#accessor);
+        });
+    }
+    reset_fields.extend(clear_defaults(&input));
+
+    let skipped = skipped_maps(&input);
+    let doc = match skipped.is_empty() {
+        true => "Erases every field.".to_string(),
+        false => format!(
+            "Erases every field but {}, which are maps and can't be erased.",
+            skipped.join(", ")
+        ),
+    };
+    quote! {
+        #[doc = #doc]
+        impl #impl_generics stylus_sdk::storage::StorageReset for #name #ty_generics #where_clause {
+            fn reset(&mut self) {
+                #reset_fields
+            }
+        }
+    }
+    .into()
+}
+
+/// Generates `reset()` for an entrypoint struct that derives [`Erase`](derive_erase) or
+/// [`StorageReset`](derive_storage_reset), which erases every field but maps, including those of
+/// nested structs. Since the struct needn't be `Erase` to be reset, deriving `Erase` is replaced
+/// with deriving `StorageReset`, so that it may have maps.
+pub fn entrypoint_reset(input: &mut ItemStruct) -> proc_macro2::TokenStream {
+    let mut derived = false;
+    for attr in &mut input.attrs {
+        if !attr.path.is_ident("derive") {
+            continue;
+        }
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            continue;
+        };
+        let paths: Vec<_> = list
+            .nested
+            .into_iter()
+            .map(|nested| match derive_name(&nested).as_deref() {
+                Some("Erase") => {
+                    derived = true;
+                    parse_quote!(stylus_sdk::stylus_proc::StorageReset)
+                }
+                Some("StorageReset") => {
+                    derived = true;
+                    nested
+                }
+                _ => nested,
+            })
+            .collect();
+        *attr = parse_quote! { #[derive(#(#paths),*)] };
+    }
+    if !derived {
+        return quote! {};
+    }
+
+    let skipped = skipped_maps(input);
+    let mut doc = "Erases the contract's storage.".to_string();
+    if !skipped.is_empty() {
+        doc += &format!(
+            " Maps can't be erased, so {} are left in place.",
+            skipped.join(", ")
+        );
+    }
+    doc += " So are the maps of nested structs, which their `StorageReset` implementations list.";
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #doc]
+            pub fn reset(&mut self) {
+                <Self as stylus_sdk::storage::StorageReset>::reset(self);
+            }
+        }
+    }
+}
+
+/// The name of a derived trait, without its path.
+fn derive_name(nested: &NestedMeta) -> Option<String> {
+    let NestedMeta::Meta(Meta::Path(path)) = nested else {
+        return None;
+    };
+    path.segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+}
+
+/// Whether the field is the root `#[storage]` adds for default values.
+fn is_root(field: &Field) -> bool {
+    matches!(&field.ident, Some(ident) if ident == "__stylus_root")
+}
+
+fn field_accessor(field: &Field, index: usize) -> proc_macro2::TokenStream {
+    match &field.ident {
+        Some(ident) => ident.into_token_stream(),
+        None => Index::from(index).into_token_stream(),
+    }
+}
+
+/// The names of a struct's fields that are or contain maps, which can't be erased.
+fn skipped_maps(input: &ItemStruct) -> Vec<String> {
+    let fields = input.fields.iter().enumerate();
+    let fields = fields.filter(|(_, field)| contains_map(field.ty.to_token_stream()));
+    fields
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => format!("`{ident}`"),
+            None => format!("`{index}`"),
+        })
+        .collect()
+}
+
+/// Clears the flag `init_defaults` sets if the struct has defaults, so that they can be applied
+/// again. Derives may see the struct before or after `#[storage]` adds its root field.
+fn clear_defaults(input: &ItemStruct) -> proc_macro2::TokenStream {
+    let has_defaults = input.fields.iter().any(|field| {
+        let mut attrs = field.attrs.iter();
+        is_root(field) || attrs.any(|attr| attr.path.is_ident("default"))
+    });
+    if !has_defaults {
+        return quote! {};
+    }
+    quote! {
+        stylus_sdk::storage::Erase::erase(
+            &mut stylus_sdk::abi::internal::defaults_flag(self.__stylus_root),
+        );
+    }
+}
+
+/// Whether a type mentions a map, like `StorageMap` or a vector of them.
+fn contains_map(tokens: proc_macro2::TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "StorageMap",
+        TokenTree::Group(group) => contains_map(group.stream()),
+        _ => false,
+    })
+}
+
+pub fn derive_storage_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemEnum);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    if input.variants.is_empty() {
+        error!(name, "enum must have at least one variant");
+    }
+    if input.variants.len() > 256 {
+        error!(
+            name,
+            "enum must have at most 256 variants to fit in a uint8"
+        );
+    }
+
+    let mut to_u8 = quote! {};
+    let mut from_u8 = quote! {};
+    for (index, variant) in input.variants.iter().enumerate() {
+        if !matches!(variant.fields, Fields::Unit) {
+            error!(variant.fields, "variants must not have fields");
+        }
+        if let Some((_, discriminant)) = &variant.discriminant {
+            error!(
+                discriminant,
+                "variants are numbered in order and can't set discriminants"
+            );
+        }
+        let ident = &variant.ident;
+        let index = index as u8;
+        to_u8.extend(quote! { Self::#ident => #index, });
+        from_u8.extend(quote! { #index => Some(Self::#ident), });
+    }
+
+    // unvalidated decoding can't fail, so out-of-range discriminants are clamped
+    let last = &input.variants.last().unwrap().ident;
+
+    let sol_uint8 = quote!(stylus_sdk::alloy_sol_types::sol_data::Uint<8>);
+    let sol_type = quote!(stylus_sdk::alloy_sol_types::SolType);
+    let storage_enum = quote!(stylus_sdk::storage::StorageEnum);
+
+    let mut inner_types = quote! {};
+    if cfg!(feature = "export-abi") {
+        inner_types = quote! {
+            impl #impl_generics stylus_sdk::abi::export::internal::InnerTypes for #name #ty_generics #where_clause {}
+        };
+    }
+
+    quote! {
+        impl #impl_generics #storage_enum for #name #ty_generics #where_clause {
+            fn to_u8(&self) -> u8 {
+                match self {
+                    #to_u8
+                }
+            }
+
+            fn from_u8(value: u8) -> Option<Self> {
+                match value {
+                    #from_u8
+                    _ => None,
+                }
+            }
+        }
+
+        impl #impl_generics stylus_sdk::alloy_sol_types::SolValue for #name #ty_generics #where_clause {
+            type SolType = Self;
+        }
+
+        impl #impl_generics stylus_sdk::alloy_sol_types::private::SolTypeValue<Self> for #name #ty_generics #where_clause {
+            #[inline]
+            fn stv_to_tokens(&self) -> <Self as #sol_type>::Token<'_> {
+                <#sol_uint8 as #sol_type>::tokenize(&#storage_enum::to_u8(self))
+            }
+
+            #[inline]
+            fn stv_abi_encoded_size(&self) -> usize {
+                <#sol_uint8 as #sol_type>::abi_encoded_size(&#storage_enum::to_u8(self))
+            }
+
+            #[inline]
+            fn stv_eip712_data_word(&self) -> stylus_sdk::alloy_sol_types::Word {
+                <#sol_uint8 as #sol_type>::eip712_data_word(&#storage_enum::to_u8(self))
+            }
+
+            #[inline]
+            fn stv_abi_encode_packed_to(&self, out: &mut stylus_sdk::alloy_sol_types::private::Vec<u8>) {
+                <#sol_uint8 as #sol_type>::abi_encode_packed_to(&#storage_enum::to_u8(self), out)
+            }
+        }
+
+        impl #impl_generics #sol_type for #name #ty_generics #where_clause {
+            type RustType = Self;
+
+            type Token<'a> = <#sol_uint8 as #sol_type>::Token<'a>;
+
+            const SOL_NAME: &'static str = <#sol_uint8 as #sol_type>::SOL_NAME;
+
+            const ENCODED_SIZE: Option<usize> = <#sol_uint8 as #sol_type>::ENCODED_SIZE;
+
+            fn valid_token(token: &Self::Token<'_>) -> bool {
+                <#sol_uint8 as #sol_type>::valid_token(token)
+                    && <Self as #storage_enum>::from_u8(<#sol_uint8 as #sol_type>::detokenize(*token)).is_some()
+            }
+
+            fn detokenize(token: Self::Token<'_>) -> Self::RustType {
+                let value = <#sol_uint8 as #sol_type>::detokenize(token);
+                <Self as #storage_enum>::from_u8(value).unwrap_or(Self::#last)
+            }
+        }
+
+        impl #impl_generics stylus_sdk::abi::AbiType for #name #ty_generics #where_clause {
+            type SolType = Self;
+
+            const ABI: stylus_sdk::abi::ConstString = stylus_sdk::abi::ConstString::new("uint8");
+        }
+
+        #inner_types
+    }
+    .into()
+}
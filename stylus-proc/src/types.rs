@@ -9,8 +9,9 @@ use syn::Token;
 use syn_solidity::Type;
 
 /// The purity of a Solidity method
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Purity {
+    #[default]
     Pure,
     View,
     Write,
@@ -29,12 +30,6 @@ impl Purity {
     }
 }
 
-impl Default for Purity {
-    fn default() -> Self {
-        Self::Pure
-    }
-}
-
 impl FromStr for Purity {
     type Err = ();
 
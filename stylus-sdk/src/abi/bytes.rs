@@ -6,15 +6,77 @@ use crate::{
     util::evm_padded_length,
 };
 use alloc::vec::Vec;
-use alloy_sol_types::{abi::token::PackedSeqToken, private::SolTypeValue, SolType, SolValue};
+use alloy_primitives::{keccak256, FixedBytes};
+use alloy_sol_types::{
+    abi::token::{PackedSeqToken, WordToken},
+    private::SolTypeValue,
+    EventTopic, SolType, SolValue,
+};
 use core::ops::{Deref, DerefMut};
 
 /// Represents a [`bytes`] in Solidity.
 ///
+/// Byte vectors map to Solidity types as follows, both as arguments and as return values.
+///
+/// | Rust type                     | Solidity type |
+/// |-------------------------------|---------------|
+/// | [`Bytes`]                     | `bytes`       |
+/// | [`alloy_primitives::Bytes`]   | `bytes`       |
+/// | [`FixedBytes<N>`]             | `bytesN`      |
+/// | [`Vec<u8>`]                   | `uint8[]`     |
+///
+/// In particular, a [`Vec<u8>`] is an array of integers, which encodes each byte as a full
+/// 32-byte word, so methods meant to take or return `bytes` should use this type instead.
+/// Conversions to and from [`Vec<u8>`] are free.
+///
+/// ```
+/// use stylus_sdk::abi::Bytes;
+///
+/// let bytes = Bytes::from_vec(vec![1, 2, 3]);
+/// assert_eq!(bytes.into_vec(), vec![1, 2, 3]);
+/// ```
+///
 /// [`bytes`]: https://docs.soliditylang.org/en/latest/types.html#bytes-and-string-as-arrays
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Bytes(pub Vec<u8>);
 
+impl Bytes {
+    /// Wraps a vector as Solidity `bytes`.
+    pub const fn from_vec(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Unwraps the underlying vector.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Copies `N` bytes starting at `offset`, returning `None` if the input is too short.
+    ///
+    /// ```
+    /// use stylus_sdk::{abi::Bytes, alloy_primitives::FixedBytes};
+    ///
+    /// let bytes = Bytes(vec![1, 2, 3, 4]);
+    /// assert_eq!(bytes.take::<2>(1), Some(FixedBytes([2, 3])));
+    /// assert_eq!(bytes.take::<2>(3), None);
+    /// ```
+    pub fn take<const N: usize>(&self, offset: usize) -> Option<FixedBytes<N>> {
+        let end = offset.checked_add(N)?;
+        let slice = self.0.get(offset..end)?;
+        Some(FixedBytes::from_slice(slice))
+    }
+
+    /// Divides the bytes in two at `mid`, returning `None` if `mid` is out of bounds.
+    ///
+    /// Unlike [`slice::split_at`], this never panics.
+    pub fn try_split_at(&self, mid: usize) -> Option<(&[u8], &[u8])> {
+        if mid > self.0.len() {
+            return None;
+        }
+        Some(self.0.split_at(mid))
+    }
+}
+
 impl From<Bytes> for Vec<u8> {
     fn from(value: Bytes) -> Self {
         value.0
@@ -27,6 +89,24 @@ impl From<Vec<u8>> for Bytes {
     }
 }
 
+impl From<&[u8]> for Bytes {
+    fn from(b: &[u8]) -> Self {
+        Self(b.to_vec())
+    }
+}
+
+impl From<alloy_primitives::Bytes> for Bytes {
+    fn from(b: alloy_primitives::Bytes) -> Self {
+        Self(b.into())
+    }
+}
+
+impl From<Bytes> for alloy_primitives::Bytes {
+    fn from(value: Bytes) -> Self {
+        value.0.into()
+    }
+}
+
 impl Deref for Bytes {
     type Target = Vec<u8>;
 
@@ -104,6 +184,28 @@ impl SolValue for Bytes {
     type SolType = Self;
 }
 
+impl EventTopic for Bytes {
+    #[inline]
+    fn topic_preimage_length(rust: &Self::RustType) -> usize {
+        evm_padded_length(rust.len())
+    }
+
+    #[inline]
+    fn encode_topic_preimage(rust: &Self::RustType, out: &mut Vec<u8>) {
+        // the contents, right-padded to a word without a length
+        out.extend_from_slice(rust);
+        out.resize(
+            out.len() + Self::topic_preimage_length(rust) - rust.len(),
+            0,
+        );
+    }
+
+    #[inline]
+    fn encode_topic(rust: &Self::RustType) -> WordToken {
+        WordToken(keccak256(rust))
+    }
+}
+
 impl AbiType for Bytes {
     type SolType = Self;
 
@@ -113,3 +215,50 @@ impl AbiType for Bytes {
 
     const EXPORT_ABI_RET: ConstString = Self::ABI.concat(ConstString::new(" memory"));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_take() {
+        let bytes = Bytes((0..40).collect());
+        assert_eq!(bytes.take::<4>(0), Some(FixedBytes([0, 1, 2, 3])));
+        assert_eq!(bytes.take::<32>(8).unwrap()[31], 39);
+        assert_eq!(bytes.take::<32>(9), None);
+        assert_eq!(bytes.take::<0>(40), Some(FixedBytes([])));
+        assert_eq!(bytes.take::<1>(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let bytes = Bytes::from(&[1, 2, 3][..]);
+        assert_eq!(bytes, Bytes::from_vec(vec![1, 2, 3]));
+        assert_eq!(Vec::from(bytes.clone()), vec![1, 2, 3]);
+
+        let alloy = alloy_primitives::Bytes::from(bytes.clone());
+        assert_eq!(alloy[..], [1, 2, 3]);
+        assert_eq!(Bytes::from(alloy), bytes);
+    }
+
+    #[test]
+    fn test_exported_types() {
+        assert_eq!(Bytes::EXPORT_ABI_ARG.as_str(), "bytes calldata");
+        assert_eq!(Bytes::EXPORT_ABI_RET.as_str(), "bytes memory");
+        assert_eq!(
+            alloy_primitives::Bytes::EXPORT_ABI_RET.as_str(),
+            "bytes memory"
+        );
+        assert_eq!(<Vec<u8>>::EXPORT_ABI_ARG.as_str(), "uint8[] memory");
+        assert_eq!(<Vec<u8>>::EXPORT_ABI_RET.as_str(), "uint8[] memory");
+    }
+
+    #[test]
+    fn test_try_split_at() {
+        let bytes = Bytes(vec![1, 2, 3]);
+        assert_eq!(bytes.try_split_at(1), Some((&[1][..], &[2, 3][..])));
+        assert_eq!(bytes.try_split_at(3), Some((&[1, 2, 3][..], &[][..])));
+        assert_eq!(bytes.try_split_at(4), None);
+    }
+}
@@ -7,8 +7,9 @@
 use core::any::TypeId;
 
 use alloy_primitives::{Address, FixedBytes, Signed, Uint};
+use alloy_sol_types::SolError;
 
-use crate::abi::Bytes;
+use crate::abi::{Bytes, Output};
 
 /// Represents a unique Solidity Type.
 pub struct InnerType {
@@ -51,6 +52,35 @@ impl<const N: usize, T: InnerTypes> InnerTypes for [T; N] {
     }
 }
 
+/// Declares the error held by a variant of an enum deriving `SolidityError`.
+pub trait ErrorType {
+    /// The error's declaration, if it has one.
+    fn error_type() -> Option<InnerType>;
+}
+
+impl<T: SolError + 'static> ErrorType for T {
+    fn error_type() -> Option<InnerType> {
+        Some(InnerType {
+            name: format!("error {};", T::SIGNATURE.replace(',', ", ")),
+            id: TypeId::of::<T>(),
+        })
+    }
+}
+
+/// Catch-all variants may hold any revert data, so there's nothing to declare.
+impl ErrorType for crate::call::Error {
+    fn error_type() -> Option<InnerType> {
+        None
+    }
+}
+
+/// Solidity's builtin `Panic` isn't declared.
+impl ErrorType for crate::math::MathError {
+    fn error_type() -> Option<InnerType> {
+        None
+    }
+}
+
 macro_rules! impl_inner {
     ($ty:ident $($rest:ident)+) => {
         impl_inner!($ty);
@@ -61,7 +91,7 @@ macro_rules! impl_inner {
     };
 }
 
-impl_inner!(bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 String Address Bytes);
+impl_inner!(bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 String Address Bytes Output);
 
 impl<const B: usize, const L: usize> InnerTypes for Uint<B, L> {}
 impl<const B: usize, const L: usize> InnerTypes for Signed<B, L> {}
@@ -4,7 +4,10 @@
 use super::{AbiType, ConstString};
 use alloc::{string::String, vec::Vec};
 use alloy_primitives::{Address, FixedBytes, Signed, Uint};
-use alloy_sol_types::sol_data::{self, ByteCount, IntBitCount, SupportedFixedBytes, SupportedInt};
+use alloy_sol_types::{
+    sol_data::{self, ByteCount, IntBitCount, SupportedFixedBytes, SupportedInt},
+    SolType,
+};
 
 /// Generates a test to ensure the two-way relationship between Rust Types and Sol Types is bijective.
 macro_rules! test_type {
@@ -50,8 +53,25 @@ where
     const ABI: ConstString = append_dec!("bytes", N);
 }
 
+test_type!(bytes1, "bytes1", FixedBytes<1>);
+test_type!(bytes3, "bytes3", FixedBytes<3>);
+test_type!(bytes17, "bytes17", FixedBytes<17>);
+test_type!(bytes32, "bytes32", FixedBytes<32>);
+
 test_type!(bytes, "bytes calldata", super::Bytes);
 
+impl AbiType for alloy_primitives::Bytes {
+    type SolType = sol_data::Bytes;
+
+    const ABI: ConstString = ConstString::new("bytes");
+
+    const EXPORT_ABI_ARG: ConstString = append!(Self::ABI, " calldata");
+
+    const EXPORT_ABI_RET: ConstString = append!(Self::ABI, " memory");
+}
+
+test_type!(alloy_bytes, "bytes calldata", alloy_primitives::Bytes);
+
 impl<const BITS: usize, const LIMBS: usize> AbiType for Uint<BITS, LIMBS>
 where
     IntBitCount<BITS>: SupportedInt<Uint = Self>,
@@ -185,6 +205,12 @@ impl AbiType for () {
 
 test_type!(empty_tuple, "()", ());
 
+/// Tokenizes a value as its [`SolType`], which is generic over the latter so that the value's
+/// conversion is known to exist.
+pub(super) fn tokenize<S: SolType>(value: &S::RustType) -> S::Token<'_> {
+    S::tokenize(value)
+}
+
 macro_rules! impl_tuple {
     () => {};
     ($first:ident $(, $rest:ident)*) => {
@@ -216,6 +242,17 @@ macro_rules! impl_tuple {
                 .concat(ConstString::new(")"));
 
             const CAN_BE_CALLDATA: bool = false;
+
+            #[allow(non_snake_case)]
+            fn abi_encode_returns(&self) -> Vec<u8> {
+                let ( $first $(, $rest)* , ) = self;
+                let tokens = (
+                    tokenize::<$first::SolType>($first)
+                    $(, tokenize::<$rest::SolType>($rest))*
+                    ,
+                );
+                alloy_sol_types::abi::encode_params(&tokens)
+            }
         }
 
         impl_tuple! { $($rest),* }
@@ -245,3 +282,35 @@ test_type!(
         [Vec<bool>; 8],
     )
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::IntoReturnData;
+    use alloy_sol_types::SolType;
+
+    /// Checks that `bytesN` values are right-padded, both as arguments and as return values.
+    fn check_fixed_bytes<const N: usize>()
+    where
+        ByteCount<N>: SupportedFixedBytes,
+    {
+        let value = FixedBytes::<N>::repeat_byte(0xab);
+        let mut word = [0; 32];
+        word[..N].fill(0xab);
+
+        assert_eq!(value.into_return_data(), Ok(word.to_vec()), "bytes{N}");
+        let (decoded,) = <(FixedBytes<N>,) as AbiType>::SolType::abi_decode_params(&word, true)
+            .unwrap_or_else(|err| panic!("failed to decode bytes{N}: {err}"));
+        assert_eq!(decoded, value, "bytes{N}");
+    }
+
+    #[test]
+    fn test_fixed_bytes_encoding() {
+        check_fixed_bytes::<1>();
+        check_fixed_bytes::<3>();
+        check_fixed_bytes::<4>();
+        check_fixed_bytes::<17>();
+        check_fixed_bytes::<31>();
+        check_fixed_bytes::<32>();
+    }
+}
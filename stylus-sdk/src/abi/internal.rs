@@ -4,40 +4,19 @@
 //! This module provides functions for code generated by `stylus-sdk-proc`.
 //! Most users shouldn't call these.
 
-use crate::{abi::AbiType, console, msg, ArbResult};
+use crate::{
+    abi::AbiType,
+    console, crypto, msg,
+    storage::{StorageBool, StorageType},
+};
 use alloc::{vec, vec::Vec};
-use alloy_primitives::U256;
-use alloy_sol_types::SolType;
+use alloy_primitives::{FixedBytes, U256};
+use alloy_sol_types::{
+    abi::{token::PackedSeqToken, TokenSeq},
+    SolType,
+};
 use core::fmt;
 
-pub trait EncodableReturnType {
-    fn encode(self) -> ArbResult;
-}
-
-impl<T> EncodableReturnType for T
-where
-    T: AbiType + alloy_sol_types::private::SolTypeValue<<T as AbiType>::SolType>,
-{
-    #[inline(always)]
-    fn encode(self) -> ArbResult {
-        // coerce types into a tuple of at least 1 element
-        Ok(<<T as AbiType>::SolType>::abi_encode(&self))
-    }
-}
-
-impl<T, E: Into<Vec<u8>>> EncodableReturnType for Result<T, E>
-where
-    T: AbiType + alloy_sol_types::private::SolTypeValue<<T as AbiType>::SolType>,
-{
-    #[inline(always)]
-    fn encode(self) -> ArbResult {
-        match self {
-            Ok(result) => result.encode(),
-            Err(err) => Err(err.into()),
-        }
-    }
-}
-
 #[inline(always)]
 pub const fn digest_to_selector(digest: [u8; 32]) -> [u8; 4] {
     let mut selector = [0u8; 4];
@@ -57,6 +36,49 @@ pub fn deny_value(method_name: &str) -> Result<(), Vec<u8>> {
     Err(vec![])
 }
 
+alloy_sol_types::sol! {
+    /// Reverts a call whose calldata exceeds a method's `#[max_calldata]` limit.
+    error CalldataTooLarge(uint256 limit, uint256 length);
+}
+
+/// Checks the length of the calldata, including its 4-byte selector, against the given limit.
+#[allow(unused)]
+pub fn check_calldata_size(limit: usize, input: &[u8]) -> Result<(), Vec<u8>> {
+    let length = input.len() + 4;
+    if length <= limit {
+        return Ok(());
+    }
+    let error = CalldataTooLarge {
+        limit: U256::from(limit),
+        length: U256::from(length),
+    };
+    Err(alloy_sol_types::SolError::abi_encode(&error))
+}
+
+/// Answers `supportsInterface(bytes4)` for a router declaring the given interface ids, and those of
+/// the types it implements.
+#[allow(unused)]
+pub fn supports_interface(id: FixedBytes<4>, declared: &[u32], implemented: &[&[u32]]) -> bool {
+    let id = u32::from_be_bytes(id.0);
+    let implements = implemented.iter().any(|ids| ids.contains(&id));
+    id == super::ERC165_INTERFACE_ID || (id != 0xffffffff && (declared.contains(&id) || implements))
+}
+
+/// Decodes and validates the tokens of a method's arguments, which borrow from `input`.
+pub fn decode_tokens<'de, T: SolType>(input: &'de [u8]) -> alloy_sol_types::Result<T::Token<'de>>
+where
+    T::Token<'de>: TokenSeq<'de>,
+{
+    let tokens = alloy_sol_types::abi::decode_params::<T::Token<'de>>(input, true)?;
+    T::type_check(&tokens)?;
+    Ok(tokens)
+}
+
+/// Borrows a `string` argument from calldata, which must be valid UTF-8.
+pub fn borrow_str(token: PackedSeqToken<'_>) -> alloy_sol_types::Result<&str> {
+    core::str::from_utf8(token.0).map_err(|_| alloy_sol_types::Error::custom("invalid utf-8"))
+}
+
 #[allow(unused)]
 pub fn failed_to_decode_arguments(err: alloy_sol_types::Error) {
     console!("failed to decode arguments: {err}");
@@ -66,14 +88,18 @@ pub trait AbiResult {
     type OkType;
 }
 
-impl<O, E> AbiResult for Result<O, E> {
-    type OkType = O;
+impl<O: AbiResult, E> AbiResult for Result<O, E> {
+    type OkType = O::OkType;
 }
 
 impl<T: AbiType> AbiResult for T {
     type OkType = T;
 }
 
+impl AbiResult for super::Output {
+    type OkType = super::Bytes;
+}
+
 pub fn write_solidity_returns<T: AbiResult>(f: &mut fmt::Formatter) -> fmt::Result
 where
     T::OkType: AbiType,
@@ -81,9 +107,152 @@ where
     let abi = T::OkType::EXPORT_ABI_RET.as_str();
     if abi == "()" {
         Ok(())
-    } else if abi.starts_with('(') {
+    } else if is_tuple(abi) {
         write!(f, " returns {abi}")
     } else {
         write!(f, " returns ({abi})")
     }
 }
+
+/// Whether an ABI type is a tuple, which methods return as multiple values.
+/// Arrays of tuples, like `(uint8,bool)[]`, are single values.
+pub(crate) fn is_tuple(abi: &str) -> bool {
+    abi.starts_with('(') && abi.ends_with(')')
+}
+
+/// The flag recording whether `init_defaults` has run for the `#[storage]` struct at `root`, which
+/// lives at `keccak256(root ++ "stylus.storage.defaults")`.
+pub fn defaults_flag(root: U256) -> StorageBool {
+    const SUFFIX: &[u8] = b"stylus.storage.defaults";
+    let mut data = [0; 32 + SUFFIX.len()];
+    data[..32].copy_from_slice(&root.to_be_bytes::<32>());
+    data[32..].copy_from_slice(SUFFIX);
+    unsafe { StorageBool::new(crypto::keccak(data).into(), 31) }
+}
+
+/// Whether the ranges of storage slots `[a, a + a_len)` and `[b, b + b_len)` overlap.
+pub const fn slots_overlap(a: U256, a_len: usize, b: U256, b_len: usize) -> bool {
+    const fn within(start: U256, slot: U256, len: usize) -> bool {
+        let [low, rest @ ..] = *slot.wrapping_sub(start).as_limbs();
+        matches!(rest, [0, 0, 0]) && low < len as u64
+    }
+    a_len > 0 && b_len > 0 && (within(a, b, a_len) || within(b, a, b_len))
+}
+
+/// A method routed by a [`#[public]`][public] type, as listed for collision checks.
+///
+/// [public]: crate::prelude::public
+#[derive(Clone, Copy, Debug)]
+pub struct RoutedMethod {
+    pub selector: u32,
+    /// The Rust name of the method.
+    pub name: &'static str,
+    /// Whether the method is marked `#[overrides]`, allowing it to shadow differently named ones.
+    pub overrides: bool,
+    /// The error reported if the method is shadowed by one with a different name.
+    pub shadowed: &'static str,
+}
+
+/// Compares strings in a `const` context.
+pub const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolError;
+
+    #[test]
+    fn test_check_calldata_size() {
+        assert_eq!(check_calldata_size(36, &[0; 32]), Ok(()));
+        assert_eq!(check_calldata_size(68, &[0; 32]), Ok(()));
+
+        let err = check_calldata_size(35, &[0; 32]).unwrap_err();
+        assert_eq!(err[..4], CalldataTooLarge::SELECTOR);
+        let decoded = CalldataTooLarge::abi_decode(&err, true).unwrap();
+        assert_eq!(decoded.limit, U256::from(35));
+        assert_eq!(decoded.length, U256::from(36));
+    }
+
+    #[test]
+    fn test_supports_interface() {
+        let declared = [0x80ac58cd, 0xffffffff];
+        let implemented: &[&[u32]] = &[&[0x5b5e139f]];
+        assert!(supports_interface(
+            0x01ffc9a7_u32.to_be_bytes().into(),
+            &[],
+            &[]
+        ));
+        assert!(supports_interface(
+            0x80ac58cd_u32.to_be_bytes().into(),
+            &declared,
+            &[]
+        ));
+        assert!(!supports_interface(
+            0x5b5e139f_u32.to_be_bytes().into(),
+            &declared,
+            &[]
+        ));
+        assert!(supports_interface(
+            0x5b5e139f_u32.to_be_bytes().into(),
+            &declared,
+            implemented
+        ));
+
+        // ERC-165 requires rejecting the invalid id, even if it's declared
+        assert!(!supports_interface(FixedBytes([0xff; 4]), &declared, &[]));
+        assert!(!supports_interface(
+            FixedBytes([0xff; 4]),
+            &[],
+            &[&[0xffffffff]]
+        ));
+    }
+
+    #[test]
+    fn test_write_solidity_returns() {
+        use alloc::{string::String, string::ToString};
+        use alloy_primitives::Address;
+        use core::marker::PhantomData;
+
+        struct Returns<T>(PhantomData<T>);
+
+        impl<T: AbiResult> fmt::Display for Returns<T>
+        where
+            T::OkType: AbiType,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write_solidity_returns::<T>(f)
+            }
+        }
+
+        fn returns<T: AbiResult>() -> String
+        where
+            T::OkType: AbiType,
+        {
+            Returns::<T>(PhantomData).to_string()
+        }
+
+        assert_eq!(returns::<()>(), "");
+        assert_eq!(returns::<U256>(), " returns (uint256)");
+        assert_eq!(
+            returns::<(String, U256)>(),
+            " returns (string memory, uint256)"
+        );
+        assert_eq!(
+            returns::<Vec<(Address, U256)>>(),
+            " returns ((address,uint256)[] memory)"
+        );
+    }
+}
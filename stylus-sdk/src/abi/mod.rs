@@ -15,21 +15,33 @@
 //! [prelude]: crate::prelude
 
 use crate::{storage::TopLevelStorage, ArbResult};
-use alloy_sol_types::SolType;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::{abi::TokenSeq, EventTopic, SolType};
 use core::borrow::BorrowMut;
 
 pub use bytes::{Bytes, BytesSolType};
 pub use const_string::ConstString;
+pub use output::Output;
+pub use returns::IntoReturnData;
 
 #[cfg(feature = "export-abi")]
 pub use export::GenerateAbi;
 
+#[cfg(feature = "stylus-test")]
+pub use roundtrip::{assert_roundtrip, assert_roundtrip_arbitrary};
+
 #[cfg(feature = "export-abi")]
 pub mod export;
 
 mod bytes;
 mod const_string;
 mod impls;
+mod output;
+mod returns;
+
+#[cfg(feature = "stylus-test")]
+mod roundtrip;
 
 #[doc(hidden)]
 pub mod internal;
@@ -48,6 +60,23 @@ where
     /// Routes add via `#[inherit]` will only execute if no match is found among `Self`.
     /// This means that it is possible to override a method by redefining it in `Self`.
     fn route(storage: &mut S, selector: u32, input: &[u8]) -> Option<ArbResult>;
+
+    /// Handles a call that no method matches, given its full calldata, including any selector.
+    /// Returns `None` if there's no fallback, in which case the call reverts.
+    ///
+    /// The fallback is a method marked `#[fallback]`, or else that of the first type inherited
+    /// via `#[inherit]` to have one.
+    fn fallback(storage: &mut S, calldata: &[u8]) -> Option<ArbResult> {
+        let _ = (storage, calldata);
+        None
+    }
+
+    /// Whether the method routed to by a selector may be called reentrantly, or `None` if no
+    /// method matches it, in which case the `reentrant` feature decides.
+    fn allow_reentrant(selector: u32) -> Option<bool> {
+        let _ = selector;
+        None
+    }
 }
 
 /// Provides a mapping of Rust to Solidity types.
@@ -69,6 +98,13 @@ pub trait AbiType {
 
     /// Whether the type is allowed in calldata
     const CAN_BE_CALLDATA: bool = true;
+
+    /// Encodes the value as a method's return data. Tuples are returned as multiple values, so
+    /// they're encoded as a parameter list rather than as a single value.
+    #[doc(hidden)]
+    fn abi_encode_returns(&self) -> Vec<u8> {
+        alloy_sol_types::abi::encode(&impls::tokenize::<Self::SolType>(self))
+    }
 }
 
 /// Generates a function selector for the given method and its args.
@@ -97,6 +133,122 @@ macro_rules! function_selector {
     }};
 }
 
+/// The [ERC-165] interface id of `supportsInterface(bytes4)` itself.
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+pub const ERC165_INTERFACE_ID: u32 = 0x01ffc9a7;
+
+/// Computes an [ERC-165] interface id by XOR-ing together the given selectors.
+///
+/// Though ERC-165 defines ids for sets of functions, the same construction works for custom
+/// errors, whose selectors are computed the same way. This makes it possible to advertise the
+/// errors a contract may revert with via [`#[supports_interfaces]`][supports_interfaces].
+///
+/// ```
+/// use stylus_sdk::{abi::interface_id, function_selector};
+/// use alloy_primitives::{Address, U256};
+///
+/// const IERC20_ERRORS: u32 = interface_id(&[
+///     function_selector!("InsufficientBalance", Address, U256, U256),
+///     function_selector!("InsufficientAllowance", Address, Address, U256, U256),
+/// ]);
+/// ```
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+/// [supports_interfaces]: macro@crate::prelude::public
+pub const fn interface_id(selectors: &[[u8; 4]]) -> u32 {
+    let mut id = 0;
+    let mut i = 0;
+    while i < selectors.len() {
+        id ^= u32::from_be_bytes(selectors[i]);
+        i += 1;
+    }
+    id
+}
+
+/// Builds the calldata for a call, prepending the `selector` to the ABI encoding of `args`.
+///
+/// The arguments are a tuple of Rust types, mapped to Solidity ones via [`AbiType`] just as for
+/// [`function_selector`]. The result is what [`sol_interface!`][sol_interface] methods send.
+///
+/// ```
+/// use stylus_sdk::{abi, function_selector};
+/// use alloy_primitives::{Address, U256};
+///
+/// let (to, amount) = (Address::ZERO, U256::from(100));
+/// const TRANSFER: [u8; 4] = function_selector!("transfer", Address, U256);
+/// let calldata = abi::encode_call(TRANSFER, &(to, amount));
+/// assert_eq!(calldata.len(), 4 + 2 * 32);
+/// ```
+///
+/// [sol_interface]: crate::prelude::sol_interface
+pub fn encode_call<T>(selector: [u8; 4], args: &T) -> Vec<u8>
+where
+    T: AbiType + alloy_sol_types::private::SolTypeValue<T::SolType>,
+    for<'a> <T::SolType as SolType>::Token<'a>: TokenSeq<'a>,
+{
+    let args = <T::SolType as SolType>::abi_encode_params(args);
+    let mut calldata = Vec::with_capacity(4 + args.len());
+    calldata.extend(selector);
+    calldata.extend(args);
+    calldata
+}
+
+/// Builds the calldata for a call given the function's signature, like Solidity's
+/// `abi.encodeWithSignature`.
+///
+/// The signature, such as `"transfer(address,uint256)"`, is hashed when called. To hash it at
+/// compile time instead, pass the result of [`function_selector`] to [`encode_call`].
+pub fn encode_call_with_signature<T>(signature: &str, args: &T) -> Vec<u8>
+where
+    T: AbiType + alloy_sol_types::private::SolTypeValue<T::SolType>,
+    for<'a> <T::SolType as SolType>::Token<'a>: TokenSeq<'a>,
+{
+    let hash = crate::crypto::keccak(signature);
+    encode_call(internal::digest_to_selector(hash.0), args)
+}
+
+/// Converts an address into the 32-byte word used for it as an indexed event topic.
+///
+/// Like all ABI-encoded addresses, the word is left-padded with 12 zero bytes, so the address
+/// occupies its last 20 bytes.
+pub fn address_to_topic(address: Address) -> B256 {
+    address.into_word()
+}
+
+/// Recovers an address from an indexed event topic, the inverse of [`address_to_topic`].
+///
+/// The address is taken from the last 20 bytes. The 12 bytes of padding before it are ignored,
+/// and are zero for topics that hold addresses.
+pub fn topic_to_address(topic: B256) -> Address {
+    Address::from_word(topic)
+}
+
+/// Encodes a value as the topic of an indexed event parameter.
+///
+/// Values that fit in a word, like addresses and integers, are stored in the topic directly. Others,
+/// like `string`, `bytes`, arrays, and structs, can't be, so Solidity stores the keccak256 hash of
+/// their encoding instead. The value itself can't be recovered from the topic, and events generated
+/// by [`sol!`](alloy_sol_types::sol) type such fields as the [`B256`] hash.
+///
+/// ```no_run
+/// use stylus_sdk::{abi::value_to_topic, alloy_sol_types::sol, crypto::keccak, evm};
+///
+/// sol! {
+///     event Named(string indexed name);
+/// }
+///
+/// let name = value_to_topic(&String::from("alice"));
+/// assert_eq!(name, keccak("alice"));
+/// evm::log(Named { name });
+/// ```
+pub fn value_to_topic<T: AbiType>(value: &T) -> B256
+where
+    T::SolType: EventTopic,
+{
+    T::SolType::encode_topic(value).0
+}
+
 #[test]
 fn test_function_selector() {
     use alloy_primitives::{Address, U256};
@@ -106,3 +258,846 @@ fn test_function_selector() {
     const TEST_SELECTOR: [u8; 4] = function_selector!("foo", Address, U256);
     assert_eq!(TEST_SELECTOR, 0xbd0d639f_u32.to_be_bytes());
 }
+
+#[test]
+fn test_address_topics() {
+    use alloy_primitives::{address, b256};
+
+    let address = address!("00112233445566778899aabbccddeeff00112233");
+    let topic = b256!("00000000000000000000000000112233445566778899aabbccddeeff00112233");
+    assert_eq!(address_to_topic(address), topic);
+    assert_eq!(topic_to_address(topic), address);
+}
+
+#[test]
+fn test_value_topics() {
+    use alloy_primitives::U256;
+
+    // word-sized values are their own topics
+    let address = Address::repeat_byte(1);
+    assert_eq!(value_to_topic(&address), address_to_topic(address));
+    assert_eq!(value_to_topic(&U256::from(5)), B256::from(U256::from(5)));
+
+    // dynamic values are hashed without a length or padding
+    let name = alloc::string::String::from("alice");
+    assert_eq!(value_to_topic(&name), crate::crypto::keccak("alice"));
+    let data = Bytes(alloc::vec![1, 2, 3]);
+    assert_eq!(value_to_topic(&data), crate::crypto::keccak([1, 2, 3]));
+}
+
+#[test]
+fn test_interface_id() {
+    use alloy_primitives::{Address, U256};
+    assert_eq!(
+        interface_id(&[function_selector!(
+            "supportsInterface",
+            alloy_primitives::FixedBytes<4>
+        )]),
+        ERC165_INTERFACE_ID
+    );
+
+    // the ERC-20 interface id
+    const IERC20: u32 = interface_id(&[
+        function_selector!("totalSupply"),
+        function_selector!("balanceOf", Address),
+        function_selector!("transfer", Address, U256),
+        function_selector!("transferFrom", Address, Address, U256),
+        function_selector!("approve", Address, U256),
+        function_selector!("allowance", Address, Address),
+    ]);
+    assert_eq!(IERC20, 0x36372b07);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{StorageBool, StorageType},
+        stylus_proc::{public, storage, SolidityError},
+    };
+    use alloc::{string::String, vec, vec::Vec};
+    use alloy_primitives::FixedBytes;
+
+    const CAPABILITY: u32 = 0x12345678;
+
+    #[storage]
+    struct Contract {
+        flag: StorageBool,
+    }
+
+    unsafe impl TopLevelStorage for Contract {}
+
+    #[public]
+    #[supports_interfaces(CAPABILITY, interface_id(&[function_selector!("Unauthorized")]))]
+    impl Contract {
+        fn flag(&self) -> bool {
+            self.flag.get()
+        }
+
+        #[write]
+        #[non_reentrant]
+        fn poke(&self) {}
+
+        #[view]
+        #[allow_reentrant]
+        fn version() -> u8 {
+            1
+        }
+
+        fn blob(&self, len: u8) -> Output {
+            let mut output = Output::with_capacity(len as usize);
+            output.extend(0..len);
+            output
+        }
+
+        fn measure(data: &[u8], label: &str, scale: u8) -> (alloy_primitives::U256, String) {
+            let len = data.len() * scale as usize;
+            (alloy_primitives::U256::from(len), label.to_uppercase())
+        }
+
+        #[instances(u8, alloy_primitives::Address)]
+        fn pair<T: Clone>(value: T) -> (T, T) {
+            (value.clone(), value)
+        }
+    }
+
+    #[storage]
+    struct Shapes {}
+
+    unsafe impl TopLevelStorage for Shapes {}
+
+    #[public]
+    impl Shapes {
+        #[raw]
+        fn raw(fail: bool) -> ArbResult {
+            match fail {
+                true => Err(vec![0xde, 0xad]),
+                false => Ok(vec![0xbe, 0xef]),
+            }
+        }
+
+        fn encoded(fail: bool) -> ArbResult {
+            match fail {
+                true => Err(vec![0xde, 0xad]),
+                false => Ok(vec![0xbe, 0xef]),
+            }
+        }
+
+        fn checked(fail: bool) -> Result<u8, Vec<u8>> {
+            match fail {
+                true => Err(vec![0xde, 0xad]),
+                false => Ok(7),
+            }
+        }
+
+        fn typed(fail: bool) -> Result<u8, Failure> {
+            match fail {
+                true => Err(Failure::Denied(Denied { code: 3 })),
+                false => Ok(7),
+            }
+        }
+    }
+
+    #[storage]
+    struct Compliant {
+        #[borrow]
+        shapes: Shapes,
+    }
+
+    unsafe impl TopLevelStorage for Compliant {}
+
+    #[storage]
+    struct Extended {}
+
+    const BASE: u32 = interface_id(&[function_selector!("base")]);
+    const EXTENSION: u32 = interface_id(&[function_selector!("extension")]);
+
+    #[public]
+    #[interface_ids(BASE, EXTENSION)]
+    impl Extended {
+        fn base() {}
+
+        fn extension() {}
+    }
+
+    #[public]
+    #[inherit(Shapes)]
+    #[implements(Shapes, Self, Extended)]
+    #[supports_interfaces(CAPABILITY)]
+    impl Compliant {
+        #[alias("label")]
+        fn name() -> String {
+            "compliant".into()
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #[allow(missing_docs)]
+        error Denied(uint8 code);
+    }
+
+    #[derive(SolidityError)]
+    enum Failure {
+        Denied(Denied),
+    }
+
+    fn supports_interface(id: u32) -> bool {
+        let mut contract = unsafe { Contract::new(alloy_primitives::U256::ZERO, 0) };
+        let selector = u32::from_be_bytes(function_selector!("supportsInterface", FixedBytes<4>));
+        let input = <(FixedBytes<4>,) as AbiType>::SolType::abi_encode_params(&(FixedBytes(
+            id.to_be_bytes(),
+        ),));
+        let output = Contract::route(&mut contract, selector, &input)
+            .unwrap()
+            .unwrap();
+        <bool as AbiType>::SolType::abi_decode(&output, true).unwrap()
+    }
+
+    #[test]
+    fn test_supports_interfaces() {
+        assert!(supports_interface(ERC165_INTERFACE_ID));
+        assert!(supports_interface(CAPABILITY));
+        assert!(supports_interface(u32::from_be_bytes(function_selector!(
+            "Unauthorized"
+        ))));
+        assert!(!supports_interface(0xdeadbeef));
+        assert!(!supports_interface(0xffffffff));
+    }
+
+    #[test]
+    fn test_encode_call() {
+        use alloy_primitives::{Address, U256};
+        use alloy_sol_types::{sol, SolCall};
+
+        sol! {
+            function transfer(address to, uint256 amount);
+            function send(address to, bytes data, string memo);
+        }
+
+        let (to, amount) = (Address::repeat_byte(0xaa), U256::from(100));
+        let expected = transferCall { to, amount }.abi_encode();
+        let selector = function_selector!("transfer", Address, U256);
+        assert_eq!(encode_call(selector, &(to, amount)), expected);
+        let signature = "transfer(address,uint256)";
+        assert_eq!(
+            encode_call_with_signature(signature, &(to, amount)),
+            expected
+        );
+
+        let (data, memo) = (vec![1, 2, 3], "hi");
+        let expected = sendCall {
+            to,
+            data: data.clone().into(),
+            memo: memo.into(),
+        }
+        .abi_encode();
+        let args = (to, Bytes(data), alloc::string::String::from(memo));
+        let selector = function_selector!("send", Address, Bytes, alloc::string::String);
+        assert_eq!(encode_call(selector, &args), expected);
+
+        // no arguments
+        assert_eq!(
+            encode_call(function_selector!("foo"), &()),
+            function_selector!("foo")
+        );
+    }
+
+    #[test]
+    fn test_explicit_purity() {
+        use crate::methods::Purity::*;
+
+        let flag = u32::from_be_bytes(function_selector!("flag"));
+        let poke = u32::from_be_bytes(function_selector!("poke"));
+        let version = u32::from_be_bytes(function_selector!("version"));
+
+        // inferred from &self
+        assert!(Contract::__stylus_allow_override(flag, View));
+        assert!(!Contract::__stylus_allow_override(flag, Write));
+
+        // overridden by attributes
+        assert!(Contract::__stylus_allow_override(poke, Write));
+        assert!(Contract::__stylus_allow_override(version, View));
+        assert!(!Contract::__stylus_allow_override(version, Write));
+    }
+
+    #[test]
+    fn test_reentrancy_attributes() {
+        let default = cfg!(feature = "reentrant");
+        let flag = u32::from_be_bytes(function_selector!("flag"));
+        let poke = u32::from_be_bytes(function_selector!("poke"));
+        let version = u32::from_be_bytes(function_selector!("version"));
+        let pause = u32::from_be_bytes(function_selector!("pause"));
+
+        assert_eq!(Contract::__stylus_allow_reentrant(flag), Some(default));
+        assert_eq!(Contract::__stylus_allow_reentrant(poke), Some(false));
+        assert_eq!(Contract::__stylus_allow_reentrant(version), Some(true));
+        assert_eq!(Contract::__stylus_allow_reentrant(0xdeadbeef), None);
+
+        // inherited methods keep their attributes
+        assert_eq!(Pausable::__stylus_allow_reentrant(pause), Some(default));
+        assert_eq!(Pausable::__stylus_allow_reentrant(poke), Some(false));
+        assert_eq!(Pausable::__stylus_allow_reentrant(version), Some(true));
+
+        // overrides without their own attribute take that of the method they override
+        assert_eq!(Overrides::__stylus_allow_reentrant(poke), Some(false));
+        assert_eq!(Overrides::__stylus_allow_reentrant(version), Some(false));
+        assert_eq!(Overrides::__stylus_allow_reentrant(flag), Some(default));
+    }
+
+    #[storage]
+    struct Overrides {
+        #[borrow]
+        contract: Contract,
+    }
+
+    #[public]
+    #[inherit(Contract)]
+    impl Overrides {
+        #[write]
+        fn poke(&self) {}
+
+        #[view]
+        #[non_reentrant]
+        fn version() -> u8 {
+            2
+        }
+    }
+
+    #[test]
+    fn test_return_encoding() {
+        use crate::abi::IntoReturnData;
+        use alloc::{string::String, vec};
+        use alloy_primitives::{Address, U256};
+
+        // single values are encoded as a one-element parameter list
+        macro_rules! check {
+            ($ty:ty, $value:expr) => {{
+                let value: $ty = $value;
+                let expected = <($ty,) as AbiType>::SolType::abi_encode_params(&(value.clone(),));
+                assert_eq!(value.into_return_data(), Ok(expected));
+            }};
+        }
+        check!(Vec<Vec<u8>>, vec![vec![1, 2], vec![], vec![3]]);
+        check!(Vec<Bytes>, vec![Bytes(vec![1, 2]), Bytes(vec![3; 40])]);
+        check!(
+            Vec<FixedBytes<32>>,
+            vec![FixedBytes([1; 32]), FixedBytes([2; 32])]
+        );
+        check!(Vec<(Address, U256)>, vec![(Address::ZERO, U256::from(1))]);
+        check!(
+            Vec<(String, Vec<u8>)>,
+            vec![("a".into(), vec![1]), ("b".into(), vec![])]
+        );
+        check!(
+            Vec<Vec<String>>,
+            vec![vec!["a".into(), "bc".into()], vec![]]
+        );
+
+        // tuples are multiple return values, so they aren't wrapped in an offset
+        macro_rules! check_tuple {
+            ($ty:ty, $value:expr) => {{
+                let value: $ty = $value;
+                let expected = <$ty as AbiType>::SolType::abi_encode_params(&value);
+                assert_eq!(value.into_return_data(), Ok(expected));
+            }};
+        }
+        check_tuple!((String, U256), ("a".into(), U256::from(1)));
+        check_tuple!(
+            (U256, Vec<Vec<u8>>),
+            (U256::from(2), vec![vec![1], vec![2, 3]])
+        );
+        check_tuple!((U256, Address), (U256::from(3), Address::ZERO));
+        check_tuple!((Vec<FixedBytes<32>>,), (vec![FixedBytes([4; 32])],));
+    }
+
+    #[test]
+    fn test_implements() {
+        let shapes = interface_id(&[
+            function_selector!("raw", bool),
+            function_selector!("encoded", bool),
+            function_selector!("checked", bool),
+            function_selector!("typed", bool),
+        ]);
+        assert_eq!(Shapes::__stylus_interface_id(), shapes);
+        assert_eq!(
+            Compliant::__stylus_interface_id(),
+            u32::from_be_bytes(function_selector!("name"))
+        );
+
+        let supports = |id: u32| {
+            let mut contract = unsafe { Compliant::new(alloy_primitives::U256::ZERO, 0) };
+            let selector =
+                u32::from_be_bytes(function_selector!("supportsInterface", FixedBytes<4>));
+            let input = <(FixedBytes<4>,) as AbiType>::SolType::abi_encode_params(&(FixedBytes(
+                id.to_be_bytes(),
+            ),));
+            let output = Compliant::route(&mut contract, selector, &input).unwrap();
+            <bool as AbiType>::SolType::abi_decode(&output.unwrap(), true).unwrap()
+        };
+        assert!(supports(ERC165_INTERFACE_ID));
+        assert!(supports(shapes));
+        assert!(supports(Compliant::__stylus_interface_id()));
+        assert!(supports(CAPABILITY));
+
+        // the combined id of an inheriting type isn't advertised unless listed
+        assert!(!supports(shapes ^ Compliant::__stylus_interface_id()));
+
+        // types may list their ids instead
+        assert_eq!(Extended::__STYLUS_INTERFACE_IDS, [BASE, EXTENSION]);
+        assert!(supports(BASE) && supports(EXTENSION));
+        assert!(!supports(Extended::__stylus_interface_id()));
+    }
+
+    #[test]
+    fn test_return_shapes() {
+        use alloy_sol_types::SolError;
+
+        let call = |name: &str, fail: bool| {
+            let mut contract = unsafe { Shapes::new(alloy_primitives::U256::ZERO, 0) };
+            let digest = crate::crypto::keccak(format!("{name}(bool)"));
+            let selector = u32::from_be_bytes(internal::digest_to_selector(digest.0));
+            let input = <(bool,) as AbiType>::SolType::abi_encode_params(&(fail,));
+            Shapes::route(&mut contract, selector, &input).unwrap()
+        };
+        let seven = <u8 as AbiType>::SolType::abi_encode(&7);
+
+        // raw results are passed through
+        assert_eq!(call("raw", false), Ok(vec![0xbe, 0xef]));
+        assert_eq!(call("raw", true), Err(vec![0xde, 0xad]));
+
+        // without #[raw], an ArbResult's bytes are encoded like any other value
+        let encoded = <Vec<u8> as AbiType>::SolType::abi_encode(&vec![0xbe, 0xef]);
+        assert_eq!(call("encoded", false), Ok(encoded));
+        assert_eq!(call("encoded", true), Err(vec![0xde, 0xad]));
+
+        // other results encode their values, reverting with their errors' bytes
+        assert_eq!(call("checked", false), Ok(seven.clone()));
+        assert_eq!(call("checked", true), Err(vec![0xde, 0xad]));
+        assert_eq!(call("typed", false), Ok(seven));
+        assert_eq!(call("typed", true), Err(Denied { code: 3 }.abi_encode()));
+    }
+
+    #[test]
+    fn test_output() {
+        let mut contract = unsafe { Contract::new(alloy_primitives::U256::ZERO, 0) };
+        let selector = u32::from_be_bytes(function_selector!("blob", u8));
+        let input = <(u8,) as AbiType>::SolType::abi_encode_params(&(40,));
+        let output = Contract::route(&mut contract, selector, &input)
+            .unwrap()
+            .unwrap();
+        let bytes = <Bytes as AbiType>::SolType::abi_decode(&output, true).unwrap();
+        assert_eq!(bytes, Bytes((0..40).collect()));
+    }
+
+    #[test]
+    fn test_borrowed_args() {
+        use alloy_primitives::U256;
+
+        type Args = (Bytes, String, u8);
+        let mut contract = unsafe { Contract::new(U256::ZERO, 0) };
+        let selector = u32::from_be_bytes(function_selector!("measure", Bytes, String, u8));
+
+        let args = (Bytes(vec![7; 100]), String::from("blob"), 3);
+        let input = <Args as AbiType>::SolType::abi_encode_params(&args);
+        let output = Contract::route(&mut contract, selector, &input)
+            .unwrap()
+            .unwrap();
+        let (len, label) =
+            <(U256, String) as AbiType>::SolType::abi_decode_params(&output, true).unwrap();
+        assert_eq!(len, U256::from(300));
+        assert_eq!(label, "BLOB");
+
+        // strings must be valid utf-8
+        let args = (Bytes(vec![]), String::from("\u{80}"), 1);
+        let mut input = <Args as AbiType>::SolType::abi_encode_params(&args);
+        let len = input.len();
+        input[len - 32] = 0xff;
+        let output = Contract::route(&mut contract, selector, &input).unwrap();
+        assert_eq!(output, Err(vec![]));
+    }
+
+    #[test]
+    fn test_generic_instances() {
+        use alloy_primitives::Address;
+
+        let mut contract = unsafe { Contract::new(alloy_primitives::U256::ZERO, 0) };
+        let mut call = |selector: [u8; 4], input: &[u8]| {
+            let selector = u32::from_be_bytes(selector);
+            Contract::route(&mut contract, selector, input)
+                .unwrap()
+                .unwrap()
+        };
+
+        // each instance has its own selector
+        let input = <(u8,) as AbiType>::SolType::abi_encode_params(&(7,));
+        let output = call(function_selector!("pair", u8), &input);
+        let decoded = <(u8, u8) as AbiType>::SolType::abi_decode_params(&output, true);
+        assert_eq!(decoded.unwrap(), (7, 7));
+
+        let address = Address::repeat_byte(7);
+        let input = <(Address,) as AbiType>::SolType::abi_encode_params(&(address,));
+        let output = call(function_selector!("pair", Address), &input);
+        let decoded = <(Address, Address) as AbiType>::SolType::abi_decode_params(&output, true);
+        assert_eq!(decoded.unwrap(), (address, address));
+    }
+
+    alloy_sol_types::sol! {
+        error UnknownCall(bytes data);
+    }
+
+    #[derive(crate::stylus_proc::SolidityError)]
+    enum ProxyError {
+        UnknownCall(UnknownCall),
+    }
+
+    #[storage]
+    struct Proxy {
+        calls: crate::storage::StorageU64,
+    }
+
+    unsafe impl TopLevelStorage for Proxy {}
+
+    #[public]
+    impl Proxy {
+        fn calls(&self) -> u64 {
+            self.calls.get().to()
+        }
+
+        #[fallback]
+        #[payable]
+        fn fallback(&mut self, calldata: &[u8]) -> Result<Vec<u8>, ProxyError> {
+            if calldata.starts_with(b"bad") {
+                let data = calldata.to_vec().into();
+                return Err(UnknownCall { data }.into());
+            }
+            let calls = self.calls.get() + alloy_primitives::U64::from(1);
+            self.calls.set(calls);
+            Ok(calldata.iter().rev().copied().collect())
+        }
+    }
+
+    #[storage]
+    struct Wrapper {
+        #[borrow]
+        proxy: Proxy,
+    }
+
+    unsafe impl TopLevelStorage for Wrapper {}
+
+    #[public]
+    #[inherit(Proxy)]
+    impl Wrapper {}
+
+    #[test]
+    fn test_fallback() {
+        use crate::call::FromRevert;
+
+        let mut proxy = unsafe { Wrapper::new(alloy_primitives::U256::from(0x6565), 0) };
+        let calls = u32::from_be_bytes(function_selector!("calls"));
+        assert!(Wrapper::route(&mut proxy, calls, &[]).is_some());
+        assert_eq!(Wrapper::route(&mut proxy, 0xdeadbeef, &[]), None);
+
+        // the fallback is inherited, and gets the full calldata
+        assert_eq!(
+            Wrapper::fallback(&mut proxy, &[1, 2, 3]),
+            Some(Ok(vec![3, 2, 1]))
+        );
+        assert_eq!(Wrapper::fallback(&mut proxy, &[]), Some(Ok(vec![])));
+        assert_eq!(proxy.proxy.calls(), 2);
+
+        // errors are encoded
+        let Some(Err(data)) = Wrapper::fallback(&mut proxy, b"bad call") else {
+            panic!("fallback didn't revert");
+        };
+        let Some(ProxyError::UnknownCall(err)) = ProxyError::from_revert(&data) else {
+            panic!("failed to decode error");
+        };
+        assert_eq!(err.data, b"bad call".to_vec());
+
+        // types without a fallback have none
+        let mut contract = unsafe { Contract::new(alloy_primitives::U256::ZERO, 0) };
+        assert_eq!(Contract::fallback(&mut contract, &[1, 2, 3]), None);
+    }
+
+    #[storage]
+    struct Pausable {
+        paused: StorageBool,
+        #[borrow]
+        contract: Contract,
+    }
+
+    unsafe impl TopLevelStorage for Pausable {}
+
+    #[public]
+    #[inherit(Contract)]
+    #[before_dispatch(when_not_paused)]
+    impl Pausable {
+        fn pause(&mut self) {
+            self.paused.set(true);
+        }
+
+        #[fallback]
+        #[payable]
+        fn fallback(&mut self, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+            Ok(calldata.to_vec())
+        }
+    }
+
+    impl Pausable {
+        fn when_not_paused(&mut self, selector: u32, _input: &[u8]) -> Result<(), Vec<u8>> {
+            match self.paused.get() && selector != u32::from_be_bytes(function_selector!("pause")) {
+                true => Err(b"paused".to_vec()),
+                false => Ok(()),
+            }
+        }
+    }
+
+    #[storage]
+    struct Limited {}
+
+    unsafe impl TopLevelStorage for Limited {}
+
+    #[public]
+    impl Limited {
+        #[max_calldata(68)]
+        fn hash(data: &[u8]) -> FixedBytes<32> {
+            crate::crypto::keccak(data)
+        }
+    }
+
+    #[test]
+    fn test_selectors() {
+        let selector = |name: &str| {
+            let selectors = Pausable::selectors();
+            let (selector, _) = selectors.into_iter().find(|(_, n)| *n == name).unwrap();
+            selector
+        };
+        let names: Vec<_> = Pausable::selectors()
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Pausable::pause",
+                "Contract::flag",
+                "Contract::poke",
+                "Contract::version",
+                "Contract::blob",
+                "Contract::measure",
+                "Contract::pair::<u8>",
+                "Contract::pair::<alloy_primitives::Address>",
+                "Contract::supports_interface",
+            ]
+        );
+        assert_eq!(selector("Pausable::pause"), function_selector!("pause"));
+        assert_eq!(selector("Contract::blob"), function_selector!("blob", u8));
+        assert_eq!(
+            selector("Contract::supports_interface"),
+            function_selector!("supportsInterface", FixedBytes<4>)
+        );
+    }
+
+    #[storage]
+    struct Branded {
+        #[borrow]
+        contract: Contract,
+    }
+
+    unsafe impl TopLevelStorage for Branded {}
+
+    #[public]
+    #[inherit(Contract)]
+    impl Branded {
+        #[selector(name = "version")]
+        fn brand_version() -> u8 {
+            2
+        }
+
+        fn flag(&self) -> bool {
+            true
+        }
+    }
+
+    #[storage]
+    struct Rebranded {
+        #[borrow]
+        contract: Contract,
+    }
+
+    unsafe impl TopLevelStorage for Rebranded {}
+
+    #[public]
+    #[inherit(Contract)]
+    impl Rebranded {
+        #[selector(name = "version")]
+        #[overrides]
+        fn brand_version() -> u8 {
+            2
+        }
+    }
+
+    #[test]
+    fn test_selector_collisions() {
+        assert_eq!(Contract::__stylus_selector_collision(), None);
+        assert_eq!(Pausable::__stylus_selector_collision(), None);
+
+        // overriding `flag` is fine, but `brand_version` shadows `version` unintentionally
+        let error = Branded::__stylus_selector_collision().unwrap();
+        assert!(error.starts_with("`Contract::version` is shadowed"));
+        assert_eq!(Rebranded::__stylus_selector_collision(), None);
+    }
+
+    #[storage]
+    struct Migrated {
+        #[borrow]
+        contract: Contract,
+    }
+
+    unsafe impl TopLevelStorage for Migrated {}
+
+    #[public]
+    #[inherit(Contract)]
+    impl Migrated {
+        #[alias("getBalance")]
+        #[alias("balance")]
+        fn balance_of(owner: alloy_primitives::Address) -> u8 {
+            owner[0]
+        }
+    }
+
+    #[storage]
+    struct Misaliased {
+        #[borrow]
+        contract: Contract,
+    }
+
+    unsafe impl TopLevelStorage for Misaliased {}
+
+    #[public]
+    #[inherit(Contract)]
+    impl Misaliased {
+        #[alias("flag")]
+        fn is_flagged(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_aliases() {
+        use alloy_primitives::Address;
+
+        let mut contract = unsafe { Migrated::new(alloy_primitives::U256::ZERO, 0) };
+        let input =
+            <(Address,) as AbiType>::SolType::abi_encode_params(&(Address::repeat_byte(7),));
+        let names = [
+            function_selector!("balanceOf", Address),
+            function_selector!("getBalance", Address),
+            function_selector!("balance", Address),
+        ];
+        for selector in names {
+            let selector = u32::from_be_bytes(selector);
+            let result = Migrated::route(&mut contract, selector, &input).unwrap();
+            assert_eq!(result.unwrap()[31], 7);
+        }
+
+        // aliases are listed under the method's name
+        let selectors: Vec<_> = Migrated::selectors().into_iter().take(3).collect();
+        assert_eq!(
+            selectors,
+            [
+                (
+                    function_selector!("balanceOf", Address).into(),
+                    "Migrated::balance_of"
+                ),
+                (
+                    function_selector!("getBalance", Address).into(),
+                    "Migrated::balance_of"
+                ),
+                (
+                    function_selector!("balance", Address).into(),
+                    "Migrated::balance_of"
+                ),
+            ]
+        );
+
+        // aliases are checked for collisions like any other selector
+        assert_eq!(Migrated::__stylus_selector_collision(), None);
+        let error = Misaliased::__stylus_selector_collision().unwrap();
+        assert!(error.starts_with("`Contract::flag` is shadowed"));
+    }
+
+    #[cfg(feature = "export-abi")]
+    #[test]
+    fn test_exported_aliases() {
+        use core::fmt;
+
+        struct Abi;
+
+        impl fmt::Display for Abi {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                <Migrated as export::GenerateAbi>::fmt_abi(f)
+            }
+        }
+
+        // only the method's own name is exported
+        let abi = Abi.to_string();
+        assert!(abi.contains("function balanceOf(address owner) external pure returns (uint8);"));
+        assert!(!abi.contains("getBalance"));
+    }
+
+    #[test]
+    fn test_before_dispatch() {
+        let mut contract = unsafe { Pausable::new(alloy_primitives::U256::ZERO, 0) };
+        let flag = u32::from_be_bytes(function_selector!("flag"));
+        let pause = u32::from_be_bytes(function_selector!("pause"));
+
+        assert!(Pausable::route(&mut contract, flag, &[]).unwrap().is_ok());
+        assert!(Pausable::route(&mut contract, pause, &[]).unwrap().is_ok());
+
+        // the hook guards inherited methods too
+        let result = Pausable::route(&mut contract, flag, &[]).unwrap();
+        assert_eq!(result, Err(b"paused".to_vec()));
+
+        // unknown selectors are left to the fallback, which the hook also guards
+        assert_eq!(Pausable::route(&mut contract, 0xdeadbeef, &[]), None);
+        for calldata in [&[0xde, 0xad, 0xbe, 0xef, 1][..], &[1, 2], &[]] {
+            let result = <Pausable as Router<Pausable>>::fallback(&mut contract, calldata);
+            assert_eq!(result, Some(Err(b"paused".to_vec())));
+        }
+        contract.paused.set(false);
+        let result = <Pausable as Router<Pausable>>::fallback(&mut contract, &[1, 2]);
+        assert_eq!(result, Some(Ok(vec![1, 2])));
+    }
+
+    #[test]
+    fn test_max_calldata() {
+        use alloy_sol_types::SolError;
+
+        let mut contract = unsafe { Limited::new(alloy_primitives::U256::ZERO, 0) };
+        let selector = u32::from_be_bytes(function_selector!("hash", Bytes));
+        let call = |contract: &mut Limited, data: Vec<u8>| {
+            let input = <(Bytes,) as AbiType>::SolType::abi_encode_params(&(Bytes(data),));
+            Limited::route(contract, selector, &input).unwrap()
+        };
+
+        // the selector, offset, and length fit exactly
+        let expected = crate::crypto::keccak([]).to_vec();
+        assert_eq!(call(&mut contract, vec![]), Ok(expected));
+
+        // one more word of data is rejected before decoding
+        let error = internal::CalldataTooLarge {
+            limit: alloy_primitives::U256::from(68),
+            length: alloy_primitives::U256::from(100),
+        };
+        assert_eq!(call(&mut contract, vec![7]), Err(error.abi_encode()));
+
+        // even when the oversized calldata wouldn't decode
+        let result = Limited::route(&mut contract, selector, &[0xff; 65]).unwrap();
+        let error = internal::CalldataTooLarge {
+            limit: alloy_primitives::U256::from(68),
+            length: alloy_primitives::U256::from(69),
+        };
+        assert_eq!(result, Err(error.abi_encode()));
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use crate::{abi::IntoReturnData, util::evm_padded_length, ArbResult};
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+
+/// The size of the offset and length words preceding the data of an encoded `bytes`.
+const HEADER_LEN: usize = 64;
+
+/// Builds a Solidity `bytes` return value in place.
+///
+/// Returning [`Bytes`](super::Bytes) from a method copies its contents into a second buffer while
+/// ABI-encoding it. An [`Output`] is written directly into the encoded form instead, so methods
+/// returning large blobs allocate only once.
+///
+/// ```
+/// use stylus_sdk::abi::Output;
+///
+/// fn export(chunks: &[&[u8]]) -> Output {
+///     let size = chunks.iter().map(|chunk| chunk.len()).sum();
+///     let mut output = Output::with_capacity(size);
+///     for chunk in chunks {
+///         output.extend_from_slice(chunk);
+///     }
+///     output
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Output {
+    buf: Vec<u8>,
+}
+
+impl Output {
+    /// Creates an empty [`Output`].
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty [`Output`] with space for `capacity` bytes without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut buf = Vec::with_capacity(HEADER_LEN + evm_padded_length(capacity));
+        buf.resize(HEADER_LEN, 0);
+        Self { buf }
+    }
+
+    /// The number of bytes written.
+    pub fn len(&self) -> usize {
+        self.buf.len() - HEADER_LEN
+    }
+
+    /// Whether no bytes have been written.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The bytes written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[HEADER_LEN..]
+    }
+
+    /// Appends a byte.
+    pub fn push(&mut self, byte: u8) {
+        self.buf.push(byte);
+    }
+
+    /// Appends a slice of bytes.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Completes the ABI encoding by filling in the header and padding.
+    fn finish(mut self) -> Vec<u8> {
+        let len = self.len();
+        self.buf[31] = 0x20;
+        self.buf[32..HEADER_LEN].copy_from_slice(&U256::from(len).to_be_bytes::<32>());
+        self.buf.resize(HEADER_LEN + evm_padded_length(len), 0);
+        self.buf
+    }
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for Output {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Output").field(&self.as_slice()).finish()
+    }
+}
+
+impl Extend<u8> for Output {
+    fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+        self.buf.extend(iter);
+    }
+}
+
+impl<'a> Extend<&'a u8> for Output {
+    fn extend<T: IntoIterator<Item = &'a u8>>(&mut self, iter: T) {
+        self.buf.extend(iter);
+    }
+}
+
+impl From<&[u8]> for Output {
+    fn from(bytes: &[u8]) -> Self {
+        let mut output = Self::with_capacity(bytes.len());
+        output.extend_from_slice(bytes);
+        output
+    }
+}
+
+impl IntoReturnData for Output {
+    #[inline(always)]
+    fn into_return_data(self) -> ArbResult {
+        Ok(self.finish())
+    }
+}
+
+impl<E: Into<Vec<u8>>> IntoReturnData for Result<Output, E> {
+    #[inline(always)]
+    fn into_return_data(self) -> ArbResult {
+        match self {
+            Ok(output) => output.into_return_data(),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::{AbiType, Bytes};
+    use alloy_sol_types::SolType;
+
+    #[test]
+    fn test_encoding() {
+        for len in [0, 1, 31, 32, 33, 100] {
+            let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let expected = <Bytes as AbiType>::SolType::abi_encode(&Bytes(data.clone()));
+
+            let mut output = Output::new();
+            output.extend(&data);
+            assert_eq!(output.as_slice(), data);
+            assert_eq!(output.into_return_data(), Ok(expected.clone()));
+
+            let output: Result<Output, Vec<u8>> = Ok(Output::from(&data[..]));
+            assert_eq!(output.into_return_data(), Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::AbiType;
+use crate::ArbResult;
+use alloc::vec::Vec;
+use alloy_sol_types::private::SolTypeValue;
+
+/// Converts the value returned by a [`#[public]`][public] method into the call's return data.
+///
+/// The encoding follows from the method's return type:
+///
+/// 1. `Result<T, E>` ABI-encodes an `Ok` value as `T` would be. An `Err` reverts with `E`
+///    converted into bytes, which covers both `Vec<u8>` and errors deriving
+///    [`SolidityError`][SolidityError].
+/// 2. Any other `T` implementing [`AbiType`] is ABI-encoded, with tuples returned as multiple
+///    values.
+///
+/// [`Output`](super::Output) is written as a `bytes` return value, on its own or inside a
+/// `Result`.
+///
+/// Methods marked `#[raw]` skip this trait. Their [`ArbResult`] is passed through as is, with
+/// `Ok` bytes becoming the raw return data and `Err` bytes the raw revert data.
+///
+/// [public]: crate::prelude::public
+/// [SolidityError]: crate::prelude::SolidityError
+pub trait IntoReturnData {
+    /// Encodes the value as return data, or as revert data for an error.
+    fn into_return_data(self) -> ArbResult;
+}
+
+impl<T> IntoReturnData for T
+where
+    T: AbiType + SolTypeValue<<T as AbiType>::SolType>,
+{
+    #[inline(always)]
+    fn into_return_data(self) -> ArbResult {
+        Ok(self.abi_encode_returns())
+    }
+}
+
+impl<T, E: Into<Vec<u8>>> IntoReturnData for Result<T, E>
+where
+    T: AbiType + SolTypeValue<<T as AbiType>::SolType>,
+{
+    #[inline(always)]
+    fn into_return_data(self) -> ArbResult {
+        match self {
+            Ok(result) => result.into_return_data(),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
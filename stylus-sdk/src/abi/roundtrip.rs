@@ -0,0 +1,87 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Round-trip checks for ABI encodings, for use in contract test suites.
+
+use alloy_sol_types::SolType;
+use core::fmt::Debug;
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    test_runner::{TestCaseError, TestRunner},
+};
+
+/// Asserts that a value decodes to itself after being ABI-encoded, which guards against
+/// mismatched encodings in types like tuples with dynamic members.
+///
+/// ```
+/// use stylus_sdk::{abi::assert_roundtrip, alloy_primitives::{Address, U256}};
+/// use stylus_sdk::alloy_sol_types::sol_data;
+///
+/// type Balance = (sol_data::Address, sol_data::Uint<256>, sol_data::String);
+/// assert_roundtrip::<Balance>(&(Address::ZERO, U256::from(5), "savings".into()));
+/// ```
+pub fn assert_roundtrip<T: SolType>(value: &T::RustType)
+where
+    T::RustType: PartialEq + Debug,
+{
+    if let Err(err) = roundtrip::<T>(value) {
+        panic!("{err}");
+    }
+}
+
+/// Asserts that random values of `T` decode to themselves after being ABI-encoded, as with
+/// [`assert_roundtrip`]. Failures are shrunk to a minimal value before being reported.
+///
+/// ```
+/// use stylus_sdk::{abi::assert_roundtrip_arbitrary, alloy_sol_types::sol_data};
+///
+/// assert_roundtrip_arbitrary::<(sol_data::Bytes, sol_data::Array<sol_data::Uint<64>>)>();
+/// ```
+pub fn assert_roundtrip_arbitrary<T: SolType>()
+where
+    T::RustType: Arbitrary + PartialEq + Debug,
+{
+    let mut runner = TestRunner::default();
+    let result = runner.run(&any::<T::RustType>(), |value| {
+        roundtrip::<T>(&value).map_err(TestCaseError::fail)
+    });
+    if let Err(err) = result {
+        panic!("{}: {err}", T::SOL_NAME);
+    }
+}
+
+fn roundtrip<T: SolType>(value: &T::RustType) -> Result<(), String>
+where
+    T::RustType: PartialEq + Debug,
+{
+    let encoded = T::abi_encode(value);
+    match T::abi_decode(&encoded, true) {
+        Ok(decoded) if decoded == *value => Ok(()),
+        Ok(decoded) => Err(format!("{value:?} decoded as {decoded:?}")),
+        Err(err) => Err(format!("{value:?} failed to decode: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::{AbiType, Bytes};
+    use alloy_primitives::Address;
+    use alloy_sol_types::sol_data;
+
+    #[test]
+    fn test_roundtrip() {
+        type Dynamic = <(Vec<Address>, Bytes, String, bool) as AbiType>::SolType;
+        assert_roundtrip::<Dynamic>(&(
+            vec![Address::repeat_byte(1)],
+            vec![1, 2, 3].into(),
+            "hi".into(),
+            true,
+        ));
+
+        assert_roundtrip_arbitrary::<sol_data::Uint<256>>();
+        assert_roundtrip_arbitrary::<(sol_data::Address, sol_data::FixedBytes<20>)>();
+        assert_roundtrip_arbitrary::<(sol_data::String, sol_data::Array<sol_data::Bytes>)>();
+        assert_roundtrip_arbitrary::<(sol_data::Uint<64>, (sol_data::Bool, sol_data::String))>();
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Role-based access control, compatible with OpenZeppelin's `AccessControl`.
+//!
+//! Roles are identified by `bytes32` values, conventionally the hash of the role's name. Each
+//! role has an admin role whose members may grant and revoke it. By default, every role's admin
+//! is [`DEFAULT_ADMIN_ROLE`], which is also its own admin.
+//!
+//! ```ignore
+//! const MINTER_ROLE: B256 = B256::new(keccak_const::Keccak256::new().update(b"MINTER_ROLE").finalize());
+//!
+//! #[public]
+//! #[inherit(AccessControl)]
+//! impl Token {
+//!     pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+//!         self.access.only_role(MINTER_ROLE)?;
+//!         ...
+//!     }
+//! }
+//! ```
+//!
+//! As with the other components, the contract grants the initial roles itself, typically by
+//! calling [`_grant_role`](AccessControl::_grant_role) from an initialization method.
+
+use crate::{
+    evm, msg,
+    storage::{StorageB256, StorageBool, StorageMap},
+    stylus_proc::{public, storage, SolidityError},
+};
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::sol;
+
+/// The role that administers all others unless [set otherwise](AccessControl::_set_role_admin).
+pub const DEFAULT_ADMIN_ROLE: B256 = B256::ZERO;
+
+sol! {
+    /// Emitted when `account` is granted `role` by `sender`.
+    event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender);
+
+    /// Emitted when `account` loses `role`, either revoked by `sender` or renounced.
+    event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender);
+
+    /// Emitted when the admin role of `role` changes.
+    event RoleAdminChanged(bytes32 indexed role, bytes32 indexed previousAdminRole, bytes32 indexed newAdminRole);
+
+    /// The account is missing the role the operation requires.
+    #[allow(missing_docs)]
+    error AccessControlUnauthorizedAccount(address account, bytes32 neededRole);
+
+    /// The caller tried to renounce a role on behalf of another account.
+    error AccessControlBadConfirmation();
+}
+
+/// Represents the ways [`AccessControl`] methods may fail.
+#[derive(SolidityError)]
+pub enum AccessControlError {
+    /// The caller lacks a role.
+    UnauthorizedAccount(AccessControlUnauthorizedAccount),
+    /// The confirmation passed to `renounceRole` isn't the caller.
+    BadConfirmation(AccessControlBadConfirmation),
+}
+
+/// The members and admin of a role.
+#[storage]
+struct RoleData {
+    members: StorageMap<Address, StorageBool>,
+    admin_role: StorageB256,
+}
+
+/// Tracks which accounts hold which roles.
+#[storage]
+pub struct AccessControl {
+    roles: StorageMap<B256, RoleData>,
+}
+
+impl AccessControl {
+    /// Fails with [`AccessControlUnauthorizedAccount`] unless the caller has `role`.
+    pub fn only_role(&self, role: B256) -> Result<(), AccessControlError> {
+        self.check_role(role, msg::sender())
+    }
+
+    /// Fails with [`AccessControlUnauthorizedAccount`] unless `account` has `role`.
+    pub fn check_role(&self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        if !self.has_role(role, account) {
+            let err = AccessControlUnauthorizedAccount {
+                account,
+                neededRole: role,
+            };
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Grants `role` to `account` without any checks, returning whether it was newly granted.
+    ///
+    /// Emits [`RoleGranted`] if the account didn't already have the role.
+    pub fn _grant_role(&mut self, role: B256, account: Address) -> bool {
+        let mut data = self.roles.setter(role);
+        let mut member = data.members.setter(account);
+        if member.get() {
+            return false;
+        }
+        member.set(true);
+        evm::log(RoleGranted {
+            role,
+            account,
+            sender: msg::sender(),
+        });
+        true
+    }
+
+    /// Revokes `role` from `account` without any checks, returning whether it was held.
+    ///
+    /// Emits [`RoleRevoked`] if the account had the role.
+    pub fn _revoke_role(&mut self, role: B256, account: Address) -> bool {
+        let mut data = self.roles.setter(role);
+        let mut member = data.members.setter(account);
+        if !member.get() {
+            return false;
+        }
+        member.set(false);
+        evm::log(RoleRevoked {
+            role,
+            account,
+            sender: msg::sender(),
+        });
+        true
+    }
+
+    /// Sets the role whose members administer `role`, emitting [`RoleAdminChanged`].
+    pub fn _set_role_admin(&mut self, role: B256, admin_role: B256) {
+        let mut data = self.roles.setter(role);
+        let previous_admin_role = data.admin_role.get();
+        data.admin_role.set(admin_role);
+        evm::log(RoleAdminChanged {
+            role,
+            previousAdminRole: previous_admin_role,
+            newAdminRole: admin_role,
+        });
+    }
+}
+
+#[public]
+impl AccessControl {
+    /// Whether `account` has `role`.
+    pub fn has_role(&self, role: B256, account: Address) -> bool {
+        self.roles.getter(role).members.get(account)
+    }
+
+    /// The role whose members may grant and revoke `role`.
+    pub fn get_role_admin(&self, role: B256) -> B256 {
+        self.roles.getter(role).admin_role.get()
+    }
+
+    /// Grants `role` to `account`. The caller must have `role`'s admin role.
+    pub fn grant_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.only_role(self.get_role_admin(role))?;
+        self._grant_role(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from `account`. The caller must have `role`'s admin role.
+    pub fn revoke_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.only_role(self.get_role_admin(role))?;
+        self._revoke_role(role, account);
+        Ok(())
+    }
+
+    /// Gives up `role`. To guard against mistakes, `caller_confirmation` must be the caller.
+    pub fn renounce_role(
+        &mut self,
+        role: B256,
+        caller_confirmation: Address,
+    ) -> Result<(), AccessControlError> {
+        if caller_confirmation != msg::sender() {
+            return Err(AccessControlBadConfirmation {}.into());
+        }
+        self._revoke_role(role, caller_confirmation);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageType, test_host};
+    use alloy_primitives::U256;
+    use alloy_sol_types::SolEvent;
+
+    const ADMIN: Address = Address::repeat_byte(0xad);
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const MINTER_ROLE: B256 = B256::repeat_byte(0x01);
+    const MINTER_ADMIN_ROLE: B256 = B256::repeat_byte(0x02);
+
+    fn access() -> AccessControl {
+        let mut access = unsafe { AccessControl::new(U256::ZERO, 0) };
+        access._grant_role(DEFAULT_ADMIN_ROLE, ADMIN);
+        access
+    }
+
+    fn missing_role(result: Result<(), AccessControlError>) -> Option<(Address, B256)> {
+        match result {
+            Err(AccessControlError::UnauthorizedAccount(err)) => {
+                Some((err.account, err.neededRole))
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_grant_and_revoke() {
+        let mut access = access();
+
+        test_host::set_sender(ALICE);
+        assert_eq!(
+            missing_role(access.grant_role(MINTER_ROLE, ALICE)),
+            Some((ALICE, DEFAULT_ADMIN_ROLE))
+        );
+        assert_eq!(
+            missing_role(access.only_role(MINTER_ROLE)),
+            Some((ALICE, MINTER_ROLE))
+        );
+
+        test_host::set_sender(ADMIN);
+        assert!(access.grant_role(MINTER_ROLE, ALICE).is_ok());
+        assert!(access.has_role(MINTER_ROLE, ALICE));
+        assert!(!access._grant_role(MINTER_ROLE, ALICE));
+
+        assert!(access.revoke_role(MINTER_ROLE, ALICE).is_ok());
+        assert!(!access.has_role(MINTER_ROLE, ALICE));
+        assert!(!access._revoke_role(MINTER_ROLE, ALICE));
+
+        // only changes in membership are logged
+        let events: Vec<_> = test_host::logs()
+            .iter()
+            .map(|(topics, _)| topics[0])
+            .collect();
+        assert_eq!(
+            events,
+            [
+                RoleGranted::SIGNATURE_HASH,
+                RoleGranted::SIGNATURE_HASH,
+                RoleRevoked::SIGNATURE_HASH
+            ]
+        );
+    }
+
+    #[test]
+    fn test_role_admin() {
+        let mut access = access();
+        access._set_role_admin(MINTER_ROLE, MINTER_ADMIN_ROLE);
+        access._grant_role(MINTER_ADMIN_ROLE, ALICE);
+        assert_eq!(access.get_role_admin(MINTER_ROLE), MINTER_ADMIN_ROLE);
+        assert_eq!(access.get_role_admin(MINTER_ADMIN_ROLE), DEFAULT_ADMIN_ROLE);
+
+        // the default admin no longer administers the minter role
+        test_host::set_sender(ADMIN);
+        assert_eq!(
+            missing_role(access.grant_role(MINTER_ROLE, ADMIN)),
+            Some((ADMIN, MINTER_ADMIN_ROLE))
+        );
+
+        test_host::set_sender(ALICE);
+        assert!(access.grant_role(MINTER_ROLE, ADMIN).is_ok());
+        assert!(access.has_role(MINTER_ROLE, ADMIN));
+    }
+
+    #[test]
+    fn test_renounce() {
+        let mut access = access();
+        test_host::set_sender(ADMIN);
+        assert!(matches!(
+            access.renounce_role(DEFAULT_ADMIN_ROLE, ALICE),
+            Err(AccessControlError::BadConfirmation(_))
+        ));
+        assert!(access.renounce_role(DEFAULT_ADMIN_ROLE, ADMIN).is_ok());
+        assert!(!access.has_role(DEFAULT_ADMIN_ROLE, ADMIN));
+    }
+}
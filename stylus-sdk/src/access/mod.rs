@@ -0,0 +1,22 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Reusable components for restricting who may call a contract and when.
+//!
+//! Each component is a [`#[storage]`][storage] type meant to be embedded in a contract with
+//! `#[borrow]` and inherited via [`#[inherit]`][inherit], which exposes its public methods.
+//! Methods that anyone calling them could abuse, like [`Pausable::pause`], aren't public. The
+//! contract calls them from its own methods, after checking who the caller is.
+//!
+//! Note that this code is unaudited.
+//!
+//! [storage]: macro@crate::prelude::storage
+//! [inherit]: macro@crate::prelude::public
+
+pub use control::{AccessControl, AccessControlError};
+pub use ownable::{Ownable, Ownable2Step, OwnableError};
+pub use pausable::{Pausable, PausableError};
+
+pub mod control;
+pub mod ownable;
+pub mod pausable;
@@ -0,0 +1,296 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Single-account ownership, optionally transferred in two steps.
+//!
+//! [`Ownable`] gives a contract an owner who alone may call the methods guarded by
+//! [`only_owner`](Ownable::only_owner). Since Stylus contracts lack constructors, the contract
+//! must set the initial owner itself, typically from an initialization method.
+//!
+//! ```ignore
+//! #[storage]
+//! #[entrypoint]
+//! struct Treasury {
+//!     #[borrow]
+//!     ownable: Ownable,
+//!     ...
+//! }
+//!
+//! #[public]
+//! #[inherit(Ownable)]
+//! impl Treasury {
+//!     pub fn init(&mut self) -> Result<(), Vec<u8>> {
+//!         if self.ownable.owner() != Address::ZERO {
+//!             return Err(b"already initialized".to_vec());
+//!         }
+//!         self.ownable._transfer_ownership(msg::sender());
+//!         Ok(())
+//!     }
+//!
+//!     pub fn sweep(&mut self, to: Address) -> Result<(), Vec<u8>> {
+//!         self.ownable.only_owner()?;
+//!         ...
+//!     }
+//! }
+//! ```
+//!
+//! [`Ownable2Step`] works the same way, except that a new owner must accept ownership before it
+//! takes effect. This guards against transferring ownership to a mistyped address.
+
+use crate::{
+    evm, msg,
+    storage::{Erase, StorageAddress},
+    stylus_proc::{public, storage, SolidityError},
+};
+use alloy_primitives::Address;
+use alloy_sol_types::sol;
+
+sol! {
+    /// Emitted when ownership changes, including on renouncement, where `newOwner` is zero.
+    event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+
+    /// Emitted when the owner of an [`Ownable2Step`] nominates a new owner.
+    event OwnershipTransferStarted(address indexed previousOwner, address indexed newOwner);
+
+    /// The caller isn't allowed to perform the operation.
+    #[allow(missing_docs)]
+    error OwnableUnauthorizedAccount(address account);
+
+    /// The owner can't be the given address, such as the zero address.
+    #[allow(missing_docs)]
+    error OwnableInvalidOwner(address owner);
+}
+
+/// Represents the ways [`Ownable`] and [`Ownable2Step`] methods may fail.
+#[derive(SolidityError)]
+pub enum OwnableError {
+    /// The caller isn't the owner, or for acceptance, the pending owner.
+    UnauthorizedAccount(OwnableUnauthorizedAccount),
+    /// The proposed owner is invalid.
+    InvalidOwner(OwnableInvalidOwner),
+}
+
+/// Tracks the owner of a contract.
+#[storage]
+pub struct Ownable {
+    owner: StorageAddress,
+}
+
+impl Ownable {
+    /// Fails with [`OwnableUnauthorizedAccount`] unless the caller is the owner.
+    pub fn only_owner(&self) -> Result<(), OwnableError> {
+        let account = msg::sender();
+        if account != self.owner.get() {
+            return Err(OwnableUnauthorizedAccount { account }.into());
+        }
+        Ok(())
+    }
+
+    /// Sets the owner without any checks, emitting [`OwnershipTransferred`].
+    ///
+    /// Contracts use this to set the initial owner.
+    pub fn _transfer_ownership(&mut self, new_owner: Address) {
+        let previous_owner = self.owner.get();
+        self.owner.set(new_owner);
+        evm::log(OwnershipTransferred {
+            previousOwner: previous_owner,
+            newOwner: new_owner,
+        });
+    }
+}
+
+#[public]
+impl Ownable {
+    /// The current owner, which is zero if there is none.
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+
+    /// Transfers ownership to `new_owner`, who must not be the zero address.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        if new_owner.is_zero() {
+            return Err(OwnableInvalidOwner { owner: new_owner }.into());
+        }
+        self._transfer_ownership(new_owner);
+        Ok(())
+    }
+
+    /// Gives up ownership, leaving the contract without an owner.
+    ///
+    /// Afterward, methods guarded by [`only_owner`](Self::only_owner) can never be called.
+    pub fn renounce_ownership(&mut self) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        self._transfer_ownership(Address::ZERO);
+        Ok(())
+    }
+}
+
+/// Tracks the owner of a contract, requiring new owners to accept ownership.
+///
+/// This type exposes `owner()` itself rather than inheriting [`Ownable`], so it can be borrowed
+/// by a contract directly.
+#[storage]
+pub struct Ownable2Step {
+    ownable: Ownable,
+    pending_owner: StorageAddress,
+}
+
+impl Ownable2Step {
+    /// Fails with [`OwnableUnauthorizedAccount`] unless the caller is the owner.
+    pub fn only_owner(&self) -> Result<(), OwnableError> {
+        self.ownable.only_owner()
+    }
+
+    /// Sets the owner without any checks, clearing any pending owner.
+    ///
+    /// Contracts use this to set the initial owner.
+    pub fn _transfer_ownership(&mut self, new_owner: Address) {
+        self.pending_owner.erase();
+        self.ownable._transfer_ownership(new_owner);
+    }
+}
+
+#[public]
+impl Ownable2Step {
+    /// The current owner, which is zero if there is none.
+    pub fn owner(&self) -> Address {
+        self.ownable.owner()
+    }
+
+    /// The nominated owner who has yet to accept, which is zero if there is none.
+    pub fn pending_owner(&self) -> Address {
+        self.pending_owner.get()
+    }
+
+    /// Nominates `new_owner`, who takes ownership upon calling `acceptOwnership`.
+    ///
+    /// Nominating the zero address cancels a pending transfer.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        self.pending_owner.set(new_owner);
+        evm::log(OwnershipTransferStarted {
+            previousOwner: self.owner(),
+            newOwner: new_owner,
+        });
+        Ok(())
+    }
+
+    /// Completes a transfer of ownership. Only the pending owner may call this.
+    pub fn accept_ownership(&mut self) -> Result<(), OwnableError> {
+        let account = msg::sender();
+        if account != self.pending_owner.get() || account.is_zero() {
+            return Err(OwnableUnauthorizedAccount { account }.into());
+        }
+        self._transfer_ownership(account);
+        Ok(())
+    }
+
+    /// Gives up ownership, leaving the contract without an owner or pending owner.
+    pub fn renounce_ownership(&mut self) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        self._transfer_ownership(Address::ZERO);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageType, test_host};
+    use alloy_primitives::U256;
+    use alloy_sol_types::SolEvent;
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+
+    fn unauthorized(result: Result<(), OwnableError>) -> Option<Address> {
+        match result {
+            Err(OwnableError::UnauthorizedAccount(err)) => Some(err.account),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_ownable() {
+        let mut ownable = unsafe { Ownable::new(U256::ZERO, 0) };
+        ownable._transfer_ownership(ALICE);
+
+        test_host::set_sender(BOB);
+        assert_eq!(unauthorized(ownable.only_owner()), Some(BOB));
+        assert_eq!(unauthorized(ownable.transfer_ownership(BOB)), Some(BOB));
+        assert_eq!(unauthorized(ownable.renounce_ownership()), Some(BOB));
+
+        test_host::set_sender(ALICE);
+        assert!(ownable.only_owner().is_ok());
+        assert!(matches!(
+            ownable.transfer_ownership(Address::ZERO),
+            Err(OwnableError::InvalidOwner(_))
+        ));
+        assert!(ownable.transfer_ownership(BOB).is_ok());
+        assert_eq!(ownable.owner(), BOB);
+
+        test_host::set_sender(BOB);
+        assert!(ownable.renounce_ownership().is_ok());
+        assert_eq!(ownable.owner(), Address::ZERO);
+
+        let logs = test_host::logs();
+        let transfers: Vec<_> = logs
+            .iter()
+            .map(|(topics, _)| (topics[1], topics[2]))
+            .collect();
+        assert_eq!(
+            transfers,
+            [
+                (Address::ZERO.into_word(), ALICE.into_word()),
+                (ALICE.into_word(), BOB.into_word()),
+                (BOB.into_word(), Address::ZERO.into_word()),
+            ]
+        );
+        assert!(logs
+            .iter()
+            .all(|(topics, _)| topics[0] == OwnershipTransferred::SIGNATURE_HASH));
+    }
+
+    #[test]
+    fn test_ownable_2_step() {
+        let mut ownable = unsafe { Ownable2Step::new(U256::ZERO, 0) };
+        ownable._transfer_ownership(ALICE);
+
+        test_host::set_sender(ALICE);
+        assert!(ownable.transfer_ownership(BOB).is_ok());
+        assert_eq!((ownable.owner(), ownable.pending_owner()), (ALICE, BOB));
+
+        // only the pending owner may accept
+        assert_eq!(unauthorized(ownable.accept_ownership()), Some(ALICE));
+
+        test_host::set_sender(BOB);
+        assert!(ownable.accept_ownership().is_ok());
+        assert_eq!(
+            (ownable.owner(), ownable.pending_owner()),
+            (BOB, Address::ZERO)
+        );
+        assert_eq!(unauthorized(ownable.accept_ownership()), Some(BOB));
+
+        // renouncing clears a pending transfer
+        assert!(ownable.transfer_ownership(ALICE).is_ok());
+        assert!(ownable.renounce_ownership().is_ok());
+        assert_eq!(
+            (ownable.owner(), ownable.pending_owner()),
+            (Address::ZERO, Address::ZERO)
+        );
+
+        // with no pending owner, the zero address can't accept
+        test_host::set_sender(Address::ZERO);
+        assert_eq!(
+            unauthorized(ownable.accept_ownership()),
+            Some(Address::ZERO)
+        );
+
+        let started = test_host::logs()
+            .into_iter()
+            .filter(|(topics, _)| topics[0] == OwnershipTransferStarted::SIGNATURE_HASH)
+            .count();
+        assert_eq!(started, 2);
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! An emergency stop that contracts can trigger to halt selected methods.
+//!
+//! ```ignore
+//! #[storage]
+//! #[entrypoint]
+//! struct Vault {
+//!     #[borrow]
+//!     pausable: Pausable,
+//!     ...
+//! }
+//!
+//! #[public]
+//! #[inherit(Pausable)]
+//! impl Vault {
+//!     pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+//!         self.pausable.when_not_paused()?;
+//!         ...
+//!     }
+//! }
+//! ```
+
+use crate::{
+    evm, msg,
+    storage::StorageBool,
+    stylus_proc::{public, storage, SolidityError},
+};
+use alloy_sol_types::sol;
+
+sol! {
+    /// Emitted when `account` pauses the contract.
+    event Paused(address account);
+
+    /// Emitted when `account` unpauses the contract.
+    event Unpaused(address account);
+
+    /// The operation requires the contract not be paused.
+    error EnforcedPause();
+
+    /// The operation requires the contract be paused.
+    error ExpectedPause();
+}
+
+/// Represents the ways [`Pausable`] methods may fail.
+#[derive(SolidityError)]
+pub enum PausableError {
+    /// The contract is paused.
+    EnforcedPause(EnforcedPause),
+    /// The contract isn't paused.
+    ExpectedPause(ExpectedPause),
+}
+
+/// Tracks whether a contract is paused. Its only public method is `paused()`.
+#[storage]
+pub struct Pausable {
+    paused: StorageBool,
+}
+
+impl Pausable {
+    /// Pauses the contract, emitting [`Paused`] with the caller's address.
+    ///
+    /// Fails if the contract is already paused.
+    pub fn pause(&mut self) -> Result<(), PausableError> {
+        self.when_not_paused()?;
+        self.paused.set(true);
+        evm::log(Paused {
+            account: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Unpauses the contract, emitting [`Unpaused`] with the caller's address.
+    ///
+    /// Fails if the contract isn't paused.
+    pub fn unpause(&mut self) -> Result<(), PausableError> {
+        self.when_paused()?;
+        self.paused.set(false);
+        evm::log(Unpaused {
+            account: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Fails with [`EnforcedPause`] if the contract is paused.
+    pub fn when_not_paused(&self) -> Result<(), PausableError> {
+        match self.paused.get() {
+            true => Err(EnforcedPause {}.into()),
+            false => Ok(()),
+        }
+    }
+
+    /// Fails with [`ExpectedPause`] unless the contract is paused.
+    pub fn when_paused(&self) -> Result<(), PausableError> {
+        match self.paused.get() {
+            true => Ok(()),
+            false => Err(ExpectedPause {}.into()),
+        }
+    }
+}
+
+#[public]
+impl Pausable {
+    /// Whether the contract is paused.
+    pub fn paused(&self) -> bool {
+        self.paused.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageType, test_host};
+    use alloy_primitives::U256;
+    use alloy_sol_types::{SolError, SolEvent};
+
+    #[test]
+    fn test_pause_and_unpause() {
+        let mut pausable = unsafe { Pausable::new(U256::ZERO, 0) };
+        assert!(!pausable.paused());
+        assert!(pausable.when_not_paused().is_ok());
+        assert!(matches!(
+            pausable.unpause(),
+            Err(PausableError::ExpectedPause(_))
+        ));
+
+        assert!(pausable.pause().is_ok());
+        assert!(pausable.paused());
+        assert!(pausable.when_paused().is_ok());
+
+        let err: Vec<u8> = pausable.when_not_paused().unwrap_err().into();
+        assert_eq!(err, EnforcedPause::SELECTOR);
+        assert!(pausable.pause().is_err());
+
+        assert!(pausable.unpause().is_ok());
+        assert!(!pausable.paused());
+
+        let topics: Vec<_> = test_host::logs()
+            .into_iter()
+            .map(|(topics, _)| topics[0])
+            .collect();
+        assert_eq!(topics, [Paused::SIGNATURE_HASH, Unpaused::SIGNATURE_HASH]);
+    }
+}
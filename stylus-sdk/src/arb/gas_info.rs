@@ -0,0 +1,161 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Wrappers for the [`ArbGasInfo`][ArbGasInfo] precompile, which lives at address `0x6c`.
+//!
+//! Arbitrum transactions pay both for L2 execution and for posting their data to L1.
+//! These getters expose the components of that pricing.
+//!
+//! [ArbGasInfo]: https://docs.arbitrum.io/build-decentralized-apps/precompiles/reference#arbgasinfo
+
+use crate::call::{Error, StaticCallContext};
+use alloy_primitives::{address, Address, U256};
+use interface::IArbGasInfo;
+
+/// The address of the `ArbGasInfo` precompile.
+pub const ARB_GAS_INFO_ADDRESS: Address = address!("000000000000000000000000000000000000006c");
+
+mod interface {
+    use stylus_proc::sol_interface;
+
+    sol_interface! {
+        interface IArbGasInfo {
+            function getPricesInWei() external view returns (uint256, uint256, uint256, uint256, uint256, uint256);
+            function getPricesInArbGas() external view returns (uint256, uint256, uint256);
+            function getL1BaseFeeEstimate() external view returns (uint256);
+            function getMinimumGasPrice() external view returns (uint256);
+        }
+    }
+}
+
+/// The current gas prices in wei, as returned by [`get_prices_in_wei`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PricesInWei {
+    /// Cost of the L1 portion of a simple L2 transaction.
+    pub per_l2_tx: U256,
+    /// Cost of each byte of L1 calldata.
+    pub per_l1_calldata_byte: U256,
+    /// Cost of allocating a new storage slot.
+    pub per_storage_allocation: U256,
+    /// The L2 base fee per unit of gas.
+    pub per_arb_gas_base: U256,
+    /// The congestion fee per unit of gas.
+    pub per_arb_gas_congestion: U256,
+    /// The total price per unit of gas.
+    pub per_arb_gas_total: U256,
+}
+
+/// The current gas prices in units of L2 gas, as returned by [`get_prices_in_arb_gas`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PricesInArbGas {
+    /// Gas charged for the L1 portion of a simple L2 transaction.
+    pub per_l2_tx: U256,
+    /// Gas charged for each byte of L1 calldata.
+    pub per_l1_calldata_byte: U256,
+    /// Gas charged for allocating a new storage slot.
+    pub per_storage_allocation: U256,
+}
+
+/// Gets the current gas prices in wei, using the caller's preferred aggregator.
+pub fn get_prices_in_wei(context: impl StaticCallContext) -> Result<PricesInWei, Error> {
+    let prices = IArbGasInfo::new(ARB_GAS_INFO_ADDRESS).get_prices_in_wei(context)?;
+    Ok(PricesInWei {
+        per_l2_tx: prices.0,
+        per_l1_calldata_byte: prices.1,
+        per_storage_allocation: prices.2,
+        per_arb_gas_base: prices.3,
+        per_arb_gas_congestion: prices.4,
+        per_arb_gas_total: prices.5,
+    })
+}
+
+/// Gets the current gas prices in units of L2 gas, using the caller's preferred aggregator.
+pub fn get_prices_in_arb_gas(context: impl StaticCallContext) -> Result<PricesInArbGas, Error> {
+    let prices = IArbGasInfo::new(ARB_GAS_INFO_ADDRESS).get_prices_in_arb_gas(context)?;
+    Ok(PricesInArbGas {
+        per_l2_tx: prices.0,
+        per_l1_calldata_byte: prices.1,
+        per_storage_allocation: prices.2,
+    })
+}
+
+/// Gets the current estimate of the L1 base fee.
+pub fn get_l1_base_fee_estimate(context: impl StaticCallContext) -> Result<U256, Error> {
+    IArbGasInfo::new(ARB_GAS_INFO_ADDRESS).get_l_1_base_fee_estimate(context)
+}
+
+/// Gets the minimum gas price needed for a transaction to succeed.
+pub fn get_minimum_gas_price(context: impl StaticCallContext) -> Result<U256, Error> {
+    IArbGasInfo::new(ARB_GAS_INFO_ADDRESS).get_minimum_gas_price(context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::TopLevelStorage, test_host};
+    use alloc::vec::Vec;
+
+    struct Contract;
+
+    unsafe impl TopLevelStorage for Contract {}
+
+    /// Encodes the words `ArbGasInfo` returns.
+    fn words(values: &[u64]) -> Result<Vec<u8>, Vec<u8>> {
+        let words = values
+            .iter()
+            .map(|&value| U256::from(value).to_be_bytes::<32>());
+        Ok(words.flatten().collect())
+    }
+
+    /// The calldata of the last call, which should be to `ArbGasInfo`.
+    fn last_call() -> Vec<u8> {
+        let (to, data, _) = test_host::calls().pop().unwrap();
+        assert_eq!(to, ARB_GAS_INFO_ADDRESS);
+        data
+    }
+
+    #[test]
+    fn test_prices() {
+        // each component lands in its own field, in the precompile's order
+        test_host::mock_call(ARB_GAS_INFO_ADDRESS, words(&[1, 2, 3, 4, 5, 6]));
+        let prices = PricesInWei {
+            per_l2_tx: U256::from(1),
+            per_l1_calldata_byte: U256::from(2),
+            per_storage_allocation: U256::from(3),
+            per_arb_gas_base: U256::from(4),
+            per_arb_gas_congestion: U256::from(5),
+            per_arb_gas_total: U256::from(6),
+        };
+        assert_eq!(get_prices_in_wei(&Contract), Ok(prices));
+        assert_eq!(last_call(), function_selector!("getPricesInWei"));
+
+        test_host::mock_call(ARB_GAS_INFO_ADDRESS, words(&[7, 8, 9]));
+        let prices = PricesInArbGas {
+            per_l2_tx: U256::from(7),
+            per_l1_calldata_byte: U256::from(8),
+            per_storage_allocation: U256::from(9),
+        };
+        assert_eq!(get_prices_in_arb_gas(&Contract), Ok(prices));
+        assert_eq!(last_call(), function_selector!("getPricesInArbGas"));
+
+        // too few words don't decode
+        test_host::mock_call(ARB_GAS_INFO_ADDRESS, words(&[1, 2]));
+        let result = get_prices_in_arb_gas(&Contract);
+        assert!(matches!(result, Err(Error::AbiDecodingFailed(_))));
+    }
+
+    #[test]
+    fn test_fees() {
+        test_host::mock_call(ARB_GAS_INFO_ADDRESS, words(&[30_000_000_000]));
+        let fee = get_l1_base_fee_estimate(&Contract);
+        assert_eq!(fee, Ok(U256::from(30_000_000_000_u64)));
+        assert_eq!(last_call(), function_selector!("getL1BaseFeeEstimate"));
+
+        test_host::mock_call(ARB_GAS_INFO_ADDRESS, words(&[100_000_000]));
+        assert_eq!(
+            get_minimum_gas_price(&Contract),
+            Ok(U256::from(100_000_000))
+        );
+        assert_eq!(last_call(), function_selector!("getMinimumGasPrice"));
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Typed wrappers for Arbitrum's precompiled contracts.
+//!
+//! Arbitrum chains expose chain-specific functionality, like L2 block numbers and L2-to-L1
+//! messaging, through precompiles at fixed addresses. The functions in this module encode calls
+//! to these precompiles so that contracts needn't declare the interfaces themselves.
+//!
+//! See also [`block`](crate::block), [`contract`](crate::contract), [`evm`](crate::evm),
+//! [`msg`](crate::msg), and [`tx`](crate::tx).
+//!
+//! ```no_run
+//! use stylus_sdk::{alloy_primitives::U256, arb, call::StaticCallContext};
+//!
+//! fn l2_block_number(context: impl StaticCallContext) -> U256 {
+//!     arb::sys::arb_block_number(context).unwrap()
+//! }
+//! ```
+
+pub mod gas_info;
+pub mod sys;
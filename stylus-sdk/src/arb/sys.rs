@@ -0,0 +1,106 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Wrappers for the [`ArbSys`][ArbSys] precompile, which lives at address `0x64`.
+//!
+//! Unlike [`block::number`](crate::block::number), which returns an estimate of the L1 block
+//! number, [`arb_block_number`] returns the number of the current L2 block.
+//!
+//! [ArbSys]: https://docs.arbitrum.io/build-decentralized-apps/precompiles/reference#arbsys
+
+use crate::call::{Error, MutatingCallContext, StaticCallContext};
+use alloy_primitives::{address, Address, Bytes, B256, U256};
+use interface::IArbSys;
+
+/// The address of the `ArbSys` precompile.
+pub const ARB_SYS_ADDRESS: Address = address!("0000000000000000000000000000000000000064");
+
+mod interface {
+    use stylus_proc::sol_interface;
+
+    sol_interface! {
+        interface IArbSys {
+            function arbBlockNumber() external view returns (uint256);
+            function arbBlockHash(uint256 arb_block_num) external view returns (bytes32);
+            function sendTxToL1(address destination, bytes calldata data) external payable returns (uint256);
+        }
+    }
+}
+
+/// Gets the number of the current L2 block.
+pub fn arb_block_number(context: impl StaticCallContext) -> Result<U256, Error> {
+    IArbSys::new(ARB_SYS_ADDRESS).arb_block_number(context)
+}
+
+/// Gets the hash of the given L2 block, which must be within the 256 most recent blocks.
+/// The precompile reverts for blocks outside this range.
+pub fn arb_block_hash(context: impl StaticCallContext, number: U256) -> Result<B256, Error> {
+    IArbSys::new(ARB_SYS_ADDRESS).arb_block_hash(context, number)
+}
+
+/// Sends a transaction to L1, returning the unique identifier of the L2-to-L1 message.
+///
+/// The call's value is transferred to `destination` on L1 once the message is executed.
+/// Because the call is payable, it requires a [`MutatingCallContext`].
+pub fn send_tx_to_l1(
+    context: impl MutatingCallContext,
+    destination: Address,
+    data: impl Into<Bytes>,
+) -> Result<U256, Error> {
+    IArbSys::new(ARB_SYS_ADDRESS).send_tx_to_l_1(context, destination, data.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abi, call::Call, storage::TopLevelStorage, test_host};
+    use alloc::{vec, vec::Vec};
+
+    struct Contract;
+
+    unsafe impl TopLevelStorage for Contract {}
+
+    /// The calldata of the last call, which should be to `ArbSys`.
+    fn last_call() -> Vec<u8> {
+        let (to, data, _) = test_host::calls().pop().unwrap();
+        assert_eq!(to, ARB_SYS_ADDRESS);
+        data
+    }
+
+    #[test]
+    fn test_block_queries() {
+        test_host::mock_call(ARB_SYS_ADDRESS, Ok(U256::from(1234).to_be_bytes_vec()));
+        assert_eq!(arb_block_number(&Contract), Ok(U256::from(1234)));
+        assert_eq!(last_call(), function_selector!("arbBlockNumber"));
+
+        let hash = B256::repeat_byte(0xbb);
+        test_host::mock_call(ARB_SYS_ADDRESS, Ok(hash.to_vec()));
+        assert_eq!(arb_block_hash(&Contract, U256::from(7)), Ok(hash));
+        let selector = function_selector!("arbBlockHash", U256);
+        assert_eq!(last_call(), abi::encode_call(selector, &(U256::from(7),)));
+
+        // the precompile reverts for blocks out of range
+        let revert = b"invalid block number".to_vec();
+        test_host::mock_call(ARB_SYS_ADDRESS, Err(revert.clone()));
+        let result = arb_block_hash(&Contract, U256::from(1));
+        assert_eq!(result, Err(Error::Revert(revert)));
+    }
+
+    #[test]
+    fn test_send_tx_to_l1() {
+        let destination = Address::repeat_byte(0xd0);
+        let value = U256::from(5);
+        let selector = function_selector!("sendTxToL1", Address, abi::Bytes);
+        let calldata = abi::encode_call(selector, &(destination, abi::Bytes(vec![1, 2, 3])));
+
+        // the call sends the value along with the message
+        test_host::mock_call(ARB_SYS_ADDRESS, Ok(U256::from(42).to_be_bytes_vec()));
+        test_host::expect_call(ARB_SYS_ADDRESS, &calldata, value);
+        let mut contract = Contract;
+        let context = Call::new_in(&mut contract).value(value);
+        let result = send_tx_to_l1(context, destination, vec![1, 2, 3]);
+        assert_eq!(result, Ok(U256::from(42)));
+        test_host::verify_expectations();
+        assert_eq!(last_call(), calldata);
+    }
+}
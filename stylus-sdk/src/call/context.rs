@@ -1,7 +1,7 @@
 // Copyright 2022-2024, Offchain Labs, Inc.
 // For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
 
-use crate::storage::TopLevelStorage;
+use crate::{evm, storage::TopLevelStorage};
 
 use super::{CallContext, MutatingCallContext, NonPayableCallContext, StaticCallContext};
 use alloy_primitives::U256;
@@ -10,7 +10,7 @@ use cfg_if::cfg_if;
 /// Enables configurable calls to other contracts.
 #[derive(Debug, Clone)]
 pub struct Call<S, const HAS_VALUE: bool = false> {
-    gas: u64,
+    gas: Gas,
     value: Option<U256>,
     storage: S,
 }
@@ -31,7 +31,7 @@ where
     ///
     /// ```no_run
     /// use stylus_sdk::call::{Call, Error};
-    /// use stylus_sdk::{prelude::*, evm, msg, alloy_primitives::Address};
+    /// use stylus_sdk::{prelude::*, msg, alloy_primitives::Address};
     /// extern crate alloc;
     ///
     /// sol_interface! {
@@ -47,7 +47,7 @@ where
     /// ) -> Result<String, Error> {
     ///
     ///     let config = Call::new_in(storage)
-    ///         .gas(evm::gas_left() / 2)        // limit to half the gas left
+    ///         .gas_fraction(1, 2)              // limit to half the gas left
     ///         .value(msg::value());            // set the callvalue
     ///
     ///     account.make_payment(config, user)   // note the snake case
@@ -61,17 +61,54 @@ where
     /// [`new`]: Call::new
     pub fn new_in(storage: &'a mut S) -> Self {
         Self {
-            gas: u64::MAX,
+            gas: Gas::Limit(u64::MAX),
             value: None,
             storage,
         }
     }
 }
 
+impl<'a, S> Call<&'a S, false>
+where
+    S: TopLevelStorage + 'a,
+{
+    /// Similar to [`new_static`], but intended for projects and libraries using reentrant patterns.
+    ///
+    /// Since static calls can't modify state, a shared reference to [`TopLevelStorage`] suffices.
+    /// See [`new_in`] for why one is required.
+    ///
+    /// [`new_static`]: Call::new_static
+    /// [`new_in`]: Call::new_in
+    pub fn new_static_in(storage: &'a S) -> StaticCall<&'a S> {
+        StaticCall {
+            gas: Gas::Limit(u64::MAX),
+            _storage: storage,
+        }
+    }
+}
+
 impl<S, const HAS_VALUE: bool> Call<S, HAS_VALUE> {
     /// Amount of gas to supply the call.
     /// Values greater than the amount provided will be clipped to all gas left.
     pub fn gas(self, gas: u64) -> Self {
+        let gas = Gas::Limit(gas);
+        Self { gas, ..self }
+    }
+
+    /// Supplies the call a fraction of the gas left at the time it's made.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `denominator` is zero.
+    pub fn gas_fraction(self, numerator: u64, denominator: u64) -> Self {
+        assert!(denominator != 0, "gas fraction with zero denominator");
+        let gas = Gas::Fraction(numerator, denominator);
+        Self { gas, ..self }
+    }
+
+    /// Supplies the call all of the gas left at the time it's made, except for `reserve`.
+    pub fn gas_all_but(self, reserve: u64) -> Self {
+        let gas = Gas::AllBut(reserve);
         Self { gas, ..self }
     }
 
@@ -86,14 +123,181 @@ impl<S, const HAS_VALUE: bool> Call<S, HAS_VALUE> {
     }
 }
 
+/// Configures a read-only call to another contract, made in a static context.
+///
+/// Unlike [`Call`], this can't carry value, and only the `pure` and `view` methods of a
+/// [`sol_interface!`] accept it. Create one with [`Call::new_static`] or [`Call::new_static_in`].
+///
+/// [`sol_interface!`]: crate::prelude::sol_interface
+#[derive(Debug, Clone)]
+pub struct StaticCall<S> {
+    gas: Gas,
+    /// Borrowed only to tie the call to the contract's storage.
+    _storage: S,
+}
+
+impl<S> StaticCall<S> {
+    /// Amount of gas to supply the call.
+    /// Values greater than the amount provided will be clipped to all gas left.
+    pub fn gas(self, gas: u64) -> Self {
+        let gas = Gas::Limit(gas);
+        Self { gas, ..self }
+    }
+
+    /// Supplies the call a fraction of the gas left at the time it's made.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `denominator` is zero.
+    pub fn gas_fraction(self, numerator: u64, denominator: u64) -> Self {
+        assert!(denominator != 0, "gas fraction with zero denominator");
+        let gas = Gas::Fraction(numerator, denominator);
+        Self { gas, ..self }
+    }
+
+    /// Supplies the call all of the gas left at the time it's made, except for `reserve`.
+    pub fn gas_all_but(self, reserve: u64) -> Self {
+        let gas = Gas::AllBut(reserve);
+        Self { gas, ..self }
+    }
+}
+
+/// Gas and value settings shared by many calls, defined once and bound to storage for each call.
+///
+/// Unlike a [`Call`], a preset doesn't borrow storage, so it may be a `const` or kept around
+/// between calls. Binding it with [`call_in`] or [`static_call_in`] borrows the contract's
+/// [`TopLevelStorage`] as [`Call::new_in`] does, keeping calls that may reenter sound.
+///
+/// ```no_run
+/// use stylus_sdk::call::{Call, CallPreset};
+/// use stylus_sdk::{prelude::*, storage::StorageAddress};
+///
+/// const ORACLE_CALL: CallPreset = CallPreset::new().gas_all_but(20_000);
+///
+/// #[storage]
+/// pub struct Contract {
+///     oracle: StorageAddress,
+/// }
+///
+/// unsafe impl TopLevelStorage for Contract {}
+///
+/// impl Contract {
+///     fn oracle_call(&mut self) -> Call<&mut Self> {
+///         ORACLE_CALL.call_in(self)
+///     }
+/// }
+/// ```
+///
+/// [`call_in`]: CallPreset::call_in
+/// [`static_call_in`]: CallPreset::static_call_in
+/// [`TopLevelStorage`]: crate::storage::TopLevelStorage
+#[derive(Debug, Clone, Copy)]
+pub struct CallPreset<const HAS_VALUE: bool = false> {
+    gas: Gas,
+    value: Option<U256>,
+}
+
+impl CallPreset<false> {
+    /// Begin configuring a preset, which by default supplies calls all the gas left.
+    pub const fn new() -> Self {
+        Self {
+            gas: Gas::Limit(u64::MAX),
+            value: None,
+        }
+    }
+
+    /// Configures a read-only call, in a static context, with the preset's gas.
+    pub fn static_call_in<S: TopLevelStorage>(self, storage: &S) -> StaticCall<&S> {
+        StaticCall {
+            gas: self.gas,
+            _storage: storage,
+        }
+    }
+}
+
+impl Default for CallPreset<false> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const HAS_VALUE: bool> CallPreset<HAS_VALUE> {
+    /// Amount of gas to supply calls. See [`Call::gas`].
+    pub const fn gas(self, gas: u64) -> Self {
+        let gas = Gas::Limit(gas);
+        Self { gas, ..self }
+    }
+
+    /// Supplies calls a fraction of the gas left when they're made. See [`Call::gas_fraction`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `denominator` is zero.
+    pub const fn gas_fraction(self, numerator: u64, denominator: u64) -> Self {
+        assert!(denominator != 0, "gas fraction with zero denominator");
+        let gas = Gas::Fraction(numerator, denominator);
+        Self { gas, ..self }
+    }
+
+    /// Supplies calls all of the gas left when they're made, except for `reserve`.
+    pub const fn gas_all_but(self, reserve: u64) -> Self {
+        let gas = Gas::AllBut(reserve);
+        Self { gas, ..self }
+    }
+
+    /// Amount of ETH in wei to give the other contract on each call. See [`Call::value`].
+    pub const fn value(self, value: U256) -> CallPreset<true> {
+        CallPreset {
+            gas: self.gas,
+            value: Some(value),
+        }
+    }
+
+    /// Configures a call with the preset's gas and value.
+    pub fn call_in<S: TopLevelStorage>(self, storage: &mut S) -> Call<&mut S, HAS_VALUE> {
+        Call {
+            gas: self.gas,
+            value: self.value,
+            storage,
+        }
+    }
+}
+
+impl<S> CallContext for StaticCall<S> {
+    fn gas(&self) -> u64 {
+        self.gas.resolve()
+    }
+}
+
 impl<S, const HAS_VALUE: bool> CallContext for Call<S, HAS_VALUE> {
     fn gas(&self) -> u64 {
-        self.gas
+        self.gas.resolve()
+    }
+}
+
+/// How much gas to supply a call, which may depend on the gas left when it's made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Gas {
+    Limit(u64),
+    Fraction(u64, u64),
+    AllBut(u64),
+}
+
+impl Gas {
+    fn resolve(self) -> u64 {
+        match self {
+            Self::Limit(gas) => gas,
+            Self::Fraction(numerator, denominator) => {
+                let gas = evm::gas_left() as u128 * numerator as u128 / denominator as u128;
+                gas.try_into().unwrap_or(u64::MAX)
+            }
+            Self::AllBut(reserve) => evm::gas_left().saturating_sub(reserve),
+        }
     }
 }
 
 // allow &self as a context
-impl<'a, T> CallContext for &'a T
+impl<T> CallContext for &T
 where
     T: TopLevelStorage,
 {
@@ -113,10 +317,10 @@ where
 }
 
 // allow &self to be a `pure` and `static` call context
-impl<'a, T> StaticCallContext for &'a T where T: TopLevelStorage {}
+impl<T> StaticCallContext for &T where T: TopLevelStorage {}
 
 // allow &mut self to be a `pure` and `static` call context
-impl<'a, T> StaticCallContext for &'a mut T where T: TopLevelStorage {}
+impl<T> StaticCallContext for &mut T where T: TopLevelStorage {}
 
 // allow &mut self to be a `write` and `payable` call context
 unsafe impl<T> MutatingCallContext for &mut T
@@ -137,6 +341,8 @@ cfg_if! {
 
         impl<S: TopLevelStorage> StaticCallContext for Call<&S, false> {}
 
+        impl<S: TopLevelStorage> StaticCallContext for StaticCall<&S> {}
+
         impl<S: TopLevelStorage> StaticCallContext for Call<&mut S, false> {}
 
         impl<S: TopLevelStorage> NonPayableCallContext for Call<&mut S, false> {}
@@ -153,6 +359,8 @@ cfg_if! {
 
         impl<S> StaticCallContext for Call<S, false> {}
 
+        impl<S> StaticCallContext for StaticCall<S> {}
+
         impl<S> NonPayableCallContext for Call<S, false> {}
 
         unsafe impl<S, const HAS_VALUE: bool> MutatingCallContext for Call<S, HAS_VALUE> {
@@ -180,7 +388,7 @@ cfg_if! {
             ///
             /// ```ignore
             /// use stylus_sdk::call::{Call, Error};
-            /// use stylus_sdk::{prelude::*, evm, msg, alloy_primitives::Address};
+            /// use stylus_sdk::{prelude::*, msg, alloy_primitives::Address};
             /// extern crate alloc;
             ///
             /// sol_interface! {
@@ -191,7 +399,7 @@ cfg_if! {
             ///
             /// pub fn do_call(account: IService, user: Address) -> Result<String, Error> {
             ///     let config = Call::new()
-            ///         .gas(evm::gas_left() / 2)       // limit to half the gas left
+            ///         .gas_fraction(1, 2)             // limit to half the gas left
             ///         .value(msg::value());           // set the callvalue
             ///
             ///     account.make_payment(config, user)  // note the snake case
@@ -201,11 +409,89 @@ cfg_if! {
             /// [OpenOptions]: https://doc.rust-lang.org/stable/std/fs/struct.OpenOptions.html
             pub fn new() -> Self {
                 Self {
-                    gas: u64::MAX,
+                    gas: Gas::Limit(u64::MAX),
                     value: None,
                     storage: (),
                 }
             }
+
+            /// Begin configuring a read-only call, for the `pure` and `view` methods of other
+            /// contracts.
+            ///
+            /// The resulting [`StaticCall`] can't be given value or passed to methods that might
+            /// modify state, so mistakes are caught at compile time.
+            ///
+            /// This is not available if `reentrant` feature is enabled. See [`Call::new_static_in`].
+            ///
+            /// ```ignore
+            /// use stylus_sdk::call::{Call, Error};
+            /// use stylus_sdk::{prelude::*, alloy_primitives::{Address, U256}};
+            ///
+            /// sol_interface! {
+            ///     interface IToken {
+            ///         function balanceOf(address owner) external view returns (uint256);
+            ///     }
+            /// }
+            ///
+            /// pub fn balance(token: IToken, owner: Address) -> Result<U256, Error> {
+            ///     let config = Call::new_static().gas_fraction(1, 2);
+            ///     token.balance_of(config, owner)
+            /// }
+            /// ```
+            pub fn new_static() -> StaticCall<()> {
+                StaticCall {
+                    gas: Gas::Limit(u64::MAX),
+                    _storage: (),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Contract;
+
+    unsafe impl TopLevelStorage for Contract {}
+
+    #[test]
+    fn test_gas() {
+        fn gas(context: impl CallContext) -> u64 {
+            context.gas()
         }
+
+        // the test host always has u64::MAX gas left
+        let mut contract = Contract;
+        let call = Call::new_static_in(&contract);
+        assert_eq!(gas(call.clone().gas(1000)), 1000);
+        assert_eq!(gas(call.clone().gas_fraction(1, 2)), u64::MAX / 2);
+        assert_eq!(gas(call.clone().gas_fraction(3, 4)), u64::MAX / 4 * 3 + 2);
+        assert_eq!(gas(call.clone().gas_fraction(2, 1)), u64::MAX);
+        assert_eq!(gas(call.gas_all_but(5000)), u64::MAX - 5000);
+
+        let call = Call::new_in(&mut contract)
+            .gas_all_but(5000)
+            .value(U256::from(1));
+        assert_eq!(gas(call), u64::MAX - 5000);
+    }
+
+    #[test]
+    fn test_call_preset() {
+        const PRESET: CallPreset<true> = CallPreset::new()
+            .gas(50_000)
+            .value(U256::from_limbs([7, 0, 0, 0]));
+
+        let mut contract = Contract;
+        let call = PRESET.call_in(&mut contract);
+        assert_eq!(CallContext::gas(&call), 50_000);
+        assert_eq!(MutatingCallContext::value(&call), U256::from(7));
+
+        // presets are reusable, and default to all the gas left
+        let call = PRESET.gas_all_but(1_000).call_in(&mut contract);
+        assert_eq!(CallContext::gas(&call), u64::MAX - 1_000);
+        let call = CallPreset::new().static_call_in(&contract);
+        assert_eq!(CallContext::gas(&call), u64::MAX);
     }
 }
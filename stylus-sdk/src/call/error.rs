@@ -5,6 +5,11 @@ use alloc::vec::Vec;
 use alloy_sol_types::{Panic, PanicKind, SolError};
 
 /// Represents error data when a call fails.
+///
+/// As in the EVM, the VM only reports whether a call succeeded, so a call that runs out of gas or
+/// otherwise fails without reverting is a [`Revert`](Self::Revert) with no data. Unlike most
+/// reverts, such a call uses all the gas it was given, which callers wishing to tell the two apart
+/// may check with [`evm::gas_left`](crate::evm::gas_left).
 #[derive(Debug, PartialEq)]
 pub enum Error {
     /// Revert data returned by the other contract.
@@ -13,6 +18,27 @@ pub enum Error {
     AbiDecodingFailed(alloy_sol_types::Error),
 }
 
+impl Error {
+    /// Decodes the revert data of a failed call as the callee's error type, returning [`None`]
+    /// if the call didn't revert or the data doesn't match any of its errors.
+    ///
+    /// ```ignore
+    /// match token.transfer(&mut *self, to, amount) {
+    ///     Ok(success) => ...,
+    ///     Err(err) => match err.decode_revert::<Erc20Error>() {
+    ///         Some(Erc20Error::InsufficientBalance(err)) => ...,
+    ///         _ => return Err(err.into()),
+    ///     },
+    /// }
+    /// ```
+    pub fn decode_revert<E: FromRevert>(&self) -> Option<E> {
+        match self {
+            Error::Revert(data) => E::from_revert(data),
+            Error::AbiDecodingFailed(_) => None,
+        }
+    }
+}
+
 impl From<alloy_sol_types::Error> for Error {
     fn from(err: alloy_sol_types::Error) -> Self {
         Error::AbiDecodingFailed(err)
@@ -35,6 +61,13 @@ impl MethodError for Error {
     }
 }
 
+impl MethodError for crate::math::MathError {
+    #[inline]
+    fn encode(self) -> Vec<u8> {
+        From::from(self)
+    }
+}
+
 impl<T: SolError> MethodError for T {
     #[inline]
     fn encode(self) -> Vec<u8> {
@@ -42,6 +75,48 @@ impl<T: SolError> MethodError for T {
     }
 }
 
+/// Decode an error from revert data, the inverse of [`MethodError`].
+///
+/// This is implemented for errors declared with [`sol!`][sol] and for enums deriving
+/// [`SolidityError`][SolidityError]. It should not be necessary to implement this.
+///
+/// [sol]: alloy_sol_types::sol
+/// [SolidityError]: crate::prelude::SolidityError
+pub trait FromRevert: Sized {
+    /// Decodes the error, returning [`None`] if the data is for some other error.
+    fn from_revert(data: &[u8]) -> Option<Self>;
+}
+
+/// Accepts any revert, such as for a catch-all variant of a [`SolidityError`][SolidityError].
+///
+/// [SolidityError]: crate::prelude::SolidityError
+impl FromRevert for Error {
+    #[inline]
+    fn from_revert(data: &[u8]) -> Option<Self> {
+        Some(Error::Revert(data.to_vec()))
+    }
+}
+
+/// Accepts the `Panic` Solidity raises for the same failure.
+impl FromRevert for crate::math::MathError {
+    #[inline]
+    fn from_revert(data: &[u8]) -> Option<Self> {
+        let panic = alloy_sol_types::Panic::abi_decode(data, true).ok()?;
+        match panic.kind()? {
+            alloy_sol_types::PanicKind::UnderOverflow => Some(Self::Overflow),
+            alloy_sol_types::PanicKind::DivisionByZero => Some(Self::DivisionByZero),
+            _ => None,
+        }
+    }
+}
+
+impl<T: SolError> FromRevert for T {
+    #[inline]
+    fn from_revert(data: &[u8]) -> Option<Self> {
+        T::abi_decode(data, true).ok()
+    }
+}
+
 impl From<Error> for Vec<u8> {
     #[allow(unused)]
     fn from(err: Error) -> Vec<u8> {
@@ -54,3 +129,87 @@ impl From<Error> for Vec<u8> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stylus_proc::SolidityError;
+    use alloy_primitives::{Address, U256};
+    use alloy_sol_types::sol;
+
+    sol! {
+        error InsufficientBalance(address from, uint256 have, uint256 want);
+        error Paused();
+        error Unknown();
+    }
+
+    #[derive(SolidityError)]
+    enum TokenError {
+        InsufficientBalance(InsufficientBalance),
+        Paused(Paused),
+    }
+
+    #[derive(SolidityError)]
+    enum CatchAll {
+        Paused(Paused),
+        Other(Error),
+    }
+
+    #[test]
+    fn test_decode_revert() {
+        let err = InsufficientBalance {
+            from: Address::repeat_byte(1),
+            have: U256::from(1),
+            want: U256::from(2),
+        };
+        let revert = Error::Revert(err.abi_encode());
+        let Some(TokenError::InsufficientBalance(decoded)) = revert.decode_revert() else {
+            panic!("failed to decode revert");
+        };
+        assert_eq!((decoded.from, decoded.want), (err.from, err.want));
+        assert!(revert.decode_revert::<Paused>().is_none());
+
+        let revert = Error::Revert(Paused {}.abi_encode());
+        assert!(matches!(
+            revert.decode_revert(),
+            Some(TokenError::Paused(_))
+        ));
+        assert!(matches!(revert.decode_revert(), Some(CatchAll::Paused(_))));
+
+        // unknown errors only match catch-all variants
+        let revert = Error::Revert(Unknown {}.abi_encode());
+        assert!(revert.decode_revert::<TokenError>().is_none());
+        let Some(CatchAll::Other(other)) = revert.decode_revert() else {
+            panic!("failed to catch revert");
+        };
+        assert_eq!(other, revert);
+
+        let failed = Error::AbiDecodingFailed(alloy_sol_types::Error::Overrun);
+        assert!(failed.decode_revert::<CatchAll>().is_none());
+    }
+
+    #[cfg(feature = "export-abi")]
+    #[test]
+    fn test_exported_errors() {
+        use crate::abi::export::internal::InnerTypes;
+        use alloc::vec::Vec;
+
+        // catch-all variants aren't declared
+        let names: Vec<_> = CatchAll::inner_types()
+            .into_iter()
+            .map(|ty| ty.name)
+            .collect();
+        assert_eq!(names, ["error Paused();"]);
+        let names: Vec<_> = TokenError::inner_types()
+            .into_iter()
+            .map(|ty| ty.name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "error InsufficientBalance(address, uint256, uint256);",
+                "error Paused();"
+            ]
+        );
+    }
+}
@@ -15,7 +15,11 @@ use alloc::vec::Vec;
 use alloy_primitives::Address;
 
 pub use self::{
-    context::Call, error::Error, error::MethodError, raw::RawCall, traits::*,
+    context::{Call, CallPreset, StaticCall},
+    error::Error,
+    error::{FromRevert, MethodError},
+    raw::RawCall,
+    traits::*,
     transfer::transfer_eth,
 };
 
@@ -92,3 +96,79 @@ pub fn call(context: impl MutatingCallContext, to: Address, data: &[u8]) -> Resu
             .map_err(Error::Revert)
     }}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{evm, storage::TopLevelStorage, test_host};
+    use alloc::vec;
+
+    struct Contract;
+
+    unsafe impl TopLevelStorage for Contract {}
+
+    /// Gives the first oracle half the gas left, trying the second with the rest if it fails.
+    fn query(contract: &mut Contract, oracles: [Address; 2]) -> Result<Vec<u8>, Error> {
+        let first = call(
+            Call::new_in(contract).gas_fraction(1, 2),
+            oracles[0],
+            b"query",
+        );
+        first.or_else(|_| call(Call::new_in(contract), oracles[1], b"query"))
+    }
+
+    #[test]
+    fn test_gas_budgeting() {
+        let oracles = [Address::repeat_byte(1), Address::repeat_byte(2)];
+        test_host::set_gas_left(100_000);
+        test_host::set_call_gas(10_000);
+
+        test_host::mock_call(oracles[0], Ok(b"first".to_vec()));
+        assert_eq!(query(&mut Contract, oracles).ok(), Some(b"first".to_vec()));
+        assert_eq!(evm::gas_left(), 90_000);
+
+        // the first oracle uses all 45k gas it's given, leaving 45k for the second
+        test_host::mock_call_out_of_gas(oracles[0]);
+        test_host::mock_call(oracles[1], Ok(b"second".to_vec()));
+        assert_eq!(query(&mut Contract, oracles).ok(), Some(b"second".to_vec()));
+        assert_eq!(evm::gas_left(), 35_000);
+
+        let calls = test_host::calls();
+        assert_eq!(calls[0], (oracles[0], b"query".to_vec(), 50_000));
+        assert_eq!(
+            calls[1..],
+            [
+                (oracles[0], b"query".to_vec(), 45_000),
+                (oracles[1], b"query".to_vec(), 45_000),
+            ]
+        );
+
+        // out-of-gas calls fail without return data
+        test_host::mock_call_out_of_gas(oracles[1]);
+        let Err(Error::Revert(data)) = query(&mut Contract, oracles) else {
+            panic!("expected the calls to fail");
+        };
+        assert_eq!(data, vec![]);
+        assert_eq!(evm::gas_left(), 0);
+    }
+
+    #[test]
+    fn test_out_of_gas() {
+        let target = Address::repeat_byte(3);
+        test_host::set_gas_left(100_000);
+        test_host::set_call_gas(1_000);
+        let call_target = || call(Call::new_in(&mut Contract).gas(20_000), target, b"x");
+
+        // running out of gas looks like reverting without data, which typed errors don't match
+        test_host::mock_call_out_of_gas(target);
+        let err = call_target().unwrap_err();
+        assert_eq!(err, Error::Revert(vec![]));
+        assert!(err.decode_revert::<crate::math::MathError>().is_none());
+        assert_eq!(evm::gas_left(), 80_000);
+
+        // but uses all the gas given, unlike an empty revert
+        test_host::mock_call(target, Err(vec![]));
+        assert_eq!(call_target(), Err(Error::Revert(vec![])));
+        assert_eq!(evm::gas_left(), 79_000);
+    }
+}
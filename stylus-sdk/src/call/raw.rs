@@ -1,11 +1,13 @@
 // Copyright 2023-2024, Offchain Labs, Inc.
 // For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
 
+use super::Error;
 use crate::{
     contract::{read_return_data, RETURN_DATA_LEN},
     hostio, tx, ArbResult,
 };
 use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::SolType;
 use cfg_if::cfg_if;
 
 #[cfg(feature = "reentrant")]
@@ -59,24 +61,6 @@ pub(crate) enum CachePolicy {
     Clear,
 }
 
-#[derive(Copy, Clone)]
-#[repr(C)]
-struct RustVec {
-    ptr: *mut u8,
-    len: usize,
-    cap: usize,
-}
-
-impl Default for RustVec {
-    fn default() -> Self {
-        Self {
-            ptr: core::ptr::null_mut(),
-            len: 0,
-            cap: 0,
-        }
-    }
-}
-
 impl RawCall {
     /// Begin configuring the raw call, similar to how [`std::fs::OpenOptions`][OpenOptions] works.
     ///
@@ -225,7 +209,7 @@ impl RawCall {
             };
 
             unsafe {
-                RETURN_DATA_LEN.set(outs_len);
+                (*core::ptr::addr_of_mut!(RETURN_DATA_LEN)).set(outs_len);
             }
 
             let outs = read_return_data(self.offset, self.size);
@@ -235,4 +219,47 @@ impl RawCall {
             }
         }
     }
+
+    unsafe_reentrant! {
+        /// Performs a raw call like [`call`], then ABI-decodes the return data as a `T`.
+        ///
+        /// The return data is decoded as `abi.decode(data, (T))` would, matching the methods
+        /// generated by [`sol_interface!`]. A revert is returned as [`Error::Revert`], while
+        /// return data that fails to decode is an [`Error::AbiDecodingFailed`].
+        ///
+        /// ```no_run
+        /// use stylus_sdk::call::RawCall;
+        /// use stylus_sdk::alloy_sol_types::sol_data::Uint;
+        /// use stylus_sdk::{alloy_primitives::{address, U256}, function_selector};
+        ///
+        /// let token = address!("361594F5429D23ECE0A88E4fBE529E1c49D524d8");
+        /// let calldata = function_selector!("totalSupply");
+        ///
+        /// # #[allow(unused_unsafe)]
+        /// let supply: U256 = unsafe {
+        ///     RawCall::new_static()
+        ///         .call_decoding::<Uint<256>>(token, &calldata)
+        ///         .unwrap()
+        /// };
+        /// ```
+        ///
+        /// # Safety
+        ///
+        /// This function becomes `unsafe` when the `reentrant` feature is enabled, for the same
+        /// reasons as [`call`].
+        ///
+        /// [`call`]: RawCall::call
+        /// [`sol_interface!`]: crate::prelude::sol_interface
+        /// [`Error::Revert`]: super::Error::Revert
+        /// [`Error::AbiDecodingFailed`]: super::Error::AbiDecodingFailed
+        pub fn call_decoding<T: SolType>(
+            self,
+            contract: Address,
+            calldata: &[u8],
+        ) -> Result<T::RustType, Error> {
+            #[allow(unused_unsafe)]
+            let outs = unsafe { self.call(contract, calldata) }.map_err(Error::Revert)?;
+            Ok(<(T,) as SolType>::abi_decode_params(&outs, true)?.0)
+        }
+    }
 }
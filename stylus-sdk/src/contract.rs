@@ -13,13 +13,18 @@
 //! ```
 
 use crate::{
+    abi::Router,
     hostio::{self, wrap_hostio},
+    msg,
+    storage::TopLevelStorage,
     types::AddressVM,
+    ArbResult,
 };
-use alloc::vec::Vec;
-use alloy_primitives::{Address, U256};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{Address, B256, U256};
+use core::borrow::BorrowMut;
 
-/// Reads the invocation's calldata.
+/// Reads the invocation's calldata, recording its selector for [`msg::sig`].
 /// The [`entrypoint`](macro@stylus_proc::entrypoint) macro uses this under the hood.
 pub fn args(len: usize) -> Vec<u8> {
     let mut input = Vec::with_capacity(len);
@@ -27,6 +32,13 @@ pub fn args(len: usize) -> Vec<u8> {
         hostio::read_args(input.as_mut_ptr());
         input.set_len(len);
     }
+    #[cfg(not(any(test, feature = "stylus-test")))]
+    {
+        let mut sig = [0; 4];
+        let prefix = &input[..len.min(4)];
+        sig[..prefix.len()].copy_from_slice(prefix);
+        unsafe { (*core::ptr::addr_of_mut!(msg::SIG)).set(sig.into()) };
+    }
     input
 }
 
@@ -69,3 +81,214 @@ wrap_hostio!(
 pub fn balance() -> U256 {
     address().balance()
 }
+
+/// Gets the code deployed at the given address, which is empty for [`EOA`]s.
+/// The semantics are equivalent to that of the EVM's [`EXT_CODECOPY`] opcode.
+///
+/// [`EOA`]: https://ethereum.org/en/developers/docs/accounts/#types-of-account
+/// [`EXT_CODECOPY`]: https://www.evm.codes/#3C
+pub fn code_at(address: Address) -> Vec<u8> {
+    address.code()
+}
+
+/// Gets the size in bytes of the code deployed at the given address, which is `0` for [`EOA`]s.
+/// This is Solidity's `address.code.length`, and is equivalent to the EVM's [`EXT_CODESIZE`] opcode.
+///
+/// [`EOA`]: https://ethereum.org/en/developers/docs/accounts/#types-of-account
+/// [`EXT_CODESIZE`]: https://www.evm.codes/#3B
+pub fn code_size_at(address: Address) -> usize {
+    address.code_size()
+}
+
+/// Gets the hash of the code deployed at the given address, as Solidity's `address.codehash`.
+/// The semantics are equivalent to that of the EVM's [`EXT_CODEHASH`] opcode, so existing accounts
+/// without code hash to `keccak("")`, while accounts that don't exist hash to zero.
+///
+/// [`EXT_CODEHASH`]: https://www.evm.codes/#3F
+pub fn code_hash_at(address: Address) -> B256 {
+    address.code_hash()
+}
+
+/// Whether the given address has code, as Solidity's `address.code.length > 0`.
+///
+/// Useful for deciding whether to call a receiver hook, like those of ERC-721 and ERC-1155, but
+/// not for telling [`EOA`]s apart from contracts. A contract only gets its code once its constructor
+/// finishes, so this returns `false` for contracts being deployed, including calls made from their
+/// constructors, as well as for addresses where contracts will be deployed in the future.
+///
+/// [`EOA`]: https://ethereum.org/en/developers/docs/accounts/#types-of-account
+pub fn is_contract(address: Address) -> bool {
+    code_size_at(address) > 0
+}
+
+/// Calls one of the contract's own public methods by selector, routing through its [`Router`]
+/// rather than making an external call. `args` holds the ABI-encoded arguments, without the
+/// selector. This allows patterns like a multicall that batches calls to the contract's methods.
+///
+/// The method runs as part of the current call, so [`msg::sender`] and [`msg::value`] are
+/// unchanged, and a payable method is still needed for calls with value. The entrypoint's
+/// reentrancy rules still apply: during a reentrant call, only methods allowing reentrancy can be
+/// reached. Like the entrypoint, unknown selectors revert with empty data, but they aren't passed
+/// to a [`#[fallback]`][fallback].
+///
+/// ```ignore
+/// #[public]
+/// impl Contract {
+///     pub fn multicall(&mut self, calls: Vec<Bytes>) -> Result<Vec<Bytes>, Vec<u8>> {
+///         let mut results = vec![];
+///         for call in calls {
+///             // calls too short for a selector revert, like unknown ones
+///             let Some(selector) = call.get(..4) else {
+///                 return Err(vec![]);
+///             };
+///             let selector = u32::from_be_bytes(selector.try_into().unwrap());
+///             results.push(contract::call_self(self, selector, &call[4..])?.into());
+///         }
+///         Ok(results)
+///     }
+/// }
+/// ```
+///
+/// [fallback]: macro@stylus_proc::public#handling-unknown-calls-fallback
+pub fn call_self<S>(storage: &mut S, selector: u32, args: &[u8]) -> ArbResult
+where
+    S: Router<S> + TopLevelStorage + BorrowMut<S::Storage>,
+{
+    let allow_reentrant = S::allow_reentrant(selector).unwrap_or(cfg!(feature = "reentrant"));
+    if !allow_reentrant && msg::reentrant() {
+        return Err(vec![]);
+    }
+    S::route(storage, selector, args).unwrap_or_else(|| Err(vec![]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abi::Bytes,
+        crypto,
+        storage::{StorageType, StorageU256},
+        stylus_proc::{public, storage},
+        test_host,
+    };
+    use alloy_primitives::FixedBytes;
+    use alloy_sol_types::{sol_data, SolType};
+
+    #[test]
+    fn test_code_at() {
+        let contract = Address::repeat_byte(0xc0);
+        let code = [0x60, 0x80, 0x60, 0x40];
+        test_host::set_code(contract, &code);
+
+        assert_eq!(code_at(contract), code);
+        assert_eq!(code_size_at(contract), 4);
+        assert_eq!(code_hash_at(contract), crypto::keccak(code));
+
+        let eoa = Address::repeat_byte(0xe0);
+        assert_eq!(code_at(eoa), Vec::<u8>::new());
+        assert_eq!(code_size_at(eoa), 0);
+        assert_eq!(code_hash_at(eoa), B256::ZERO);
+    }
+
+    #[test]
+    fn test_sig() {
+        test_host::set_calldata(&[0xa9, 0x05, 0x9c, 0xbb, 0x01]);
+        assert_eq!(args(5), [0xa9, 0x05, 0x9c, 0xbb, 0x01]);
+        assert_eq!(msg::sig(), FixedBytes([0xa9, 0x05, 0x9c, 0xbb]));
+
+        test_host::set_calldata(&[0x12, 0x34]);
+        args(2);
+        assert_eq!(msg::sig(), FixedBytes([0x12, 0x34, 0x00, 0x00]));
+    }
+
+    #[test]
+    fn test_is_contract() {
+        let contract = Address::repeat_byte(0xc1);
+        assert!(!is_contract(contract));
+
+        test_host::set_code(contract, &[0x00]);
+        assert!(is_contract(contract));
+        assert!(!is_contract(Address::repeat_byte(0xe1)));
+    }
+
+    #[storage]
+    struct Counter {
+        count: StorageU256,
+    }
+
+    unsafe impl TopLevelStorage for Counter {}
+
+    #[public]
+    impl Counter {
+        fn add(&mut self, amount: U256) -> U256 {
+            let count = self.count.get() + amount;
+            self.count.set(count);
+            count
+        }
+
+        #[allow_reentrant]
+        fn count(&self) -> U256 {
+            self.count.get()
+        }
+
+        fn sig() -> FixedBytes<4> {
+            msg::sig()
+        }
+
+        fn multicall(&mut self, calls: Vec<Bytes>) -> Result<Vec<Bytes>, Vec<u8>> {
+            let mut results = vec![];
+            for call in calls {
+                let Some(selector) = call.get(..4) else {
+                    return Err(vec![]);
+                };
+                let selector = u32::from_be_bytes(selector.try_into().unwrap());
+                results.push(call_self(self, selector, &call[4..])?.into());
+            }
+            Ok(results)
+        }
+    }
+
+    #[test]
+    fn test_call_self() {
+        const ADD: [u8; 4] = function_selector!("add", U256);
+        const COUNT: [u8; 4] = function_selector!("count");
+        let mut counter = unsafe { Counter::new(U256::from(0x6c6c), 0) };
+
+        let add = |amount: u64| {
+            let mut call = ADD.to_vec();
+            call.extend(sol_data::Uint::<256>::abi_encode(&U256::from(amount)));
+            Bytes::from(call)
+        };
+        let results = counter.multicall(vec![add(2), add(3), COUNT.to_vec().into()]);
+        let results = results.unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(*results[2], U256::from(5).to_be_bytes::<32>());
+        assert_eq!(counter.count.get(), U256::from(5));
+
+        // unknown selectors revert, as do calls too short to have one
+        assert_eq!(call_self(&mut counter, 0xdeadbeef, &[]), Err(vec![]));
+        let short = Bytes::from(COUNT[..2].to_vec());
+        assert_eq!(counter.multicall(vec![short]), Err(vec![]));
+
+        // routed methods see the outer call's selector
+        const SIG: [u8; 4] = function_selector!("sig");
+        const MULTICALL: [u8; 4] = function_selector!("multicall", Vec<Bytes>);
+        test_host::set_calldata(&MULTICALL);
+        let results = counter.multicall(vec![SIG.to_vec().into()]).unwrap();
+        assert_eq!(results[0][..4], MULTICALL);
+
+        let selector = u32::from_be_bytes;
+        assert_eq!(
+            <Counter as Router<Counter>>::allow_reentrant(selector(COUNT)),
+            Some(true)
+        );
+        assert_eq!(
+            <Counter as Router<Counter>>::allow_reentrant(selector(ADD)),
+            Some(cfg!(feature = "reentrant"))
+        );
+        assert_eq!(
+            <Counter as Router<Counter>>::allow_reentrant(0xdeadbeef),
+            None
+        );
+    }
+}
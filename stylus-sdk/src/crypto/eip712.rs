@@ -0,0 +1,293 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Hashing of [EIP-712] typed structured data.
+//!
+//! Structs declared with alloy's [`sol!`] macro implement [`SolStruct`], which knows each
+//! struct's type string, including those of any nested structs. The functions here apply
+//! EIP-712's recursive encoding rules to such structs and their members:
+//!
+//! - atomic values like `uint256` and `address` are padded to a 32-byte word
+//! - `bytes` and `string` are hashed
+//! - arrays, including `address[]` and arrays of structs, hash the concatenated encodings of their elements
+//! - nested structs are replaced by their [`hash_struct`]
+//!
+//! ```no_run
+//! use stylus_sdk::{alloy_primitives::address, alloy_sol_types::sol, crypto::eip712};
+//!
+//! sol! {
+//!     struct Order {
+//!         address maker;
+//!         address[] allowed_takers;
+//!     }
+//! }
+//!
+//! let order = Order {
+//!     maker: address!("361594F5429D23ECE0A88E4fBE529E1c49D524d8"),
+//!     allowed_takers: vec![],
+//! };
+//! let hash = eip712::hash_struct(&order);
+//! ```
+//!
+//! To produce the digest a signer actually signs, combine the struct's hash with a
+//! [`domain_separator`] via [`hash_typed_data`].
+//!
+//! ```no_run
+//! # use stylus_sdk::{alloy_primitives::{address, B256}, crypto::eip712};
+//! # let struct_hash = B256::ZERO;
+//! let domain = eip712::domain_separator(
+//!     "Permit2",
+//!     "1",
+//!     42161,
+//!     address!("000000000022D473030F116dDEE9F6B43aC78BA3"),
+//! );
+//! let digest = eip712::hash_typed_data(domain, struct_hash);
+//! ```
+//!
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+//! [`sol!`]: alloy_sol_types::sol
+
+use crate::crypto::keccak;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{SolStruct, SolValue};
+
+/// The `typeHash` of `EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)`.
+pub const DOMAIN_TYPE_HASH: B256 = B256::new(
+    crate::keccak_const::Keccak256::new()
+        .update(
+            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+        )
+        .finalize(),
+);
+
+/// Computes the domain separator for a domain with the common `name`, `version`, `chainId`,
+/// and `verifyingContract` fields.
+///
+/// Contracts usually pass [`block::chainid`](crate::block::chainid) and
+/// [`contract::address`](crate::contract::address) for the latter two. For domains with other
+/// fields, such as a `salt`, use alloy's [`Eip712Domain`](alloy_sol_types::Eip712Domain).
+pub fn domain_separator(
+    name: &str,
+    version: &str,
+    chain_id: u64,
+    verifying_contract: Address,
+) -> B256 {
+    let mut encoded = Vec::with_capacity(5 * 32);
+    encoded.extend(DOMAIN_TYPE_HASH);
+    encoded.extend(keccak(name));
+    encoded.extend(keccak(version));
+    encoded.extend(U256::from(chain_id).to_be_bytes::<32>());
+    encoded.extend(verifying_contract.into_word());
+    keccak(encoded)
+}
+
+/// Computes the digest to be signed for a struct, which is the hash of
+/// `"\x19\x01" || domainSeparator || hashStruct(message)`.
+pub fn hash_typed_data(domain_separator: B256, struct_hash: B256) -> B256 {
+    let mut encoded = [0; 66];
+    encoded[..2].copy_from_slice(&[0x19, 0x01]);
+    encoded[2..34].copy_from_slice(domain_separator.as_slice());
+    encoded[34..].copy_from_slice(struct_hash.as_slice());
+    keccak(encoded)
+}
+
+/// Computes the EIP-712 `typeHash` of a struct declared with [`sol!`], which is the hash of its
+/// type string followed by those of the structs it references, sorted by name.
+///
+/// ```no_run
+/// use stylus_sdk::{alloy_sol_types::sol, eip712_type_hash};
+///
+/// sol! {
+///     struct Permit {
+///         address owner;
+///         address spender;
+///         uint256 value;
+///         uint256 nonce;
+///         uint256 deadline;
+///     }
+/// }
+///
+/// let type_hash = eip712_type_hash!(Permit);
+/// ```
+///
+/// [`sol!`]: alloy_sol_types::sol
+#[macro_export]
+macro_rules! eip712_type_hash {
+    ($ty:ty) => {
+        $crate::crypto::keccak(
+            <$ty as $crate::alloy_sol_types::SolStruct>::eip712_encode_type().as_bytes(),
+        )
+    };
+}
+
+/// Computes the EIP-712 `hashStruct` of a value, which is the hash of its type's `typeHash`
+/// followed by the encoding of each member.
+pub fn hash_struct<T: SolStruct>(value: &T) -> B256 {
+    value.eip712_hash_struct()
+}
+
+/// Computes the 32-byte EIP-712 `encodeData` word for a single member value.
+///
+/// This is the word a value of type `T` contributes when it appears as a member of a struct,
+/// which for arrays and nested structs is itself a hash computed recursively.
+pub fn encode_data<T: SolValue>(value: &T) -> B256 {
+    value.eip712_data_word()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, keccak256, U256};
+    use alloy_sol_types::{eip712_domain, sol};
+
+    sol! {
+        struct Person {
+            string name;
+            address[] wallets;
+        }
+
+        struct Mail {
+            Person from;
+            Person[] to;
+            string contents;
+        }
+    }
+
+    fn mail() -> Mail {
+        Mail {
+            from: Person {
+                name: "Cow".into(),
+                wallets: vec![
+                    address!("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+                    address!("DeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"),
+                ],
+            },
+            to: vec![Person {
+                name: "Bob".into(),
+                wallets: vec![
+                    address!("bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+                    address!("B0BdaBea57B0BDABeA57b0bdABEA57b0BDabEa57"),
+                    address!("B0B0b0b0b0b0B000000000000000000000000000"),
+                ],
+            }],
+            contents: "Hello, Bob!".into(),
+        }
+    }
+
+    #[test]
+    fn test_nested_arrays() {
+        // the eth_signTypedData_v4 reference example
+        let domain = eip712_domain! {
+            name: "Ether Mail",
+            version: "1",
+            chain_id: 1,
+            verifying_contract: address!("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"),
+        };
+        let mail = mail();
+
+        let mut digest = vec![0x19, 0x01];
+        digest.extend(domain.separator());
+        digest.extend(hash_struct(&mail));
+        assert_eq!(
+            keccak256(digest),
+            b256!("a85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2")
+        );
+    }
+
+    #[test]
+    fn test_domain_and_digest() {
+        let contract = address!("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC");
+        let domain = eip712_domain! {
+            name: "Ether Mail",
+            version: "1",
+            chain_id: 1,
+            verifying_contract: contract,
+        };
+        let separator = domain_separator("Ether Mail", "1", 1, contract);
+        assert_eq!(separator, domain.separator());
+
+        // unit tests run with Arbitrum One's chain id
+        let arbitrum = eip712_domain! {
+            name: "Ether Mail",
+            version: "1",
+            chain_id: 42161,
+            verifying_contract: contract,
+        };
+        assert_eq!(
+            domain_separator("Ether Mail", "1", crate::block::chainid(), contract),
+            arbitrum.separator()
+        );
+        assert_eq!(
+            DOMAIN_TYPE_HASH,
+            b256!("8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f")
+        );
+
+        let mail = mail();
+        assert_eq!(
+            hash_typed_data(separator, hash_struct(&mail)),
+            mail.eip712_signing_hash(&domain)
+        );
+        assert_eq!(
+            eip712_type_hash!(Mail),
+            keccak256(
+                "Mail(Person from,Person[] to,string contents)Person(string name,address[] wallets)"
+            )
+        );
+        assert_eq!(eip712_type_hash!(Person), mail.from.eip712_type_hash());
+    }
+
+    #[test]
+    fn test_empty_dynamic_members() {
+        // empty strings and arrays still hash, rather than encoding as zero
+        let empty = keccak256([]);
+        assert_eq!(encode_data(&alloc::string::String::new()), empty);
+        assert_eq!(encode_data(&Vec::<Address>::new()), empty);
+        assert_eq!(encode_data(&Vec::<Person>::new()), empty);
+
+        let nobody = Person {
+            name: "".into(),
+            wallets: vec![],
+        };
+        let mut encoded = eip712_type_hash!(Person).to_vec();
+        encoded.extend(empty);
+        encoded.extend(empty);
+        assert_eq!(hash_struct(&nobody), keccak256(encoded));
+    }
+
+    #[test]
+    fn test_recursive_encoding() {
+        let mail = mail();
+
+        // address[] hashes the concatenation of its padded elements
+        let wallets: Vec<u8> = mail
+            .from
+            .wallets
+            .iter()
+            .flat_map(|w| w.into_word())
+            .collect();
+        assert_eq!(encode_data(&mail.from.wallets), keccak256(wallets));
+
+        // uint256[] does the same
+        let amounts = vec![U256::from(1), U256::from(2)];
+        let words: Vec<u8> = amounts.iter().flat_map(|x| x.to_be_bytes::<32>()).collect();
+        assert_eq!(encode_data(&amounts), keccak256(words));
+
+        // nested structs are replaced by their hashStruct
+        assert_eq!(encode_data(&mail.from), hash_struct(&mail.from));
+
+        // arrays of structs hash the concatenation of each element's hashStruct
+        let to: Vec<u8> = mail.to.iter().flat_map(hash_struct).collect();
+        assert_eq!(encode_data(&mail.to), keccak256(to));
+
+        // the struct's own hash covers its typeHash and each member's encoding
+        let type_hash = keccak256(
+            "Mail(Person from,Person[] to,string contents)Person(string name,address[] wallets)",
+        );
+        let mut encoded = type_hash.to_vec();
+        encoded.extend(encode_data(&mail.from));
+        encoded.extend(encode_data(&mail.to));
+        encoded.extend(keccak256(&mail.contents));
+        assert_eq!(hash_struct(&mail), keccak256(encoded));
+    }
+}
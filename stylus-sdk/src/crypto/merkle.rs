@@ -0,0 +1,176 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Verification of Merkle proofs, compatible with OpenZeppelin's `MerkleProof` library and
+//! `@openzeppelin/merkle-tree`.
+//!
+//! Each pair of nodes is hashed in sorted order, so proofs don't need to say which side each
+//! sibling is on. This is useful for airdrops and allowlists, where a contract stores just the
+//! root of a tree of entries and each claimant supplies a proof of their own.
+//!
+//! # Hashing leaves
+//!
+//! Leaves should be hashed twice, as [`standard_leaf`] does. A single hash of 64 bytes of data
+//! could collide with an internal node, letting an attacker prove a "leaf" that was never in the
+//! tree. Hashing the encoded values twice avoids this, and matches the leaves of the
+//! `StandardMerkleTree` built by `@openzeppelin/merkle-tree`.
+//!
+//! ```no_run
+//! use stylus_sdk::{alloy_primitives::{Address, B256, U256}, crypto::merkle};
+//!
+//! fn can_claim(root: B256, proof: &[B256], account: Address, amount: U256) -> bool {
+//!     let leaf = merkle::standard_leaf(&(account, amount));
+//!     merkle::verify(proof, root, leaf)
+//! }
+//! ```
+
+use crate::crypto::keccak;
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use alloy_sol_types::{abi::TokenSeq, SolType, SolValue};
+
+/// Hashes a leaf's values the way OpenZeppelin's `StandardMerkleTree` does, as
+/// `keccak256(keccak256(abi.encode(values...)))`.
+///
+/// Pass multiple values as a tuple, such as `&(account, amount)`.
+pub fn standard_leaf<T: SolValue>(values: &T) -> B256
+where
+    for<'a> <T::SolType as SolType>::Token<'a>: TokenSeq<'a>,
+{
+    keccak(keccak(values.abi_encode_params()))
+}
+
+/// Hashes a pair of nodes in sorted order.
+pub fn hash_pair(a: B256, b: B256) -> B256 {
+    let (a, b) = if a < b { (a, b) } else { (b, a) };
+    let mut data = [0; 64];
+    data[..32].copy_from_slice(a.as_slice());
+    data[32..].copy_from_slice(b.as_slice());
+    keccak(data)
+}
+
+/// Computes the root of the tree implied by a `leaf` and its `proof`.
+pub fn process_proof(proof: &[B256], leaf: B256) -> B256 {
+    proof
+        .iter()
+        .fold(leaf, |node, &sibling| hash_pair(node, sibling))
+}
+
+/// Whether `proof` proves that `leaf` is in the tree with the given `root`.
+pub fn verify(proof: &[B256], root: B256, leaf: B256) -> bool {
+    process_proof(proof, leaf) == root
+}
+
+/// Computes the root of the tree implied by a multiproof, or `None` if it's malformed.
+///
+/// A multiproof proves several `leaves` at once. Each of the `proof_flags` says whether the next
+/// hash combines two nodes already known, taken from the leaves and then the hashes computed so
+/// far, or one such node with the next node of the `proof`. The `leaves` must be in the order
+/// `@openzeppelin/merkle-tree`'s `getMultiProof` returns them.
+pub fn process_multi_proof(proof: &[B256], proof_flags: &[bool], leaves: &[B256]) -> Option<B256> {
+    let total_hashes = proof_flags.len();
+    if leaves.len() + proof.len() != total_hashes + 1 {
+        return None;
+    }
+
+    let mut hashes = Vec::with_capacity(total_hashes);
+    let (mut leaf_pos, mut hash_pos, mut proof_pos) = (0, 0, 0);
+    let mut next_known = |hashes: &[B256]| match leaf_pos < leaves.len() {
+        true => {
+            leaf_pos += 1;
+            Some(leaves[leaf_pos - 1])
+        }
+        false => {
+            hash_pos += 1;
+            hashes.get(hash_pos - 1).copied()
+        }
+    };
+    for &flag in proof_flags {
+        let a = next_known(&hashes)?;
+        let b = match flag {
+            true => next_known(&hashes)?,
+            false => {
+                proof_pos += 1;
+                *proof.get(proof_pos - 1)?
+            }
+        };
+        hashes.push(hash_pair(a, b));
+    }
+
+    match (hashes.last(), leaves.first()) {
+        (Some(&root), _) => (proof_pos == proof.len()).then_some(root),
+        (None, Some(&leaf)) => Some(leaf),
+        (None, None) => proof.first().copied(),
+    }
+}
+
+/// Whether a multiproof proves that all of the `leaves` are in the tree with the given `root`.
+///
+/// See [`process_multi_proof`] for the meaning of the arguments.
+pub fn verify_multi_proof(
+    proof: &[B256],
+    proof_flags: &[bool],
+    root: B256,
+    leaves: &[B256],
+) -> bool {
+    process_multi_proof(proof, proof_flags, leaves) == Some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, U256};
+
+    #[test]
+    fn test_standard_tree() {
+        // the example from @openzeppelin/merkle-tree's readme
+        let values = [
+            (
+                address!("1111111111111111111111111111111111111111"),
+                5000000000000000000u128,
+            ),
+            (
+                address!("2222222222222222222222222222222222222222"),
+                2500000000000000000u128,
+            ),
+        ];
+        let root = b256!("d4dee0beab2d53f2cc83e567171bd2820e49898130a22622b10ead383e90bd77");
+
+        let leaves = values.map(|(account, amount)| standard_leaf(&(account, U256::from(amount))));
+        assert!(verify(&[leaves[1]], root, leaves[0]));
+        assert!(verify(&[leaves[0]], root, leaves[1]));
+        assert!(!verify(&[leaves[0]], root, leaves[0]));
+
+        // a single hash of the encoding isn't a valid leaf
+        let (account, amount) = values[0];
+        let single = keccak((account, U256::from(amount)).abi_encode_params());
+        assert!(!verify(&[leaves[1]], root, single));
+    }
+
+    #[test]
+    fn test_multi_proof() {
+        let [a, b, c, d] = [0u8, 1, 2, 3].map(|i| keccak([i]));
+        let (ab, cd) = (hash_pair(a, b), hash_pair(c, d));
+        let root = hash_pair(ab, cd);
+
+        // order within a pair doesn't matter
+        assert!(verify(&[d, ab], root, c));
+        assert!(verify(&[c, ab], root, d));
+        assert_eq!(hash_pair(ab, cd), hash_pair(cd, ab));
+
+        // proving a, b, and c needs only d
+        let flags = [true, false, true];
+        assert!(verify_multi_proof(&[d], &flags, root, &[a, b, c]));
+        assert!(!verify_multi_proof(&[a], &flags, root, &[a, b, c]));
+        assert!(verify_multi_proof(&[cd], &[true, false], root, &[a, b]));
+
+        // malformed proofs are rejected rather than panicking
+        assert_eq!(process_multi_proof(&[d], &[true, false], &[a, b, c]), None);
+        assert_eq!(process_multi_proof(&[b, c], &[false, true], &[a]), None);
+        assert_eq!(process_multi_proof(&[], &[true], &[a]), None);
+
+        // trees of one leaf
+        assert!(verify_multi_proof(&[], &[], a, &[a]));
+        assert!(verify_multi_proof(&[a], &[], a, &[]));
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2022-2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! VM-accelerated cryptography.
+//!
+//! See also [`block`](crate::block), [`contract`](crate::contract), [`evm`](crate::evm),
+//! [`msg`](crate::msg), and [`tx`](crate::tx).
+//!
+//! ```no_run
+//! use stylus_sdk::crypto;
+//! use stylus_sdk::alloy_primitives::address;
+//!
+//! let preimage = address!("361594F5429D23ECE0A88E4fBE529E1c49D524d8");
+//! let hash = crypto::keccak(&preimage);
+//! ```
+
+use crate::call::RawCall;
+use alloy_primitives::{address, uint, Address, B256, U256};
+
+pub mod eip712;
+pub mod merkle;
+pub mod signature;
+
+/// Efficiently computes the [`keccak256`] hash of the given preimage.
+///
+/// [`keccak256`]: https://en.wikipedia.org/wiki/SHA-3
+pub fn keccak<T: AsRef<[u8]>>(bytes: T) -> B256 {
+    alloy_primitives::keccak256(bytes)
+}
+
+/// The address of the `ecrecover` precompile.
+pub const ECRECOVER_ADDRESS: Address = address!("0000000000000000000000000000000000000001");
+
+/// Half the order of the secp256k1 curve. Signatures with a larger `s` are malleable.
+const SECP256K1_HALF_ORDER: U256 =
+    uint!(0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0_U256);
+
+/// Recovers the address that signed `hash` via the `ecrecover` precompile.
+///
+/// Returns `None` if the signature is invalid. Unlike the raw precompile, this also rejects
+/// signatures whose `s` is in the upper half of the curve order, since each such signature has
+/// a twin that recovers to the same address. The recovery byte `v` must be `27` or `28`.
+pub fn ecrecover(hash: B256, v: u8, r: B256, s: B256) -> Option<Address> {
+    if U256::from_be_bytes(s.0) > SECP256K1_HALF_ORDER || !matches!(v, 27 | 28) {
+        return None;
+    }
+    let mut input = [0; 128];
+    input[..32].copy_from_slice(hash.as_slice());
+    input[63] = v;
+    input[64..96].copy_from_slice(r.as_slice());
+    input[96..].copy_from_slice(s.as_slice());
+
+    // the precompile can't reenter, so the call is safe regardless of the reentrant feature
+    #[allow(unused_unsafe)]
+    let output = unsafe { RawCall::new_static().call(ECRECOVER_ADDRESS, &input) }.ok()?;
+    if output.len() != 32 {
+        return None;
+    }
+    let signer = Address::from_word(B256::from_slice(&output));
+    (!signer.is_zero()).then_some(signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecrecover_rejects_malleable() {
+        // n - 1, which lies in the upper half of the curve order
+        let high_s = B256::from(SECP256K1_HALF_ORDER * U256::from(2));
+        assert_eq!(ecrecover(B256::ZERO, 27, B256::ZERO, high_s), None);
+        assert_eq!(ecrecover(B256::ZERO, 0, B256::ZERO, B256::ZERO), None);
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Parsing of ECDSA signatures supplied as raw bytes.
+//!
+//! Signatures usually arrive as a 65-byte `bytes` argument laid out as `r || s || v`. Slicing
+//! such input by hand panics when a caller supplies fewer bytes, so the helpers here check the
+//! length first and return a typed error instead.
+//!
+//! ```
+//! use stylus_sdk::crypto::signature;
+//!
+//! let mut sig = [0u8; 65];
+//! sig[64] = 27;
+//! let (r, s, v) = signature::split(&sig).unwrap();
+//! assert_eq!(v, 27);
+//!
+//! assert!(signature::split(&sig[..64]).is_err());
+//! ```
+//!
+//! The 64-byte compact form of [EIP-2098] is supported via [`parse_2098`] and [`to_2098`].
+//!
+//! [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U256};
+
+/// Splits a 65-byte `r || s || v` signature into its components.
+///
+/// The recovery byte `v` is returned as is, whether it's `27`/`28` or `0`/`1`.
+pub fn split(signature: &[u8]) -> Result<(B256, B256, u8), InvalidSignatureLength> {
+    let Ok(signature) = <&[u8; 65]>::try_from(signature) else {
+        return Err(InvalidSignatureLength(signature.len()));
+    };
+    let r = B256::from_slice(&signature[..32]);
+    let s = B256::from_slice(&signature[32..64]);
+    Ok((r, s, signature[64]))
+}
+
+/// Joins signature components into the 65-byte `r || s || v` layout.
+pub fn join(r: B256, s: B256, v: u8) -> [u8; 65] {
+    let mut signature = [0; 65];
+    signature[..32].copy_from_slice(r.as_slice());
+    signature[32..64].copy_from_slice(s.as_slice());
+    signature[64] = v;
+    signature
+}
+
+/// Expands an [EIP-2098] compact signature into its `r`, `s`, and `v` components.
+///
+/// The compact form stores the parity of the signature's `y` coordinate in the top bit of `s`,
+/// which is always free since valid signatures have `s` in the lower half of the curve order.
+/// The returned `v` is `27` or `28`.
+///
+/// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+pub fn parse_2098(compact: &[u8; 64]) -> (B256, B256, u8) {
+    let r = B256::from_slice(&compact[..32]);
+    let mut s = B256::from_slice(&compact[32..]);
+    let y_parity = s[0] >> 7;
+    s[0] &= 0x7f;
+    (r, s, 27 + y_parity)
+}
+
+/// Packs signature components into the [EIP-2098] compact form.
+///
+/// Accepts `v` as either `27`/`28` or `0`/`1`. Returns `None` if `v` is anything else, or if the
+/// top bit of `s` is set, since such a signature has no compact representation.
+///
+/// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+pub fn to_2098(r: B256, s: B256, v: u8) -> Option<[u8; 64]> {
+    let y_parity = match v {
+        0 | 27 => 0,
+        1 | 28 => 1,
+        _ => return None,
+    };
+    if s[0] & 0x80 != 0 {
+        return None;
+    }
+    let mut compact = [0; 64];
+    compact[..32].copy_from_slice(r.as_slice());
+    compact[32..].copy_from_slice(s.as_slice());
+    compact[32] |= y_parity << 7;
+    Some(compact)
+}
+
+/// Error for a signature that isn't 65 bytes long, holding the length it had.
+///
+/// As revert data, this is the Solidity custom error `InvalidSignatureLength(uint256 length)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidSignatureLength(pub usize);
+
+impl From<InvalidSignatureLength> for Vec<u8> {
+    fn from(value: InvalidSignatureLength) -> Self {
+        let mut data = Vec::with_capacity(36);
+        data.extend(crate::function_selector!("InvalidSignatureLength", U256));
+        data.extend(U256::from(value.0).to_be_bytes::<32>());
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_join() {
+        let r = B256::repeat_byte(0x11);
+        let s = B256::repeat_byte(0x22);
+        let signature = join(r, s, 28);
+        assert_eq!(split(&signature), Ok((r, s, 28)));
+
+        assert_eq!(split(&signature[..64]), Err(InvalidSignatureLength(64)));
+        assert_eq!(split(&[0; 66]), Err(InvalidSignatureLength(66)));
+        assert_eq!(split(&[]), Err(InvalidSignatureLength(0)));
+
+        let revert: Vec<u8> = InvalidSignatureLength(64).into();
+        assert_eq!(
+            revert[..4],
+            crate::function_selector!("InvalidSignatureLength", U256)
+        );
+        assert_eq!(U256::from_be_slice(&revert[4..]), U256::from(64));
+    }
+
+    #[test]
+    fn test_eip_2098() {
+        use alloy_primitives::b256;
+
+        // the test vectors from EIP-2098
+        let vectors = [
+            (
+                b256!("68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90"),
+                b256!("7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"),
+                27,
+                b256!("7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"),
+            ),
+            (
+                b256!("9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76"),
+                b256!("139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"),
+                28,
+                b256!("939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"),
+            ),
+        ];
+        for (r, s, v, y_parity_and_s) in vectors {
+            let compact = to_2098(r, s, v).unwrap();
+            assert_eq!(compact[..32], r[..]);
+            assert_eq!(compact[32..], y_parity_and_s[..]);
+            assert_eq!(parse_2098(&compact), (r, s, v));
+            assert_eq!(to_2098(r, s, v - 27), Some(compact));
+        }
+
+        let high_s = B256::repeat_byte(0x80);
+        assert_eq!(to_2098(B256::ZERO, high_s, 27), None);
+        assert_eq!(to_2098(B256::ZERO, B256::ZERO, 29), None);
+    }
+}
@@ -11,6 +11,18 @@
 //! let arbinaut = address!("361594F5429D23ECE0A88E4fBE529E1c49D524d8");
 //! console!("Gm {}", arbinaut); // prints nothing in production
 //! ```
+//!
+//! The [`debug!`](crate::debug!), [`info!`](crate::info!), [`warn!`](crate::warn!), and [`error!`](crate::error!) macros work like [`console!`](crate::console!) but prefix
+//! each message with its [`Level`]. Setting the `STYLUS_LOG_LEVEL` environment variable at
+//! build time to one of `debug`, `info`, `warn`, or `error` compiles out the less severe levels.
+//!
+//! ```no_run
+//! use stylus_sdk::{info, warn};
+//! extern crate alloc;
+//!
+//! info!("minted {} tokens", 100);
+//! warn!("allowance is nearly exhausted"); // prints "[WARN] allowance is nearly exhausted"
+//! ```
 
 /// Prints a UTF-8 encoded string to the console. Only available in debug mode.
 #[cfg(feature = "debug")]
@@ -34,3 +46,143 @@ macro_rules! console {
 macro_rules! console {
     ($($msg:tt)*) => {{}};
 }
+
+/// The severity of a message logged with [`debug!`](crate::debug!), [`info!`](crate::info!), [`warn!`](crate::warn!), or [`error!`](crate::error!).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Verbose information useful when debugging.
+    Debug,
+    /// Notable events during normal operation.
+    Info,
+    /// Suspicious conditions that don't prevent execution.
+    Warn,
+    /// Failures.
+    Error,
+}
+
+impl Level {
+    /// The prefix printed before messages of this level.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+        }
+    }
+
+    /// Parses a lowercase level name, panicking if it isn't one.
+    const fn parse(name: &str) -> Self {
+        const fn eq(a: &[u8], b: &[u8]) -> bool {
+            if a.len() != b.len() {
+                return false;
+            }
+            let mut i = 0;
+            while i < a.len() {
+                if a[i] != b[i] {
+                    return false;
+                }
+                i += 1;
+            }
+            true
+        }
+        let name = name.as_bytes();
+        if eq(name, b"debug") {
+            Self::Debug
+        } else if eq(name, b"info") {
+            Self::Info
+        } else if eq(name, b"warn") {
+            Self::Warn
+        } else if eq(name, b"error") {
+            Self::Error
+        } else {
+            panic!("STYLUS_LOG_LEVEL must be one of debug, info, warn, or error")
+        }
+    }
+}
+
+/// The least severe [`Level`] that gets printed, as set by `STYLUS_LOG_LEVEL` at build time.
+pub const LOG_LEVEL: Level = match option_env!("STYLUS_LOG_LEVEL") {
+    Some(name) => Level::parse(name),
+    None => Level::Debug,
+};
+
+/// Whether messages of the given [`Level`] are printed.
+pub const fn enabled(level: Level) -> bool {
+    level as u8 >= LOG_LEVEL as u8
+}
+
+/// Prints to the console with the given [`Level`] prefix when executing in a debug environment
+/// and the level is [enabled](enabled). Otherwise does nothing.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($msg:tt)*) => {{
+        let level: $crate::debug::Level = $level;
+        if $crate::debug::enabled(level) {
+            $crate::console!("[{}] {}", level.as_str(), format_args!($($msg)*));
+        }
+    }};
+}
+
+/// Logs a message at [`Level::Debug`]. See [`log!`](crate::log!).
+#[macro_export]
+macro_rules! debug {
+    ($($msg:tt)*) => {
+        $crate::log!($crate::debug::Level::Debug, $($msg)*)
+    };
+}
+
+/// Logs a message at [`Level::Info`]. See [`log!`](crate::log!).
+#[macro_export]
+macro_rules! info {
+    ($($msg:tt)*) => {
+        $crate::log!($crate::debug::Level::Info, $($msg)*)
+    };
+}
+
+/// Logs a message at [`Level::Warn`]. See [`log!`](crate::log!).
+#[macro_export]
+macro_rules! warn {
+    ($($msg:tt)*) => {
+        $crate::log!($crate::debug::Level::Warn, $($msg)*)
+    };
+}
+
+/// Logs a message at [`Level::Error`]. See [`log!`](crate::log!).
+#[macro_export]
+macro_rules! error {
+    ($($msg:tt)*) => {
+        $crate::log!($crate::debug::Level::Error, $($msg)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        assert!(enabled(Level::Error));
+        assert_eq!(enabled(Level::Debug), LOG_LEVEL == Level::Debug);
+        assert_eq!(Level::parse("warn"), Level::Warn);
+        assert!(Level::Info < Level::Warn);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn test_log_macros() {
+        crate::debug!("x = {}", 1);
+        crate::warn!("low balance");
+        crate::error!("{} failed", "transfer");
+        let expected = [
+            (Level::Debug, "[DEBUG] x = 1"),
+            (Level::Warn, "[WARN] low balance"),
+            (Level::Error, "[ERROR] transfer failed"),
+        ];
+        let expected: Vec<_> = expected
+            .into_iter()
+            .filter_map(|(level, text)| enabled(level).then_some(text))
+            .collect();
+        assert_eq!(crate::test_host::console(), expected);
+    }
+}
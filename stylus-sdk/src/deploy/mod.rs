@@ -6,6 +6,17 @@
 //! Currently this module only supports low-level contract creation via [`RawDeploy`],
 //! but work is being done to introduce high-level deployment patterns.
 
+use alloy_primitives::{Address, B256};
+
 pub use raw::RawDeploy;
 
 mod raw;
+
+/// Computes the address that [`CREATE2`] deploys to, given the deploying contract, the salt, and
+/// the init code, including any constructor arguments. This lets a factory know where a child
+/// will be before deploying it.
+///
+/// [`CREATE2`]: https://www.evm.codes/#f5
+pub fn create2_address(deployer: Address, salt: B256, init_code: &[u8]) -> Address {
+    deployer.create2_from_code(salt, init_code)
+}
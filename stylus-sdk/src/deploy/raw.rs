@@ -103,11 +103,129 @@ impl RawDeploy {
                 &mut revert_data_len as *mut _,
             );
         }
-        RETURN_DATA_LEN.set(revert_data_len);
+        (*core::ptr::addr_of_mut!(RETURN_DATA_LEN)).set(revert_data_len);
 
         if contract.is_zero() {
             return Err(read_return_data(0, None));
         }
         Ok(contract)
     }
+
+    /// Deploys a contract whose constructor takes arguments, given its init `code` and the
+    /// ABI-encoded `args`. Like Solidity's `new`, the arguments are appended to the init code,
+    /// so this deploys with [`CREATE2`] if a salt is configured and with [`CREATE`] otherwise.
+    ///
+    /// The arguments should be encoded as a parameter list, without a selector.
+    ///
+    /// ```no_run
+    /// use stylus_sdk::{abi::AbiType, deploy::RawDeploy};
+    /// use stylus_sdk::alloy_primitives::U256;
+    /// use stylus_sdk::alloy_sol_types::SolType;
+    ///
+    /// # let code: &[u8] = &[];
+    /// type Args = (String, String, U256);
+    /// let args: Args = ("Token".into(), "TKN".into(), U256::from(1_000_000));
+    /// let args = <Args as AbiType>::SolType::abi_encode_params(&args);
+    /// let token = unsafe { RawDeploy::new().deploy_with_args(code, &args, U256::ZERO) };
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The init code may reenter this contract, with the same caveats as [`deploy`][Self::deploy].
+    ///
+    /// [`CREATE`]: https://www.evm.codes/#f0
+    /// [`CREATE2`]: https://www.evm.codes/#f5
+    pub unsafe fn deploy_with_args(
+        self,
+        code: &[u8],
+        args: &[u8],
+        endowment: U256,
+    ) -> Result<Address, Vec<u8>> {
+        let mut init_code = Vec::with_capacity(code.len() + args.len());
+        init_code.extend_from_slice(code);
+        init_code.extend_from_slice(args);
+        self.deploy(&init_code, endowment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abi::AbiType, deploy::create2_address, test_host};
+    use alloc::{format, string::String};
+    use alloy_sol_types::{sol, SolError, SolType};
+
+    sol! {
+        error SupplyTooLarge(uint256 supply);
+    }
+
+    const CODE: &[u8] = &[0x60, 0x80, 0x60, 0x40];
+
+    /// A factory that reports why a child's constructor reverted.
+    fn spawn(supply: U256, salt: Option<B256>) -> Result<Address, String> {
+        let args = <(U256,) as AbiType>::SolType::abi_encode_params(&(supply,));
+        let deploy = RawDeploy::new().salt_option(salt);
+        match unsafe { deploy.deploy_with_args(CODE, &args, U256::ZERO) } {
+            Ok(address) => Ok(address),
+            Err(data) => match SupplyTooLarge::abi_decode(&data, true) {
+                Ok(err) => Err(format!("supply too large: {}", err.supply)),
+                Err(_) => Err("deployment failed".into()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_deploy_with_args() {
+        let child = Address::repeat_byte(0xc1);
+        test_host::mock_deploy(Ok(child));
+        assert_eq!(spawn(U256::from(100), None), Ok(child));
+
+        let salt = B256::repeat_byte(1);
+        assert_eq!(spawn(U256::from(200), Some(salt)), Ok(child));
+
+        let deployments = test_host::deployments();
+        let mut expected = CODE.to_vec();
+        expected.extend(U256::from(100).to_be_bytes::<32>());
+        assert_eq!(deployments[0], (expected, None));
+        assert_eq!(
+            deployments[1].0[CODE.len()..],
+            U256::from(200).to_be_bytes::<32>()
+        );
+        assert_eq!(deployments[1].1, Some(salt));
+    }
+
+    #[test]
+    fn test_failed_deploy() {
+        let supply = U256::MAX;
+        test_host::mock_deploy(Err(SupplyTooLarge { supply }.abi_encode()));
+        assert_eq!(
+            spawn(supply, None),
+            Err(format!("supply too large: {supply}"))
+        );
+
+        test_host::mock_deploy(Err(vec![]));
+        assert_eq!(spawn(supply, None), Err("deployment failed".into()));
+    }
+
+    #[test]
+    fn test_create2_address() {
+        // the first example from EIP-1014
+        let address = create2_address(Address::ZERO, B256::ZERO, &[0x00]);
+        let expected = "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38";
+        assert_eq!(address, expected.parse::<Address>().unwrap());
+
+        // unmocked deployments land at the counterfactual address
+        let salt = B256::repeat_byte(2);
+        let mut init_code = CODE.to_vec();
+        init_code.extend(U256::from(300).to_be_bytes::<32>());
+        let child = test_host::expected_create2_address(Address::ZERO, salt, &init_code);
+        assert_eq!(spawn(U256::from(300), Some(salt)), Ok(child));
+        assert!(crate::contract::is_contract(child));
+
+        // but only once
+        assert_eq!(
+            spawn(U256::from(300), Some(salt)),
+            Err("deployment failed".into())
+        );
+    }
 }
@@ -0,0 +1,123 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as padded [base64], using the standard alphabet.
+///
+/// [base64]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+pub fn base64_encode(data: impl AsRef<[u8]>) -> String {
+    let data = data.as_ref();
+    let mut output = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let mut buf = [0; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([0, buf[0], buf[1], buf[2]]);
+
+        // each byte of input yields a sextet, plus one for the remainder
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => output.push(ALPHABET[(bits >> (18 - 6 * i)) as usize & 0x3f] as char),
+                false => output.push('='),
+            }
+        }
+    }
+    output
+}
+
+/// Decodes padded [base64] in the standard alphabet.
+///
+/// [base64]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+pub fn base64_decode(text: impl AsRef<[u8]>) -> Result<Vec<u8>, Base64Error> {
+    let text = text.as_ref();
+    if text.len() % 4 != 0 {
+        return Err(Base64Error::InvalidLength);
+    }
+
+    let mut output = Vec::with_capacity(text.len() / 4 * 3);
+    let chunks = text.len() / 4;
+    for (index, chunk) in text.chunks(4).enumerate() {
+        // only the final chunk may be padded, and then only at its end
+        let padding = match chunk {
+            [_, _, b'=', b'='] => 2,
+            [_, _, _, b'='] => 1,
+            _ => 0,
+        };
+        if padding > 0 && index + 1 != chunks {
+            return Err(Base64Error::InvalidPadding);
+        }
+
+        let mut bits = 0;
+        for (offset, &byte) in chunk[..4 - padding].iter().enumerate() {
+            let sextet = match byte {
+                b'A'..=b'Z' => byte - b'A',
+                b'a'..=b'z' => byte - b'a' + 26,
+                b'0'..=b'9' => byte - b'0' + 52,
+                b'+' => 62,
+                b'/' => 63,
+                _ => return Err(Base64Error::InvalidByte(4 * index + offset)),
+            };
+            bits |= (sextet as u32) << (18 - 6 * offset);
+        }
+        output.extend_from_slice(&bits.to_be_bytes()[1..4 - padding]);
+    }
+    Ok(output)
+}
+
+/// The ways [`base64_decode`] may fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base64Error {
+    /// The input's length isn't a multiple of 4.
+    InvalidLength,
+    /// The byte at the given index isn't in the base64 alphabet.
+    InvalidByte(usize),
+    /// Padding appears somewhere other than the end of the input.
+    InvalidPadding,
+}
+
+impl Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => f.write_str("invalid base64 length"),
+            Self::InvalidByte(index) => write!(f, "invalid base64 byte at index {index}"),
+            Self::InvalidPadding => f.write_str("invalid base64 padding"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc_4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (data, encoded) in vectors {
+            assert_eq!(base64_encode(data), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), data.as_bytes());
+        }
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(base64_decode(base64_encode(&bytes)).unwrap(), bytes);
+        assert_eq!(base64_encode([0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(base64_decode("Zm9"), Err(Base64Error::InvalidLength));
+        assert_eq!(base64_decode("Zm9v!mFy"), Err(Base64Error::InvalidByte(4)));
+        assert_eq!(base64_decode("Zg==Zm9v"), Err(Base64Error::InvalidPadding));
+        assert_eq!(base64_decode("Z==="), Err(Base64Error::InvalidByte(1)));
+    }
+}
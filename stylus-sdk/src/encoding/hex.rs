@@ -0,0 +1,107 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::Address;
+use core::fmt::{self, Display};
+
+/// Encodes bytes as a `0x`-prefixed, lowercase hex string.
+pub fn to_hex(data: impl AsRef<[u8]>) -> String {
+    let data = data.as_ref();
+    let mut output = String::with_capacity(2 + 2 * data.len());
+    output.push_str("0x");
+    output.push_str(&::hex::encode(data));
+    output
+}
+
+/// Decodes a hex string, with or without a `0x` prefix. Both cases are accepted.
+pub fn from_hex(text: impl AsRef<str>) -> Result<Vec<u8>, HexError> {
+    let text = text.as_ref();
+    let (prefix, digits) = match text.strip_prefix("0x") {
+        Some(digits) => (2, digits),
+        None => (0, text),
+    };
+    ::hex::decode(digits).map_err(|err| match err {
+        ::hex::FromHexError::InvalidHexCharacter { c, index } => HexError::InvalidCharacter {
+            c,
+            index: prefix + index,
+        },
+        _ => HexError::OddLength,
+    })
+}
+
+/// Formats an address as `0x`-prefixed, lowercase hex.
+pub fn address_to_hex(address: Address) -> String {
+    to_hex(address)
+}
+
+/// Formats an address as `0x`-prefixed hex with the mixed-case checksum of [EIP-55].
+///
+/// [EIP-55]: https://eips.ethereum.org/EIPS/eip-55
+pub fn address_to_checksum_hex(address: Address) -> String {
+    address.to_checksum(None)
+}
+
+/// The ways [`from_hex`] may fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HexError {
+    /// The string has an odd number of digits.
+    OddLength,
+    /// The string has a character that isn't a hex digit.
+    InvalidCharacter {
+        /// The invalid character.
+        c: char,
+        /// The character's index in the string, including any prefix.
+        index: usize,
+    },
+}
+
+impl Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OddLength => f.write_str("odd number of hex digits"),
+            Self::InvalidCharacter { c, index } => {
+                write!(f, "invalid hex character {c:?} at index {index}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex([]), "0x");
+        assert_eq!(to_hex([0x00, 0xab, 0xff]), "0x00abff");
+
+        assert_eq!(from_hex("0x00abff"), Ok(vec![0x00, 0xab, 0xff]));
+        assert_eq!(from_hex("00ABff"), Ok(vec![0x00, 0xab, 0xff]));
+        assert_eq!(from_hex("0x"), Ok(vec![]));
+        assert_eq!(from_hex("0xabc"), Err(HexError::OddLength));
+        assert_eq!(
+            from_hex("0xabzz"),
+            Err(HexError::InvalidCharacter { c: 'z', index: 4 })
+        );
+        assert_eq!(
+            from_hex("0X00"),
+            Err(HexError::InvalidCharacter { c: 'X', index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_addresses() {
+        // a test vector from EIP-55
+        let address = address!("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(
+            address_to_hex(address),
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+        );
+        assert_eq!(
+            address_to_checksum_hex(address),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Text encodings for building human-readable strings on chain.
+//!
+//! These work in `no_std` contracts. Common uses are returning fully on-chain NFT metadata as a
+//! `data:` URI, or formatting hashes and addresses in `tokenURI` strings and revert reasons.
+//!
+//! ```
+//! use stylus_sdk::encoding;
+//!
+//! let json = r#"{"name":"Stylus #1"}"#;
+//! let uri = format!("data:application/json;base64,{}", encoding::base64_encode(json));
+//! assert_eq!(uri, "data:application/json;base64,eyJuYW1lIjoiU3R5bHVzICMxIn0=");
+//!
+//! assert_eq!(encoding::to_hex([0xca, 0xfe]), "0xcafe");
+//! assert_eq!(encoding::from_hex("0xcafe"), Ok(vec![0xca, 0xfe]));
+//! ```
+
+pub use self::{
+    base64::{base64_decode, base64_encode, Base64Error},
+    hex::{address_to_checksum_hex, address_to_hex, from_hex, to_hex, HexError},
+};
+
+mod base64;
+mod hex;
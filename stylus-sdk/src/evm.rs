@@ -4,18 +4,53 @@
 //! Affordances for the Ethereum Virtual Machine.
 //!
 //! See also [`block`](crate::block), [`contract`](crate::contract), [`crypto`](crate::crypto),
-//! [`msg`](crate::msg), and [`tx`](crate::msg).
+//! [`msg`](crate::msg), and [`tx`](crate::tx).
 //!
 //! ```no_run
 //! use stylus_sdk::evm;
 //!
 //! let gas = evm::gas_left();
 //! ```
+//!
+//! # Ink and gas
+//!
+//! Stylus meters WASM execution in ink, a finer-grained unit than gas. The two measure the same
+//! budget: [`ink_left`] is [`gas_left`] multiplied by the transaction's [`ink_price`], which is
+//! in ink per gas. Use [`gas_to_ink`] and [`ink_to_gas`] to convert between them.
+//!
+//! ```no_run
+//! use stylus_sdk::{evm, tx};
+//!
+//! // reserve 10k gas, measured precisely in ink
+//! let reserve = tx::gas_to_ink(10_000);
+//! let spare_ink = evm::ink_left().saturating_sub(reserve);
+//! let spare_gas = tx::ink_to_gas(spare_ink);
+//! ```
+//!
+//! [`ink_price`]: crate::tx::ink_price
+//! [`gas_to_ink`]: crate::tx::gas_to_ink
+//! [`ink_to_gas`]: crate::tx::ink_to_gas
 
 use crate::hostio::{self, wrap_hostio};
 use alloc::{vec, vec::Vec};
 use alloy_primitives::B256;
 use alloy_sol_types::{abi::token::WordToken, SolEvent, TopicList};
+use core::fmt::{self, Display};
+
+/// The ways [`raw_log`] may fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogError {
+    /// EVM logs have at most 4 topics, but this many were given.
+    TooManyTopics(usize),
+}
+
+impl Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyTopics(count) => write!(f, "too many topics: {count} > 4"),
+        }
+    }
+}
 
 /// Emits an evm log from combined topics and data.
 fn emit_log(bytes: &[u8], num_topics: usize) {
@@ -24,9 +59,20 @@ fn emit_log(bytes: &[u8], num_topics: usize) {
 
 /// Emits an EVM log from its raw topics and data.
 /// Most users should prefer the alloy-typed [`log`].
-pub fn raw_log(topics: &[B256], data: &[u8]) -> Result<(), &'static str> {
+///
+/// Topics are taken as given, so for events declared in Solidity the first should be the event's
+/// signature hash, followed by its indexed arguments. Fails without logging if there are more than 4.
+///
+/// ```no_run
+/// use stylus_sdk::{alloy_primitives::U256, crypto, evm};
+///
+/// // emits `Ping(uint256 value)` with a value of 1
+/// let signature = crypto::keccak("Ping(uint256)");
+/// evm::raw_log(&[signature], &U256::from(1).to_be_bytes::<32>()).unwrap();
+/// ```
+pub fn raw_log(topics: &[B256], data: &[u8]) -> Result<(), LogError> {
     if topics.len() > 4 {
-        return Err("too many topics");
+        return Err(LogError::TooManyTopics(topics.len()));
     }
     let mut bytes: Vec<u8> = vec![];
     bytes.extend(topics.iter().flat_map(|x| x.0.iter()));
@@ -37,18 +83,34 @@ pub fn raw_log(topics: &[B256], data: &[u8]) -> Result<(), &'static str> {
 
 /// Emits a typed alloy log.
 pub fn log<T: SolEvent>(event: T) {
+    let mut bytes = Vec::with_capacity(32 * T::TopicList::COUNT);
+    encode_log(&event, &mut bytes);
+    emit_log(&bytes, T::TopicList::COUNT);
+}
+
+/// Emits a typed alloy log for each event, in order.
+///
+/// This is equivalent to calling [`log`] in a loop, but reuses one buffer to encode every event.
+pub fn log_batch<T: SolEvent>(events: impl IntoIterator<Item = T>) {
+    let mut bytes = Vec::new();
+    for event in events {
+        bytes.clear();
+        encode_log(&event, &mut bytes);
+        emit_log(&bytes, T::TopicList::COUNT);
+    }
+}
+
+/// Encodes an event's topics followed by its data.
+fn encode_log<T: SolEvent>(event: &T, bytes: &mut Vec<u8>) {
     // According to the alloy docs, encode_topics_raw fails only if the array is too small
 
     let mut topics = [WordToken::default(); 4];
     event.encode_topics_raw(&mut topics).unwrap();
 
-    let count = T::TopicList::COUNT;
-    let mut bytes = Vec::with_capacity(32 * count);
-    for topic in &topics[..count] {
+    for topic in &topics[..T::TopicList::COUNT] {
         bytes.extend_from_slice(topic.as_slice());
     }
-    event.encode_data_to(&mut bytes);
-    emit_log(&bytes, count);
+    event.encode_data_to(bytes);
 }
 
 /// This function exists to force the compiler to import this symbol.
@@ -60,6 +122,8 @@ pub fn pay_for_memory_grow(pages: u16) {
 wrap_hostio!(
     /// Gets the amount of gas remaining. See [`Ink and Gas`] for more information on Stylus's compute pricing.
     ///
+    /// This is the [`ink_left`] converted to gas, rounding down.
+    ///
     /// [`Ink and Gas`]: https://docs.arbitrum.io/stylus/concepts/stylus-gas
     gas_left evm_gas_left u64
 );
@@ -67,6 +131,86 @@ wrap_hostio!(
 wrap_hostio!(
     /// Gets the amount of ink remaining. See [`Ink and Gas`] for more information on Stylus's compute pricing.
     ///
+    /// Ink is gas scaled by the [`ink_price`](crate::tx::ink_price), so this is more precise than
+    /// [`gas_left`].
+    ///
     /// [`Ink and Gas`]: https://docs.arbitrum.io/stylus/concepts/stylus-gas
     ink_left evm_ink_left u64
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abi, crypto::keccak, test_host};
+    use alloc::{string::String, vec};
+    use alloy_primitives::{Address, U256};
+    use alloy_sol_types::sol;
+
+    sol! {
+        event Ping(address indexed from, uint256 value);
+        event Named(string indexed name, bytes indexed tag, string label);
+    }
+
+    #[test]
+    fn test_raw_log() {
+        let topics = [B256::repeat_byte(1), B256::repeat_byte(2)];
+        let count = test_host::logs().len();
+        assert_eq!(raw_log(&topics, b"raw"), Ok(()));
+        assert_eq!(
+            raw_log(&[B256::ZERO; 5], &[]),
+            Err(LogError::TooManyTopics(5))
+        );
+
+        // typed events are recorded alongside raw ones
+        let from = Address::repeat_byte(3);
+        log(Ping {
+            from,
+            value: U256::from(4),
+        });
+
+        let logs = test_host::logs();
+        assert_eq!(logs.len(), count + 2);
+        assert_eq!(logs[count], (topics.to_vec(), b"raw".to_vec()));
+        let event =
+            Ping::decode_raw_log(logs[count + 1].0.iter().copied(), &logs[count + 1].1, true);
+        assert_eq!(event.unwrap().from, from);
+    }
+
+    #[test]
+    fn test_indexed_dynamic_fields() {
+        let name = abi::value_to_topic(&String::from("alice"));
+        let tag = abi::value_to_topic(&abi::Bytes(vec![1, 2]));
+        log(Named {
+            name,
+            tag,
+            label: "alice".into(),
+        });
+
+        // indexed strings and bytes are hashed, while the rest of the event is encoded in full
+        let (topics, data) = test_host::logs().pop().unwrap();
+        assert_eq!(topics[1..], [keccak("alice"), keccak([1, 2])]);
+        assert!(data.windows(5).any(|window| window == b"alice"));
+
+        // decoding recovers the hashes rather than the values
+        let named = test_host::events::<Named>().pop().unwrap();
+        assert_eq!((named.name, named.tag), (keccak("alice"), keccak([1, 2])));
+        assert_eq!(named.label, "alice");
+    }
+
+    #[test]
+    fn test_log_batch() {
+        let count = test_host::logs().len();
+        let pings = (1..=3).map(|i| Ping {
+            from: Address::repeat_byte(i),
+            value: U256::from(i),
+        });
+        log_batch(pings.clone());
+
+        let logs = test_host::logs();
+        assert_eq!(logs.len(), count + 3);
+        for (ping, (topics, data)) in pings.zip(&logs[count..]) {
+            let event = Ping::decode_raw_log(topics.iter().copied(), data, true).unwrap();
+            assert_eq!((event.from, event.value), (ping.from, ping.value));
+        }
+    }
+}
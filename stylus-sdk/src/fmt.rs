@@ -0,0 +1,148 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Formatting and parsing of fixed-point token amounts, without floating point.
+//!
+//! Token balances are integers scaled by a number of decimals. These helpers convert between
+//! such integers and their human-readable decimal strings, mirroring ethers' `formatUnits` and
+//! `parseUnits`.
+//!
+//! ```
+//! use stylus_sdk::{alloy_primitives::U256, fmt};
+//!
+//! let amount = U256::from(1_500_000);
+//! assert_eq!(fmt::format_units(amount, 6), "1.5");
+//! assert_eq!(fmt::parse_units("1.5", 6), Ok(amount));
+//! ```
+
+use alloc::string::{String, ToString};
+use alloy_primitives::U256;
+use core::fmt::{self, Display};
+
+/// Formats `value`, scaled by `10^decimals`, as a decimal string.
+///
+/// Trailing zeros in the fraction are dropped, though like ethers, at least one fractional digit
+/// is kept: 1 whole token formats as `"1.0"`.
+pub fn format_units(value: U256, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = value.to_string();
+
+    // pad so that there's at least one integer digit
+    let digits = match digits.len() <= decimals {
+        true => "0".repeat(decimals + 1 - digits.len()) + &digits,
+        false => digits,
+    };
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = match fraction.trim_end_matches('0') {
+        "" => "0",
+        fraction => fraction,
+    };
+    let mut output = String::with_capacity(integer.len() + 1 + fraction.len());
+    output.push_str(integer);
+    output.push('.');
+    output.push_str(fraction);
+    output
+}
+
+/// Parses a decimal string like `"1.5"` into an integer scaled by `10^decimals`.
+///
+/// Either the integer or the fractional part may be omitted, as in `"1"` or `".5"`, but not
+/// both. The fraction may have more than `decimals` digits only if the extra ones are zeros.
+pub fn parse_units(text: &str, decimals: u8) -> Result<U256, ParseError> {
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    let decimals = decimals as usize;
+    if fraction.len() > decimals {
+        return Err(ParseError::TooManyDecimals);
+    }
+
+    let padding = core::iter::repeat(b'0').take(decimals - fraction.len());
+    let digits = integer.bytes().chain(fraction.bytes()).chain(padding);
+
+    let mut value = U256::ZERO;
+    for digit in digits {
+        if !digit.is_ascii_digit() {
+            return Err(ParseError::InvalidDigit);
+        }
+        value = value
+            .checked_mul(U256::from(10))
+            .and_then(|value| value.checked_add(U256::from(digit - b'0')))
+            .ok_or(ParseError::Overflow)?;
+    }
+    Ok(value)
+}
+
+/// The ways [`parse_units`] may fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string has no digits.
+    Empty,
+    /// The string has a character other than the digits and one decimal point.
+    InvalidDigit,
+    /// The fraction has more significant digits than the number of decimals.
+    TooManyDecimals,
+    /// The scaled value doesn't fit in a `U256`.
+    Overflow,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Empty => "no digits",
+            Self::InvalidDigit => "invalid digit",
+            Self::TooManyDecimals => "too many decimals",
+            Self::Overflow => "value too large",
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_units() {
+        let format = |value: u64, decimals| format_units(U256::from(value), decimals);
+        assert_eq!(format(1_500_000, 6), "1.5");
+        assert_eq!(format(1_000_000, 6), "1.0");
+        assert_eq!(format(1_234_567, 6), "1.234567");
+        assert_eq!(format(5, 6), "0.000005");
+        assert_eq!(format(0, 18), "0.0");
+        assert_eq!(format(120, 0), "120.0");
+        assert_eq!(
+            format_units(U256::MAX, 18),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+        assert_eq!(
+            format_units(U256::from(1), 80),
+            "0.".to_string() + &"0".repeat(79) + "1"
+        );
+    }
+
+    #[test]
+    fn test_parse_units() {
+        let parse = |text| parse_units(text, 6);
+        assert_eq!(parse("1.5"), Ok(U256::from(1_500_000)));
+        assert_eq!(parse("1"), Ok(U256::from(1_000_000)));
+        assert_eq!(parse("1."), Ok(U256::from(1_000_000)));
+        assert_eq!(parse(".5"), Ok(U256::from(500_000)));
+        assert_eq!(parse("0.000005"), Ok(U256::from(5)));
+        assert_eq!(parse("1.5000000000"), Ok(U256::from(1_500_000)));
+
+        assert_eq!(parse(""), Err(ParseError::Empty));
+        assert_eq!(parse("."), Err(ParseError::Empty));
+        assert_eq!(parse("1.0000005"), Err(ParseError::TooManyDecimals));
+        assert_eq!(parse("-1"), Err(ParseError::InvalidDigit));
+        assert_eq!(parse("1.2.3"), Err(ParseError::InvalidDigit));
+        assert_eq!(parse("1e6"), Err(ParseError::InvalidDigit));
+        assert_eq!(parse_units("1", 78), Err(ParseError::Overflow));
+
+        let max = format_units(U256::MAX, 18);
+        assert_eq!(parse_units(&max, 18), Ok(U256::MAX));
+    }
+}
@@ -31,7 +31,22 @@ macro_rules! vm_hooks {
         $($(#[$meta:meta])* $vis:vis fn $func:ident ($($arg:ident : $arg_type:ty),* ) $(-> $return_type:ty)?);*
     ) => {
         cfg_if! {
-            if #[cfg(feature = "export-abi")] {
+            if #[cfg(any(test, feature = "stylus-test"))] {
+                // Forward each function to the in-memory test host.
+                $(#[$block_meta])*
+                mod $stub {
+                    $(
+                        $(#[$meta])*
+                        #[allow(clippy::missing_safety_doc)]
+                        $vis unsafe fn $func($($arg : $arg_type),*) $(-> $return_type)? {
+                            crate::test_host::step();
+                            crate::test_host::hooks::$func($($arg),*)
+                        }
+                    )*
+                }
+                #[allow(unused_imports)]
+                pub use $stub::*;
+            } else if #[cfg(feature = "export-abi")] {
                 // Generate a stub for each function.
                 // We use a module for the block macros & docstrings.
                 $(#[$block_meta])*
@@ -118,6 +133,19 @@ vm_hooks! {
     /// [`SSTORE`]: https://www.evm.codes/#55
     pub fn storage_flush_cache(clear: bool);
 
+    /// Reads a 32-byte value from transient storage, which will be `0` when not previously set
+    /// during the current transaction. The semantics are equivalent to that of the EVM's [`TLOAD`]
+    /// opcode. Unlike permanent storage, transient storage isn't cached.
+    ///
+    /// [`TLOAD`]: https://www.evm.codes/#5c
+    pub fn transient_load_bytes32(key: *const u8, dest: *mut u8);
+
+    /// Writes a 32-byte value to transient storage, which is discarded at the end of the
+    /// transaction. The semantics are equivalent to that of the EVM's [`TSTORE`] opcode.
+    ///
+    /// [`TSTORE`]: https://www.evm.codes/#5d
+    pub fn transient_store_bytes32(key: *const u8, value: *const u8);
+
     /// Gets the basefee of the current block. The semantics are equivalent to that of the EVM's
     /// [`BASEFEE`] opcode.
     ///
@@ -440,14 +468,23 @@ macro_rules! wrap_hostio {
     (@simple $(#[$meta:meta])* $name:ident, $cache:ident, $hostio:ident, $ty:ident) => {
         $(#[$meta])*
         pub fn $name() -> $ty {
-            unsafe{ $cache.get() }
+            // unit tests run in parallel, each with its own test host, so they can't share the cache
+            if cfg!(any(test, feature = "stylus-test")) {
+                return unsafe { hostio::$hostio() };
+            }
+            unsafe { (*core::ptr::addr_of_mut!($cache)).get() }
         }
         pub(crate) static mut $cache: hostio::CachedOption<$ty> = hostio::CachedOption::new(|| unsafe { hostio::$hostio() });
     };
     (@convert $(#[$meta:meta])* $name:ident, $cache:ident, $hostio:ident, $from:ident, $ty:ident) => {
         $(#[$meta])*
         pub fn $name() -> $ty {
-            unsafe{ $cache.get() }
+            if cfg!(any(test, feature = "stylus-test")) {
+                let mut data = $from::ZERO;
+                unsafe { hostio::$hostio(data.as_mut_ptr()) };
+                return data.into();
+            }
+            unsafe { (*core::ptr::addr_of_mut!($cache)).get() }
         }
         pub(crate) static mut $cache: hostio::CachedOption<$ty> = hostio::CachedOption::new(|| {
             let mut data = $from::ZERO;
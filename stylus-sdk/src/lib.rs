@@ -10,7 +10,7 @@
 //!
 //! Some of the features available in the SDK include:
 //! - **Generic**, storage-backed Rust types for programming **Solidity-equivalent** smart contracts with optimal
-//! storage caching.
+//!   storage caching.
 //! - Simple macros for writing **language-agnostic** methods and entrypoints.
 //! - Automatic export of Solidity interfaces for interoperability across programming languages.
 //! - Powerful **primitive types** backed by the feature-rich [Alloy][alloy].
@@ -26,7 +26,10 @@
 #![doc(html_logo_url = "https://arbitrum.io/assets/stylus/Arbitrum_Stylus-Logomark.png")]
 #![warn(missing_docs)]
 // Only allow the standard library in tests and for exports
-#![cfg_attr(not(any(test, feature = "export-abi")), no_std)]
+#![cfg_attr(
+    not(any(test, feature = "export-abi", feature = "stylus-test")),
+    no_std
+)]
 
 /// Use an efficient WASM allocator.
 ///
@@ -37,6 +40,9 @@ static ALLOC: mini_alloc::MiniAlloc = mini_alloc::MiniAlloc::INIT;
 
 extern crate alloc;
 
+// Lets the SDK's own macros, which refer to `stylus_sdk`, be used within this crate.
+extern crate self as stylus_sdk;
+
 pub use alloy_primitives;
 pub use alloy_sol_types;
 pub use hex;
@@ -49,21 +55,32 @@ pub mod abi;
 #[macro_use]
 pub mod debug;
 
+pub mod access;
+pub mod arb;
 pub mod block;
 pub mod call;
 pub mod contract;
 pub mod crypto;
 pub mod deploy;
+pub mod encoding;
 pub mod evm;
+pub mod fmt;
+pub mod math;
 pub mod methods;
 pub mod msg;
 pub mod prelude;
+pub mod random;
 pub mod storage;
+pub mod tokens;
 pub mod tx;
 pub mod types;
+pub mod utils;
 
 mod util;
 
+#[cfg(any(test, feature = "stylus-test"))]
+pub mod test_host;
+
 #[cfg(feature = "hostio")]
 pub mod hostio;
 
@@ -0,0 +1,226 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Integer math helpers for [`U256`], like those of OpenZeppelin's `Math` library.
+//!
+//! Contracts such as bonding curves and AMMs need roots, powers, and logarithms of token amounts.
+//! These functions compute them exactly, rounding down unless asked otherwise, and report overflow
+//! instead of wrapping.
+//!
+//! ```
+//! use stylus_sdk::{alloy_primitives::U256, math::{self, Rounding}};
+//!
+//! assert_eq!(math::sqrt(U256::from(99)), U256::from(9));
+//! assert_eq!(
+//!     math::mul_div(U256::MAX, U256::from(3), U256::from(4), Rounding::Floor),
+//!     Ok(U256::MAX / U256::from(4) * U256::from(3) + U256::from(2)),
+//! );
+//! assert_eq!(math::pow(U256::from(10), 18), Ok(U256::from(10).pow(U256::from(18))));
+//! assert_eq!(math::log2_floor(U256::from(1024)), 10);
+//! ```
+
+use alloc::vec::Vec;
+use alloy_primitives::{ruint::UintTryFrom, U256, U512};
+use alloy_sol_types::{Panic, PanicKind, SolError};
+use core::fmt::{self, Display};
+
+/// The ways the functions of this module may fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    /// The result doesn't fit in a `U256`.
+    Overflow,
+    /// The denominator is zero.
+    DivisionByZero,
+}
+
+impl Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("overflow"),
+            Self::DivisionByZero => f.write_str("division by zero"),
+        }
+    }
+}
+
+/// Encodes the error as the `Panic` Solidity raises for the same failure.
+impl From<MathError> for Vec<u8> {
+    fn from(err: MathError) -> Self {
+        let kind = match err {
+            MathError::Overflow => PanicKind::UnderOverflow,
+            MathError::DivisionByZero => PanicKind::DivisionByZero,
+        };
+        Panic::from(kind).abi_encode()
+    }
+}
+
+/// Which way to round results that aren't exact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round toward zero.
+    Floor,
+    /// Round away from zero.
+    Ceil,
+}
+
+/// Computes `a * b / denominator` with a 512-bit intermediate product, like OpenZeppelin's
+/// `Math.mulDiv`.
+///
+/// The multiplication never overflows, so this only fails if the denominator is zero or the
+/// rounded quotient doesn't fit in a `U256`.
+pub fn mul_div(a: U256, b: U256, denominator: U256, rounding: Rounding) -> Result<U256, MathError> {
+    if denominator.is_zero() {
+        return Err(MathError::DivisionByZero);
+    }
+    let product: U512 = a.widening_mul(b);
+    let (quotient, remainder) = product.div_rem(U512::from(denominator));
+    let quotient = match rounding {
+        Rounding::Ceil if !remainder.is_zero() => quotient + U512::from(1),
+        _ => quotient,
+    };
+    U256::uint_try_from(quotient).map_err(|_| MathError::Overflow)
+}
+
+/// Computes the square root of `value`, rounded down, using the Babylonian method.
+pub fn sqrt(value: U256) -> U256 {
+    if value < U256::from(2) {
+        return value;
+    }
+
+    // start above the root, from which each step only decreases
+    let mut root = U256::from(1) << (log2_floor(value) / 2 + 1);
+    loop {
+        let next = (root + value / root) >> 1;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// Raises `base` to the power of `exp`, failing if the result overflows.
+///
+/// This uses exponentiation by squaring, taking a number of steps logarithmic in `exp`.
+pub fn pow(base: U256, exp: u32) -> Result<U256, MathError> {
+    base.checked_pow(U256::from(exp)).ok_or(MathError::Overflow)
+}
+
+/// Computes the base-2 logarithm of `value`, rounded down.
+///
+/// Like OpenZeppelin's `Math.log2`, this returns 0 when `value` is 0.
+pub fn log2_floor(value: U256) -> u32 {
+    match value.bit_len() {
+        0 => 0,
+        bits => bits as u32 - 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt() {
+        let sqrt = |value: u64| sqrt(U256::from(value));
+        assert_eq!(sqrt(0), U256::ZERO);
+        assert_eq!(sqrt(1), U256::from(1));
+        assert_eq!(sqrt(2), U256::from(1));
+        assert_eq!(sqrt(3), U256::from(1));
+        assert_eq!(sqrt(4), U256::from(2));
+        assert_eq!(sqrt(99), U256::from(9));
+        assert_eq!(sqrt(100), U256::from(10));
+        assert_eq!(sqrt(u64::MAX), U256::from(u32::MAX));
+
+        for value in 0..10_000u64 {
+            let root = sqrt(value);
+            assert!(root * root <= U256::from(value));
+            assert!((root + U256::from(1)) * (root + U256::from(1)) > U256::from(value));
+        }
+
+        let max_root = U256::from(u128::MAX);
+        assert_eq!(super::sqrt(U256::MAX), max_root);
+        assert_eq!(super::sqrt(max_root * max_root), max_root);
+        assert_eq!(
+            super::sqrt(max_root * max_root - U256::from(1)),
+            max_root - U256::from(1)
+        );
+    }
+
+    #[test]
+    fn test_pow() {
+        let two = U256::from(2);
+        assert_eq!(pow(U256::ZERO, 0), Ok(U256::from(1)));
+        assert_eq!(pow(U256::ZERO, 5), Ok(U256::ZERO));
+        assert_eq!(pow(U256::from(1), u32::MAX), Ok(U256::from(1)));
+        assert_eq!(pow(two, 255), Ok(U256::from(1) << 255));
+        assert_eq!(pow(two, 256), Err(MathError::Overflow));
+        assert_eq!(
+            pow(U256::from(10), 77),
+            Ok(U256::from(10).pow(U256::from(77)))
+        );
+        assert_eq!(pow(U256::from(10), 78), Err(MathError::Overflow));
+        assert_eq!(pow(U256::MAX, 1), Ok(U256::MAX));
+        assert_eq!(pow(U256::MAX, 2), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let max = U256::MAX;
+        let mul_div = |a: u64, b: u64, denominator: u64, rounding| {
+            mul_div(
+                U256::from(a),
+                U256::from(b),
+                U256::from(denominator),
+                rounding,
+            )
+        };
+        assert_eq!(mul_div(7, 3, 2, Rounding::Floor), Ok(U256::from(10)));
+        assert_eq!(mul_div(7, 3, 2, Rounding::Ceil), Ok(U256::from(11)));
+        assert_eq!(mul_div(8, 3, 2, Rounding::Ceil), Ok(U256::from(12)));
+        assert_eq!(mul_div(0, 3, 2, Rounding::Ceil), Ok(U256::ZERO));
+        assert_eq!(
+            mul_div(1, 1, 0, Rounding::Floor),
+            Err(MathError::DivisionByZero)
+        );
+
+        let mul_div = super::mul_div;
+        assert_eq!(mul_div(max, max, max, Rounding::Floor), Ok(max));
+        assert_eq!(mul_div(max, max, max, Rounding::Ceil), Ok(max));
+        assert_eq!(
+            mul_div(max, U256::from(2), U256::from(4), Rounding::Ceil),
+            Ok(max / U256::from(2) + U256::from(1))
+        );
+        assert_eq!(
+            mul_div(max, max, max - U256::from(1), Rounding::Floor),
+            Err(MathError::Overflow)
+        );
+        assert_eq!(
+            mul_div(max, U256::from(1), U256::from(1), Rounding::Ceil),
+            Ok(max)
+        );
+        assert_eq!(
+            mul_div(max, U256::from(3), U256::from(2), Rounding::Floor),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_error_encoding() {
+        let overflow: Vec<u8> = MathError::Overflow.into();
+        assert_eq!(overflow, Panic::from(PanicKind::UnderOverflow).abi_encode());
+        let division: Vec<u8> = MathError::DivisionByZero.into();
+        assert_eq!(&division[..4], Panic::SELECTOR);
+        assert_eq!(division[35], 0x12);
+    }
+
+    #[test]
+    fn test_log2_floor() {
+        assert_eq!(log2_floor(U256::ZERO), 0);
+        assert_eq!(log2_floor(U256::from(1)), 0);
+        assert_eq!(log2_floor(U256::from(2)), 1);
+        assert_eq!(log2_floor(U256::from(3)), 1);
+        assert_eq!(log2_floor(U256::from(1024)), 10);
+        assert_eq!(log2_floor(U256::from(1025)), 10);
+        assert_eq!(log2_floor(U256::from(1) << 255), 255);
+        assert_eq!(log2_floor(U256::MAX), 255);
+    }
+}
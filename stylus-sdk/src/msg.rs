@@ -13,7 +13,7 @@
 //! ```
 
 use crate::hostio::{self, wrap_hostio};
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{Address, FixedBytes, B256, U256};
 
 wrap_hostio!(
     /// Whether the current call is reentrant.
@@ -38,3 +38,26 @@ wrap_hostio!(
     /// Get the ETH value in wei sent to the program.
     value VALUE msg_value U256
 );
+
+/// Gets the first 4 bytes of the calldata, which for calls routed to a
+/// [`#[public]`](macro@stylus_proc::public) method is its selector. This is Solidity's `msg.sig`,
+/// and is useful for guards and handlers shared between methods.
+///
+/// Like Solidity, calldata shorter than 4 bytes is padded with zeros. Methods reached through
+/// [`contract::call_self`](crate::contract::call_self) run as part of the outer call, so still see
+/// the outer call's selector rather than the one they were routed by.
+pub fn sig() -> FixedBytes<4> {
+    // unit tests run in parallel, each with its own test host, so they can't share the cache
+    #[cfg(any(test, feature = "stylus-test"))]
+    return crate::test_host::sig();
+
+    #[cfg(not(any(test, feature = "stylus-test")))]
+    unsafe {
+        (*core::ptr::addr_of_mut!(SIG)).get()
+    }
+}
+
+/// Set by [`contract::args`](crate::contract::args) when the calldata is read.
+#[cfg(not(any(test, feature = "stylus-test")))]
+pub(crate) static mut SIG: hostio::CachedOption<FixedBytes<4>> =
+    hostio::CachedOption::new(|| FixedBytes::ZERO);
@@ -9,6 +9,6 @@
 //! use stylus_sdk::prelude::*;
 //! ```
 
-pub use crate::storage::{Erase, SimpleStorageType, StorageType, TopLevelStorage};
+pub use crate::storage::{Erase, SimpleStorageType, StorageReset, StorageType, TopLevelStorage};
 pub use crate::stylus_proc::*;
 pub use crate::types::AddressVM;
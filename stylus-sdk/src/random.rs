@@ -0,0 +1,212 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Randomness from a commit-reveal scheme.
+//!
+//! Arbitrum chains have no randomness precompile or beacon, and values like the block timestamp
+//! are chosen by the sequencer, so neither they nor hashes of them are safe sources of randomness.
+//! [`CommitReveal`] instead derives a seed from secrets that participants commit to in advance.
+//!
+//! Each participant first commits to the hash of a secret, as computed by [`commitment`], and
+//! later reveals the secret, which is checked against that hash and mixed into the seed.
+//!
+//! ```ignore
+//! #[storage]
+//! #[entrypoint]
+//! struct Lottery {
+//!     #[borrow]
+//!     randomness: CommitReveal,
+//!     ...
+//! }
+//!
+//! #[public]
+//! #[inherit(CommitReveal)]
+//! impl Lottery {
+//!     pub fn draw(&mut self) -> Result<Address, Vec<u8>> {
+//!         let seed = U256::from_be_bytes(self.randomness.seed().0);
+//!         ...
+//!     }
+//! }
+//! ```
+//!
+//! # Bias
+//!
+//! A seed is only unpredictable if at least one of the secrets mixed into it is, and it can still
+//! be biased. Whoever reveals last learns the seed before anyone else and may withhold their
+//! secret if they dislike the result. Contracts should stop accepting commitments before reveals
+//! begin, and penalize participants who commit but don't reveal, such as by forfeiting a deposit.
+
+use crate::{
+    crypto, evm, msg,
+    storage::{StorageB256, StorageMap},
+    stylus_proc::{public, storage, SolidityError},
+};
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::sol;
+
+sol! {
+    /// Emitted when an account commits to a secret.
+    event Committed(address indexed account, bytes32 commitment);
+
+    /// Emitted when an account reveals its secret, mixing it into the seed.
+    event Revealed(address indexed account, bytes32 secret);
+
+    /// The account has a commitment it hasn't yet revealed.
+    #[allow(missing_docs)]
+    error CommitRevealPendingCommitment(address account);
+
+    /// The account has no commitment to reveal, possibly because it was already revealed.
+    #[allow(missing_docs)]
+    error CommitRevealNoCommitment(address account);
+
+    /// The revealed secret doesn't match the account's commitment.
+    #[allow(missing_docs)]
+    error CommitRevealMismatch(address account, bytes32 secret);
+}
+
+/// Represents the ways [`CommitReveal`] methods may fail.
+#[derive(SolidityError)]
+pub enum CommitRevealError {
+    /// The account must reveal its commitment before committing again.
+    PendingCommitment(CommitRevealPendingCommitment),
+    /// The account has nothing to reveal.
+    NoCommitment(CommitRevealNoCommitment),
+    /// The secret doesn't match the commitment.
+    Mismatch(CommitRevealMismatch),
+}
+
+/// Computes the commitment `account` makes to `secret`, which is `keccak256(account . secret)`.
+///
+/// Binding the commitment to the account prevents others from copying it.
+pub fn commitment(account: Address, secret: B256) -> B256 {
+    let mut data = [0; 52];
+    data[..20].copy_from_slice(account.as_slice());
+    data[20..].copy_from_slice(secret.as_slice());
+    crypto::keccak(data)
+}
+
+/// Accumulates a random seed from committed secrets.
+#[storage]
+pub struct CommitReveal {
+    commitments: StorageMap<Address, StorageB256>,
+    seed: StorageB256,
+}
+
+impl CommitReveal {
+    /// Records `account`'s commitment without checking the caller, emitting [`Committed`].
+    ///
+    /// The commitment should be nonzero, since zero is stored to mean there is none.
+    pub fn _commit(&mut self, account: Address, commitment: B256) -> Result<(), CommitRevealError> {
+        let mut pending = self.commitments.setter(account);
+        if !pending.get().is_zero() {
+            return Err(CommitRevealPendingCommitment { account }.into());
+        }
+        pending.set(commitment);
+        evm::log(Committed {
+            account,
+            commitment,
+        });
+        Ok(())
+    }
+
+    /// Checks `secret` against `account`'s commitment, then mixes it into the seed, emitting
+    /// [`Revealed`]. Returns the new seed.
+    ///
+    /// The commitment is cleared, so each secret can only be revealed once.
+    pub fn _reveal(&mut self, account: Address, secret: B256) -> Result<B256, CommitRevealError> {
+        let mut pending = self.commitments.setter(account);
+        let expected = pending.get();
+        if expected.is_zero() {
+            return Err(CommitRevealNoCommitment { account }.into());
+        }
+        if commitment(account, secret) != expected {
+            return Err(CommitRevealMismatch { account, secret }.into());
+        }
+        pending.set(B256::ZERO);
+
+        let seed = crypto::keccak(self.seed.get().concat_const::<32, 64>(secret));
+        self.seed.set(seed);
+        evm::log(Revealed { account, secret });
+        Ok(seed)
+    }
+}
+
+#[public]
+impl CommitReveal {
+    /// Commits the caller to the secret whose [`commitment`] is given.
+    pub fn commit(&mut self, commitment: B256) -> Result<(), CommitRevealError> {
+        self._commit(msg::sender(), commitment)
+    }
+
+    /// Reveals the caller's secret, returning the new seed.
+    pub fn reveal(&mut self, secret: B256) -> Result<B256, CommitRevealError> {
+        self._reveal(msg::sender(), secret)
+    }
+
+    /// The commitment `account` has yet to reveal, or zero if there is none.
+    pub fn commitment_of(&self, account: Address) -> B256 {
+        self.commitments.get(account)
+    }
+
+    /// The seed derived from every secret revealed so far.
+    pub fn seed(&self) -> B256 {
+        self.seed.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageType, test_host};
+    use alloy_primitives::{address, U256};
+
+    const ALICE: Address = address!("A11CEA11CEA11CEA11CEA11CEA11CEA11CEA11CE");
+    const BOB: Address = address!("B0BB0BB0BB0BB0BB0BB0BB0BB0BB0BB0BB0BB0BB");
+
+    #[test]
+    fn test_commit_reveal() {
+        let mut randomness = unsafe { CommitReveal::new(U256::from(0x6161), 0) };
+        let secret = B256::repeat_byte(0x11);
+
+        test_host::set_sender(ALICE);
+        assert!(randomness.commit(commitment(ALICE, secret)).is_ok());
+        assert!(matches!(
+            randomness.commit(commitment(ALICE, secret)),
+            Err(CommitRevealError::PendingCommitment(_))
+        ));
+
+        // bob can't reveal alice's secret, nor can he copy her commitment
+        test_host::set_sender(BOB);
+        assert!(matches!(
+            randomness.reveal(secret),
+            Err(CommitRevealError::NoCommitment(_))
+        ));
+        assert!(randomness.commit(commitment(ALICE, secret)).is_ok());
+        assert!(matches!(
+            randomness.reveal(secret),
+            Err(CommitRevealError::Mismatch(_))
+        ));
+
+        test_host::set_sender(ALICE);
+        assert!(matches!(
+            randomness.reveal(B256::repeat_byte(0x22)),
+            Err(CommitRevealError::Mismatch(_))
+        ));
+        let Ok(seed) = randomness.reveal(secret) else {
+            panic!("failed to reveal");
+        };
+        assert_eq!(seed, crypto::keccak([[0; 32], [0x11; 32]].concat()));
+        assert_eq!(randomness.seed(), seed);
+        assert_eq!(randomness.commitment_of(ALICE), B256::ZERO);
+
+        // secrets can't be revealed twice
+        assert!(matches!(
+            randomness.reveal(secret),
+            Err(CommitRevealError::NoCommitment(_))
+        ));
+        assert_eq!(randomness.seed(), seed);
+
+        // but a new commitment may be made once revealed
+        assert!(randomness.commit(commitment(ALICE, secret)).is_ok());
+    }
+}
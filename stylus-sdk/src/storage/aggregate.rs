@@ -0,0 +1,337 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{
+    Erase, StorageBool, StorageGuard, StorageGuardMut, StorageKey, StorageType, StorageU256,
+    StorageVec,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U256};
+use core::marker::PhantomData;
+
+/// Accessor for a storage-backed map of [`U256`] values that tracks the
+/// [`count`](Self::count), [`sum`](Self::sum), and [`max`](Self::max) of its values.
+///
+/// The count and sum are maintained eagerly. The max is too, except when the current maximum is
+/// removed or lowered, at which point it's marked stale. Reading a stale max scans the map's
+/// values, which costs gas linear in [`count`](Self::count). Calling
+/// [`refresh_max`](Self::refresh_max) persists the result so that later reads are cheap again.
+///
+/// Values are kept densely packed alongside the map, so unlike [`StorageMap`](super::StorageMap)
+/// this type can be fully [erased](Erase).
+///
+/// Only [`U256`] values are supported, since the sum is stored with the same width. Narrower
+/// integers can be widened on insert.
+pub struct AggregateMap<K: StorageKey> {
+    values: StorageVec<StorageU256>,
+    owners: StorageVec<StorageU256>,
+    sum: StorageU256,
+    max: StorageU256,
+    max_stale: StorageBool,
+    positions: U256,
+    marker: PhantomData<K>,
+}
+
+impl<K: StorageKey> StorageType for AggregateMap<K> {
+    type Wraps<'a> = StorageGuard<'a, AggregateMap<K>> where Self: 'a;
+    type WrapsMut<'a> = StorageGuardMut<'a, AggregateMap<K>> where Self: 'a;
+
+    const REQUIRED_SLOTS: usize = 6;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(offset == 0);
+        Self {
+            values: StorageVec::new(slot, 0),
+            owners: StorageVec::new(slot + U256::from(1), 0),
+            sum: StorageU256::new(slot + U256::from(2), 0),
+            max: StorageU256::new(slot + U256::from(3), 0),
+            max_stale: StorageBool::new(slot + U256::from(4), 0),
+            positions: slot + U256::from(5),
+            marker: PhantomData,
+        }
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl<K: StorageKey> AggregateMap<K> {
+    /// Gets the number of keys with values.
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Gets the sum of all values.
+    pub fn sum(&self) -> U256 {
+        self.sum.get()
+    }
+
+    /// Gets the largest value, or zero if the map is empty.
+    ///
+    /// If the previous maximum has since been removed or lowered, this scans every value.
+    pub fn max(&self) -> U256 {
+        match self.max_stale.get() {
+            true => self.compute_max(),
+            false => self.max.get(),
+        }
+    }
+
+    /// Gets the value at the given key, or zero if none is there.
+    pub fn get(&self, key: K) -> U256 {
+        self.get_with_position(&key).0
+    }
+
+    /// Whether a value has been inserted at the given key.
+    pub fn contains_key(&self, key: K) -> bool {
+        self.position(&key).get() != U256::ZERO
+    }
+
+    /// Sets the value at the given key, returning the previous value if one was there.
+    ///
+    /// Fails without changing the map if the new sum wouldn't fit in a [`U256`].
+    pub fn insert(&mut self, key: K, value: U256) -> Result<Option<U256>, AggregateOverflow> {
+        let (prior, index) = self.get_with_position(&key);
+        let sum = self.sum.get() - prior;
+        let sum = sum.checked_add(value).ok_or(AggregateOverflow)?;
+
+        let prior = match index {
+            Some(index) => {
+                self.values.setter(index).unwrap().set(value);
+                Some(prior)
+            }
+            None => {
+                let slot = key.to_slot(self.positions.into());
+                self.values.push(value);
+                self.owners.push(slot);
+                self.position(&key).set(U256::from(self.values.len()));
+                None
+            }
+        };
+        self.sum.set(sum);
+
+        if !self.max_stale.get() {
+            let max = self.max.get();
+            if value > max {
+                self.max.set(value);
+            } else if prior == Some(max) && value < max {
+                self.max_stale.set(true);
+            }
+        }
+        Ok(prior)
+    }
+
+    /// Removes the value at the given key, returning it if one was there.
+    pub fn remove(&mut self, key: K) -> Option<U256> {
+        let (value, index) = self.get_with_position(&key);
+        let index = index?;
+
+        // move the last entry into the vacated position
+        let last = self.values.len() - 1;
+        if index != last {
+            let moved = self.values.get(last).unwrap();
+            let owner = self.owners.get(last).unwrap();
+            self.values.setter(index).unwrap().set(moved);
+            self.owners.setter(index).unwrap().set(owner);
+
+            // SAFETY: the owner's position is only ever accessed through `&mut self`
+            let mut position = unsafe { StorageU256::new(owner, 0) };
+            position.set(U256::from(index + 1));
+        }
+        self.values.pop();
+        self.owners.pop();
+        self.position(&key).erase();
+
+        self.sum.set(self.sum.get() - value);
+        if self.values.is_empty() {
+            self.max.erase();
+            self.max_stale.erase();
+        } else if value == self.max.get() {
+            self.max_stale.set(true);
+        }
+        Some(value)
+    }
+
+    /// Recomputes the max if it's stale, persisting the result.
+    pub fn refresh_max(&mut self) -> U256 {
+        if !self.max_stale.get() {
+            return self.max.get();
+        }
+        let max = self.compute_max();
+        self.max.set(max);
+        self.max_stale.set(false);
+        max
+    }
+
+    /// Finds the largest value by scanning them all.
+    fn compute_max(&self) -> U256 {
+        (0..self.values.len())
+            .map(|i| self.values.get(i).unwrap())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Gets the value at a given key along with its index among the values.
+    fn get_with_position(&self, key: &K) -> (U256, Option<usize>) {
+        let position: usize = self.position(key).get().try_into().unwrap();
+        match position {
+            0 => (U256::ZERO, None),
+            x => (self.values.get(x - 1).unwrap(), Some(x - 1)),
+        }
+    }
+
+    /// Gets an accessor to the 1-based position of a key's value, which is zero when absent.
+    fn position(&self, key: &K) -> StorageU256 {
+        let slot = key.to_slot(B256::from(self.positions));
+        // SAFETY: positions are only written through `&mut self`
+        unsafe { StorageU256::new(slot, 0) }
+    }
+}
+
+impl<K: StorageKey> Erase for AggregateMap<K> {
+    fn erase(&mut self) {
+        for index in 0..self.owners.len() {
+            let owner = self.owners.get(index).unwrap();
+            // SAFETY: the owner's position is only ever accessed through `&mut self`
+            unsafe { StorageU256::new(owner, 0).erase() };
+        }
+        self.values.erase();
+        self.owners.erase();
+        self.sum.erase();
+        self.max.erase();
+        self.max_stale.erase();
+    }
+}
+
+/// Error for an [`AggregateMap::insert`] that would overflow the sum of the map's values.
+///
+/// As revert data, this is the same `Panic(0x11)` Solidity uses for arithmetic overflow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggregateOverflow;
+
+impl From<AggregateOverflow> for Vec<u8> {
+    fn from(_: AggregateOverflow) -> Self {
+        let mut data = Vec::with_capacity(36);
+        data.extend(crate::function_selector!("Panic", U256));
+        data.extend(U256::from(0x11).to_be_bytes::<32>());
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_host;
+    use alloy_primitives::Address;
+
+    fn map() -> AggregateMap<Address> {
+        unsafe { AggregateMap::new(U256::ZERO, 0) }
+    }
+
+    fn addr(n: u8) -> Address {
+        Address::with_last_byte(n)
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let mut map = map();
+        assert_eq!(
+            (map.count(), map.sum(), map.max()),
+            (0, U256::ZERO, U256::ZERO)
+        );
+
+        assert_eq!(map.insert(addr(1), U256::from(10)), Ok(None));
+        assert_eq!(map.insert(addr(2), U256::from(30)), Ok(None));
+        assert_eq!(map.insert(addr(3), U256::from(20)), Ok(None));
+        assert_eq!(
+            (map.count(), map.sum(), map.max()),
+            (3, U256::from(60), U256::from(30))
+        );
+
+        assert_eq!(
+            map.insert(addr(1), U256::from(15)),
+            Ok(Some(U256::from(10)))
+        );
+        assert_eq!(
+            (map.count(), map.sum(), map.max()),
+            (3, U256::from(65), U256::from(30))
+        );
+        assert_eq!(map.get(addr(1)), U256::from(15));
+        assert!(map.contains_key(addr(3)));
+        assert!(!map.contains_key(addr(4)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut map = map();
+        map.insert(addr(1), U256::MAX - U256::from(1)).unwrap();
+        assert_eq!(map.insert(addr(2), U256::from(2)), Err(AggregateOverflow));
+        assert!(!map.contains_key(addr(2)));
+        assert_eq!((map.count(), map.sum()), (1, U256::MAX - U256::from(1)));
+
+        // replacing a value only adds the difference
+        assert_eq!(
+            map.insert(addr(1), U256::MAX),
+            Ok(Some(U256::MAX - U256::from(1)))
+        );
+        assert_eq!(map.sum(), U256::MAX);
+
+        let revert: Vec<u8> = AggregateOverflow.into();
+        assert_eq!(
+            hex::encode(revert),
+            "4e487b71".to_owned() + &format!("{:064x}", 0x11)
+        );
+    }
+
+    #[test]
+    fn test_remove_max() {
+        let mut map = map();
+        map.insert(addr(1), U256::from(10)).unwrap();
+        map.insert(addr(2), U256::from(30)).unwrap();
+        map.insert(addr(3), U256::from(20)).unwrap();
+
+        assert_eq!(map.remove(addr(2)), Some(U256::from(30)));
+        assert_eq!(map.remove(addr(2)), None);
+        assert_eq!(
+            (map.count(), map.sum(), map.max()),
+            (2, U256::from(30), U256::from(20))
+        );
+
+        // the last entry moved into the removed one's place
+        assert_eq!(map.get(addr(3)), U256::from(20));
+        assert_eq!(map.remove(addr(3)), Some(U256::from(20)));
+        assert_eq!(map.refresh_max(), U256::from(10));
+
+        // lowering the max marks it stale, and raising any value past it does not
+        map.insert(addr(1), U256::from(5)).unwrap();
+        map.insert(addr(4), U256::from(7)).unwrap();
+        assert_eq!(map.max(), U256::from(7));
+        assert_eq!(map.refresh_max(), U256::from(7));
+    }
+
+    #[test]
+    fn test_erase() {
+        let mut map = map();
+        map.insert(addr(1), U256::from(10)).unwrap();
+        map.insert(addr(2), U256::from(30)).unwrap();
+        map.erase();
+
+        assert_eq!(
+            (map.count(), map.sum(), map.max()),
+            (0, U256::ZERO, U256::ZERO)
+        );
+        assert!(!map.contains_key(addr(1)));
+        for slot in 0..6 {
+            assert_eq!(test_host::storage(U256::from(slot)), B256::ZERO);
+        }
+    }
+}
@@ -16,9 +16,11 @@ impl<S: StorageType, const N: usize> StorageType for StorageArray<S, N> {
     type WrapsMut<'a> = StorageGuardMut<'a, StorageArray<S, N>> where Self: 'a;
 
     const REQUIRED_SLOTS: usize = Self::required_slots();
+    const FIXED_SLOTS: bool = S::FIXED_SLOTS;
 
     unsafe fn new(slot: U256, offset: u8) -> Self {
         debug_assert!(offset == 0);
+        let () = Self::NOT_FIXED;
         Self {
             slot,
             marker: PhantomData,
@@ -35,10 +37,17 @@ impl<S: StorageType, const N: usize> StorageType for StorageArray<S, N> {
 }
 
 impl<S: StorageType, const N: usize> StorageArray<S, N> {
+    /// Arrays of more than one element can't have fixed slots, since the elements would share them.
+    const NOT_FIXED: () = assert!(
+        N <= 1 || !S::FIXED_SLOTS,
+        "StorageArray elements can't have fixed slots"
+    );
+
     /// Gets the number of elements stored.
     ///
     /// Although this type will always have the same length, this method is still provided for
     /// consistency with [`StorageVec`].
+    #[allow(clippy::len_without_is_empty)]
     pub const fn len(&self) -> usize {
         N
     }
@@ -46,7 +55,7 @@ impl<S: StorageType, const N: usize> StorageArray<S, N> {
     /// Gets an accessor to the element at a given index, if it exists.
     /// Note: the accessor is protected by a [`StorageGuard`], which restricts
     /// its lifetime to that of `&self`.
-    pub fn getter(&self, index: impl TryInto<usize>) -> Option<StorageGuard<S>> {
+    pub fn getter(&self, index: impl TryInto<usize>) -> Option<StorageGuard<'_, S>> {
         let store = unsafe { self.accessor(index)? };
         Some(StorageGuard::new(store))
     }
@@ -54,7 +63,7 @@ impl<S: StorageType, const N: usize> StorageArray<S, N> {
     /// Gets a mutable accessor to the element at a given index, if it exists.
     /// Note: the accessor is protected by a [`StorageGuardMut`], which restricts
     /// its lifetime to that of `&mut self`.
-    pub fn setter(&mut self, index: impl TryInto<usize>) -> Option<StorageGuardMut<S>> {
+    pub fn setter(&mut self, index: impl TryInto<usize>) -> Option<StorageGuardMut<'_, S>> {
         let store = unsafe { self.accessor(index)? };
         Some(StorageGuardMut::new(store))
     }
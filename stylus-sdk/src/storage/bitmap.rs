@@ -0,0 +1,102 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{StorageGuard, StorageGuardMut, StorageMap, StorageType, StorageU256};
+use alloy_primitives::U256;
+
+/// Accessor for a map of [`U256`] indices to booleans, packing 256 of them into each slot.
+///
+/// Flags at nearby indices share a slot, so setting many of them costs far less than a
+/// [`StorageBool`](super::StorageBool) each, as when tracking claims in an airdrop. The layout
+/// matches OpenZeppelin's `BitMaps`: index `i` is bit `i & 0xff` of the word at key `i >> 8` in
+/// a `mapping(uint256 => uint256)`.
+pub struct StorageBitMap {
+    buckets: StorageMap<U256, StorageU256>,
+}
+
+impl StorageType for StorageBitMap {
+    type Wraps<'a> = StorageGuard<'a, StorageBitMap>;
+    type WrapsMut<'a> = StorageGuardMut<'a, StorageBitMap>;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(offset == 0);
+        Self {
+            buckets: StorageMap::new(slot, offset),
+        }
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl StorageBitMap {
+    /// Gets the flag at the given index, which is `false` if never set.
+    pub fn get(&self, index: U256) -> bool {
+        let (bucket, mask) = Self::locate(index);
+        !(self.buckets.get(bucket) & mask).is_zero()
+    }
+
+    /// Sets the flag at the given index.
+    pub fn set(&mut self, index: U256, value: bool) {
+        let (bucket, mask) = Self::locate(index);
+        let mut word = self.buckets.setter(bucket);
+        let bits = word.get();
+        match value {
+            true => word.set(bits | mask),
+            false => word.set(bits & !mask),
+        }
+    }
+
+    /// Flips the flag at the given index, returning its new value.
+    pub fn flip(&mut self, index: U256) -> bool {
+        let (bucket, mask) = Self::locate(index);
+        let mut word = self.buckets.setter(bucket);
+        let flipped = word.get() ^ mask;
+        word.set(flipped);
+        !(flipped & mask).is_zero()
+    }
+
+    /// The key of the word holding the flag at `index`, and the flag's bit in it.
+    fn locate(index: U256) -> (U256, U256) {
+        let bit = index.as_limbs()[0] & 0xff;
+        (index >> 8, U256::from(1) << bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageKey, test_host};
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_bitmap() {
+        let root = U256::from(0x6d6d);
+        let mut claimed = unsafe { StorageBitMap::new(root, 0) };
+        let index = |i: u64| U256::from(i);
+
+        for i in [0, 255, 256] {
+            assert!(!claimed.get(index(i)));
+            claimed.set(index(i), true);
+            assert!(claimed.get(index(i)));
+        }
+        assert!(!claimed.get(index(1)));
+        assert!(!claimed.flip(index(255)));
+        assert!(claimed.flip(index(1)));
+        claimed.set(index(0), false);
+
+        // like OpenZeppelin's BitMaps, 256 flags share a word of a mapping
+        let word = |bucket: u64| test_host::storage(U256::from(bucket).to_slot(root.into()));
+        assert_eq!(word(0), B256::from(U256::from(0b10)));
+        assert_eq!(word(1), B256::from(U256::from(1)));
+
+        claimed.set(U256::MAX, true);
+        assert!(claimed.get(U256::MAX));
+        assert_eq!(word(0), B256::from(U256::from(0b10)));
+    }
+}
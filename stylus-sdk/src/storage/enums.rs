@@ -0,0 +1,156 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{Erase, GlobalStorage, Storage, StorageGuard, StorageGuardMut, StorageType};
+use alloc::vec::Vec;
+use alloy_primitives::U256;
+use core::{cell::OnceCell, marker::PhantomData};
+
+/// Trait for fieldless enums that, like Solidity enums, are represented as a `uint8`.
+///
+/// This trait is usually implemented via `#[derive(StorageEnum)]`, which numbers the variants
+/// from zero in declaration order and also implements [`AbiType`](crate::abi::AbiType).
+pub trait StorageEnum: Sized {
+    /// Gets the variant's discriminant.
+    fn to_u8(&self) -> u8;
+
+    /// Gets the variant with the given discriminant, if there is one.
+    fn from_u8(value: u8) -> Option<Self>;
+}
+
+/// Accessor for a storage-backed [`StorageEnum`], which occupies a single byte.
+#[derive(Debug)]
+pub struct StorageEnumU8<E: StorageEnum> {
+    slot: U256,
+    offset: u8,
+    cached: OnceCell<u8>,
+    marker: PhantomData<E>,
+}
+
+impl<E: StorageEnum> StorageEnumU8<E> {
+    /// Gets the underlying enum in persistent storage.
+    ///
+    /// Fails if the stored value doesn't correspond to a variant, which can only happen if the
+    /// slot was written by something other than this accessor.
+    pub fn get(&self) -> Result<E, InvalidEnumValue> {
+        let value = self.get_raw();
+        E::from_u8(value).ok_or(InvalidEnumValue(value))
+    }
+
+    /// Sets the underlying enum in persistent storage.
+    pub fn set(&mut self, value: E) {
+        let value = value.to_u8();
+        super::overwrite_cell(&mut self.cached, value);
+        unsafe { Storage::set_byte(self.slot, self.offset.into(), value) }
+    }
+
+    /// Gets the underlying discriminant in persistent storage without validating it.
+    pub fn get_raw(&self) -> u8 {
+        *self
+            .cached
+            .get_or_init(|| unsafe { Storage::get_byte(self.slot, self.offset.into()) })
+    }
+}
+
+impl<E: StorageEnum> StorageType for StorageEnumU8<E> {
+    type Wraps<'a> = StorageGuard<'a, Self> where Self: 'a;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self> where Self: 'a;
+
+    const SLOT_BYTES: usize = 1;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        Self {
+            slot,
+            offset,
+            cached: OnceCell::new(),
+            marker: PhantomData,
+        }
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl<E: StorageEnum> Erase for StorageEnumU8<E> {
+    fn erase(&mut self) {
+        super::overwrite_cell(&mut self.cached, 0);
+        unsafe { Storage::set_byte(self.slot, self.offset.into(), 0) }
+    }
+}
+
+/// Error for a stored discriminant that doesn't correspond to any variant of a [`StorageEnum`].
+///
+/// As revert data, this is the same `Panic(0x21)` Solidity uses for invalid enum conversions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidEnumValue(pub u8);
+
+impl From<InvalidEnumValue> for Vec<u8> {
+    fn from(_: InvalidEnumValue) -> Self {
+        let mut data = Vec::with_capacity(36);
+        data.extend(crate::function_selector!("Panic", U256));
+        data.extend(U256::from(0x21).to_be_bytes::<32>());
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abi::AbiType, stylus_proc::StorageEnum};
+    use alloy_sol_types::SolType;
+
+    #[derive(StorageEnum, Clone, Copy, Debug, PartialEq)]
+    enum AuctionState {
+        Open,
+        Closed,
+        Settled,
+    }
+
+    #[test]
+    fn test_storage_enum() {
+        let mut state: StorageEnumU8<AuctionState> = unsafe { StorageEnumU8::new(U256::ZERO, 31) };
+        assert_eq!(state.get(), Ok(AuctionState::Open));
+
+        state.set(AuctionState::Settled);
+        assert_eq!(state.get(), Ok(AuctionState::Settled));
+        assert_eq!(crate::test_host::storage(U256::ZERO)[31], 2);
+
+        unsafe { Storage::set_byte(U256::ZERO, 31, 9) };
+        let state: StorageEnumU8<AuctionState> = unsafe { StorageEnumU8::new(U256::ZERO, 31) };
+        assert_eq!(state.get(), Err(InvalidEnumValue(9)));
+
+        let revert: Vec<u8> = InvalidEnumValue(9).into();
+        assert_eq!(
+            hex::encode(revert),
+            "4e487b71".to_owned() + &format!("{:064x}", 0x21)
+        );
+    }
+
+    #[test]
+    fn test_storage_enum_abi() {
+        assert_eq!(AuctionState::ABI.as_str(), "uint8");
+        assert_eq!(
+            <AuctionState as AbiType>::SolType::abi_encode(&AuctionState::Closed),
+            {
+                let mut word = [0; 32];
+                word[31] = 1;
+                word
+            }
+        );
+        let decoded = <AuctionState as AbiType>::SolType::abi_decode(&[0; 32], true);
+        assert_eq!(decoded.unwrap(), AuctionState::Open);
+
+        let mut invalid = [0; 32];
+        invalid[31] = 3;
+        assert!(<AuctionState as AbiType>::SolType::abi_decode(&invalid, true).is_err());
+
+        // without validation, out-of-range values clamp to the last variant
+        let decoded = <AuctionState as AbiType>::SolType::abi_decode(&invalid, false);
+        assert_eq!(decoded.unwrap(), AuctionState::Settled);
+    }
+}
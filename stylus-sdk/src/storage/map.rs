@@ -3,7 +3,7 @@
 
 use crate::crypto;
 
-use super::{Erase, SimpleStorageType, StorageGuard, StorageGuardMut, StorageType};
+use super::{Erase, SimpleStorageType, StorageGuard, StorageGuardMut, StorageType, StorageUint};
 use alloc::{string::String, vec::Vec};
 use alloy_primitives::{Address, FixedBytes, Signed, Uint, B256, U160, U256};
 use core::marker::PhantomData;
@@ -24,6 +24,7 @@ where
 
     unsafe fn new(slot: U256, offset: u8) -> Self {
         debug_assert!(offset == 0);
+        let () = Self::NOT_FIXED;
         Self {
             slot,
             marker: PhantomData,
@@ -44,13 +45,16 @@ where
     K: StorageKey,
     V: StorageType,
 {
+    /// Values live at slots derived from their keys, so can't have fixed slots of their own.
+    const NOT_FIXED: () = assert!(!V::FIXED_SLOTS, "StorageMap values can't have fixed slots");
+
     /// Where in a word to access the wrapped value.
     const CHILD_OFFSET: u8 = 32 - V::SLOT_BYTES as u8;
 
     /// Gets an accessor to the element at the given key, or the zero-value if none is there.
     /// Note: the accessor is protected by a [`StorageGuard`], which restricts its lifetime
     /// to that of `&self`.
-    pub fn getter(&self, key: K) -> StorageGuard<V> {
+    pub fn getter(&self, key: K) -> StorageGuard<'_, V> {
         let slot = key.to_slot(self.slot.into());
         unsafe { StorageGuard::new(V::new(slot, Self::CHILD_OFFSET)) }
     }
@@ -58,7 +62,7 @@ where
     /// Gets a mutable accessor to the element at the given key, or the zero-value is none is there.
     /// Note: the accessor is protected by a [`StorageGuardMut`], which restricts its lifetime
     /// to that of `&mut self`.
-    pub fn setter(&mut self, key: K) -> StorageGuardMut<V> {
+    pub fn setter(&mut self, key: K) -> StorageGuardMut<'_, V> {
         let slot = key.to_slot(self.slot.into());
         unsafe { StorageGuardMut::new(V::new(slot, Self::CHILD_OFFSET)) }
     }
@@ -68,6 +72,21 @@ where
         let store = self.getter(key);
         unsafe { store.into_raw().load() }
     }
+
+    /// Gets a handle to the element at the given key for reading and writing it in place.
+    /// The key's slot is computed once, however many times the handle is used.
+    ///
+    /// ```ignore
+    /// let mut balance = self.balances.entry(owner);
+    /// balance.sub(amount).ok_or(InsufficientBalance {})?;
+    /// self.balances.entry(to).update(|balance| balance + amount);
+    /// ```
+    pub fn entry(&mut self, key: K) -> StorageEntry<'_, V> {
+        StorageEntry {
+            slot: key.to_slot(self.slot.into()),
+            marker: PhantomData,
+        }
+    }
 }
 
 impl<'a, K, V> StorageMap<K, V>
@@ -116,14 +135,82 @@ where
     V: Erase,
 {
     /// Delete the element at the given key, if it exists.
+    /// To get the element being deleted, as for an event, use [`take`][Self::take] instead.
     pub fn delete(&mut self, key: K) {
         let mut store = self.setter(key);
         store.erase();
     }
 }
 
+/// A handle to the element at a key of a [`StorageMap`], as returned by [`StorageMap::entry`].
+pub struct StorageEntry<'a, V: StorageType> {
+    slot: U256,
+    marker: PhantomData<&'a mut V>,
+}
+
+impl<'a, V: StorageType> StorageEntry<'a, V> {
+    /// Where in a word to access the wrapped value.
+    const CHILD_OFFSET: u8 = 32 - V::SLOT_BYTES as u8;
+
+    /// Gets an accessor to the element.
+    pub fn getter(&self) -> StorageGuard<'_, V> {
+        unsafe { StorageGuard::new(V::new(self.slot, Self::CHILD_OFFSET)) }
+    }
+
+    /// Gets a mutable accessor to the element.
+    pub fn setter(&mut self) -> StorageGuardMut<'_, V> {
+        unsafe { StorageGuardMut::new(V::new(self.slot, Self::CHILD_OFFSET)) }
+    }
+
+    /// Gets the element, or the zero value if none is there.
+    pub fn get(&self) -> V::Wraps<'_> {
+        unsafe { V::new(self.slot, Self::CHILD_OFFSET).load() }
+    }
+}
+
+impl<'a, 's, V> StorageEntry<'a, V>
+where
+    V: SimpleStorageType<'s>,
+{
+    /// Sets the element, overwriting what may have been there.
+    pub fn set(&mut self, value: V::Wraps<'s>) {
+        let mut store = unsafe { V::new(self.slot, Self::CHILD_OFFSET) };
+        store.set_by_wrapped(value);
+    }
+
+    /// Replaces the element with the result of `f`, loading and storing it once.
+    pub fn update(&mut self, f: impl FnOnce(V::Wraps<'s>) -> V::Wraps<'s>) {
+        let value = unsafe { V::new(self.slot, Self::CHILD_OFFSET).load() };
+        self.set(f(value));
+    }
+}
+
+impl<'a, const B: usize, const L: usize> StorageEntry<'a, StorageUint<B, L>> {
+    /// Adds to the element, returning its new value.
+    /// Returns `None` and leaves the element unchanged if the addition overflows.
+    pub fn add(&mut self, value: Uint<B, L>) -> Option<Uint<B, L>> {
+        let sum = self.get().checked_add(value)?;
+        self.set(sum);
+        Some(sum)
+    }
+
+    /// Subtracts from the element, returning its new value.
+    /// Returns `None` and leaves the element unchanged if the subtraction underflows.
+    pub fn sub(&mut self, value: Uint<B, L>) -> Option<Uint<B, L>> {
+        let difference = self.get().checked_sub(value)?;
+        self.set(difference);
+        Some(difference)
+    }
+}
+
 /// Trait that allows types to be the key of a [`StorageMap`].
 ///
+/// Tuples of keys act as composite keys that map to the same slots as the equivalent nested maps.
+/// That is, `(a, b)` in a `StorageMap<(A, B), V>` is stored at `keccak(b . keccak(a . root))`,
+/// just like `b` in the inner map found at `a` in a `StorageMap<A, StorageMap<B, V>>`. This means
+/// a Solidity `mapping(A => mapping(B => V))` can be accessed in a single lookup without changing
+/// its storage layout.
+///
 /// Note: the assignment of slots must be injective.
 pub trait StorageKey {
     /// Assigns a slot based on the key and where the map is rooted.
@@ -221,3 +308,114 @@ macro_rules! impl_key {
 }
 
 impl_key!(u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize);
+
+// Composite keys derive their slots one component at a time, like the equivalent nested maps.
+macro_rules! impl_composite_key {
+    ($($ty:ident $index:tt),+) => {
+        impl<$($ty: StorageKey),+> StorageKey for ($($ty,)+) {
+            fn to_slot(&self, root: B256) -> U256 {
+                let slot: U256 = root.into();
+                $(let slot = self.$index.to_slot(B256::from(slot));)+
+                slot
+            }
+        }
+    };
+}
+
+impl_composite_key!(A 0, B 1);
+impl_composite_key!(A 0, B 1, C 2);
+impl_composite_key!(A 0, B 1, C 2, D 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageU256, test_host};
+
+    #[test]
+    fn test_composite_key_slots() {
+        let root = B256::repeat_byte(0x11);
+        let owner = Address::repeat_byte(0xaa);
+        let spender = Address::repeat_byte(0xbb);
+        let id = U256::from(7);
+
+        let nested = spender.to_slot(owner.to_slot(root).into());
+        assert_eq!((owner, spender).to_slot(root), nested);
+
+        let nested = id.to_slot(spender.to_slot(owner.to_slot(root).into()).into());
+        assert_eq!((owner, spender, id).to_slot(root), nested);
+    }
+
+    #[test]
+    fn test_composite_map_matches_nested() {
+        let owner = Address::repeat_byte(0xaa);
+        let spender = Address::repeat_byte(0xbb);
+
+        let mut flat: StorageMap<(Address, Address), StorageU256> =
+            unsafe { StorageMap::new(U256::from(3), 0) };
+        let nested: StorageMap<Address, StorageMap<Address, StorageU256>> =
+            unsafe { StorageMap::new(U256::from(3), 0) };
+
+        flat.insert((owner, spender), U256::from(100));
+        assert_eq!(nested.getter(owner).get(spender), U256::from(100));
+        assert_eq!(nested.getter(spender).get(owner), U256::ZERO);
+    }
+
+    #[test]
+    fn test_entry() {
+        let owner = Address::repeat_byte(0xcc);
+        let mut map: StorageMap<Address, StorageU256> =
+            unsafe { StorageMap::new(U256::from(0x5858), 0) };
+
+        let mut entry = map.entry(owner);
+        assert_eq!(entry.get(), U256::ZERO);
+        assert_eq!(entry.add(U256::from(10)), Some(U256::from(10)));
+        assert_eq!(entry.sub(U256::from(3)), Some(U256::from(7)));
+        assert_eq!(entry.sub(U256::from(8)), None);
+        assert_eq!(entry.get(), U256::from(7));
+        entry.update(|value| value * U256::from(6));
+        assert_eq!(map.get(owner), U256::from(42));
+
+        let mut entry = map.entry(owner);
+        assert_eq!(entry.add(U256::MAX), None);
+        entry.set(U256::MAX);
+        assert_eq!(map.get(owner), U256::MAX);
+        assert_eq!(map.get(Address::ZERO), U256::ZERO);
+    }
+
+    #[test]
+    fn test_take() {
+        let owner = Address::repeat_byte(0xdd);
+        let root = U256::from(0x6262);
+        let mut map: StorageMap<Address, StorageU256> = unsafe { StorageMap::new(root, 0) };
+
+        let slot = owner.to_slot(root.into());
+        map.insert(owner, U256::from(9));
+        assert_eq!(test_host::storage(slot), B256::from(U256::from(9)));
+        assert_eq!(map.take(owner), U256::from(9));
+        assert_eq!(map.take(owner), U256::ZERO);
+
+        // the slot is zeroed, earning the refund
+        assert_eq!(test_host::storage(slot), B256::ZERO);
+    }
+
+    #[test]
+    fn test_storage_dump() {
+        let mut balances: StorageMap<Address, StorageU256> =
+            unsafe { StorageMap::new(U256::from(0x6a6a), 0) };
+        for i in 1..=3 {
+            balances.insert(Address::repeat_byte(i), U256::from(i));
+        }
+        balances.delete(Address::repeat_byte(2));
+
+        // zeroed slots are left out
+        let mut values: Vec<_> = test_host::storage_dump()
+            .into_iter()
+            .map(|(_, value)| U256::from_be_bytes(value.0))
+            .collect();
+        values.sort();
+        assert_eq!(values, [U256::from(1), U256::from(3)]);
+
+        let slot = Address::repeat_byte(3).to_slot(B256::from(U256::from(0x6a6a)));
+        assert!(test_host::storage_dump().contains(&(slot.into(), U256::from(3).into())));
+    }
+}
@@ -18,28 +18,50 @@
 //!
 //! Storage Operations are also cached by default, ensuring that efficient usage is clean and auditable.
 //!
+//! For values that only need to last a single transaction, [`TransientU256`] and [`TransientMap`]
+//! provide access to [EIP-1153] transient storage, which is far cheaper to write.
+//!
 //! For a walkthrough of this module's features, please see [The Feature Overview][overview].
 //!
 //! [overview]: https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#storage
+//! [EIP-1153]: https://eips.ethereum.org/EIPS/eip-1153
 
 use crate::hostio;
+use alloc::vec::Vec;
 use alloy_primitives::{Address, BlockHash, BlockNumber, FixedBytes, Signed, Uint, B256, U256};
 use alloy_sol_types::sol_data::{ByteCount, SupportedFixedBytes};
 use core::{cell::OnceCell, marker::PhantomData, ops::Deref};
 
+pub use aggregate::{AggregateMap, AggregateOverflow};
 pub use array::StorageArray;
+pub use bitmap::StorageBitMap;
 pub use bytes::{StorageBytes, StorageString};
-pub use map::{StorageKey, StorageMap};
+pub use enums::{InvalidEnumValue, StorageEnum, StorageEnumU8};
+pub use map::{StorageEntry, StorageKey, StorageMap};
+pub use option::StorageOption;
 pub use traits::{
-    Erase, GlobalStorage, SimpleStorageType, StorageGuard, StorageGuardMut, StorageType,
-    TopLevelStorage,
+    Erase, GlobalStorage, SimpleStorageType, StorageGuard, StorageGuardMut, StorageReset,
+    StorageType, TopLevelStorage,
+};
+#[cfg(feature = "storage-transaction")]
+pub use transaction::StorageTransaction;
+pub use transient::{
+    ReentrancyGuardReentrantCall, ReentrancyLock, TransientMap, TransientReentrancyGuard,
+    TransientU256,
 };
 pub use vec::StorageVec;
 
+mod aggregate;
 mod array;
+mod bitmap;
 mod bytes;
+mod enums;
 mod map;
+mod option;
 mod traits;
+#[cfg(feature = "storage-transaction")]
+mod transaction;
+mod transient;
 mod vec;
 
 pub(crate) type Storage = StorageCache;
@@ -63,6 +85,8 @@ impl GlobalStorage for StorageCache {
     ///
     /// May alias storage.
     unsafe fn set_word(key: U256, value: B256) {
+        #[cfg(feature = "storage-transaction")]
+        transaction::record_write(key);
         hostio::storage_cache_bytes32(B256::from(key).as_ptr(), value.as_ptr())
     }
 }
@@ -83,6 +107,54 @@ impl StorageCache {
     }
 }
 
+/// Accesses the storage type `T` at the given `slot`, such as one specified by an EIP.
+///
+/// Borrowing the [`TopLevelStorage`] ensures that the contract's fields can't be used while the
+/// returned accessor lives. To declare fields at fixed slots instead, which is safe, see the
+/// `#[slot]` attribute of [`#[storage]`][storage].
+///
+/// ```no_run
+/// use stylus_sdk::{alloy_primitives::{uint, Address, U256}, prelude::*, storage};
+///
+/// // bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)
+/// const IMPLEMENTATION_SLOT: U256 =
+///     uint!(0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc_U256);
+///
+/// fn implementation(contract: &impl TopLevelStorage) -> Address {
+///     unsafe { storage::at_slot::<storage::StorageAddress>(contract, IMPLEMENTATION_SLOT).get() }
+/// }
+/// ```
+///
+/// # Safety
+///
+/// Accessors cache the values they read, so the slots of `T` must not be written through another
+/// accessor, such as a field of the contract, that's used after this one.
+///
+/// This can't be made safe by the borrow alone. The contract's fields outlive it, along with any
+/// values they cached before it began, so a field read before writing through the returned
+/// accessor would still see the old value afterward. Only the caller knows whether `slot`
+/// overlaps a field, while `#[slot]` fields are checked against each other when compiled.
+///
+/// [storage]: macro@stylus_proc::storage
+pub unsafe fn at_slot<'a, T: StorageType + 'a>(
+    _storage: &'a impl TopLevelStorage,
+    slot: U256,
+) -> StorageGuard<'a, T> {
+    StorageGuard::new(T::new(slot, (32 - T::SLOT_BYTES) as u8))
+}
+
+/// Mutably accesses the storage type `T` at the given `slot`.
+///
+/// # Safety
+///
+/// See [`at_slot`].
+pub unsafe fn at_slot_mut<'a, T: StorageType + 'a>(
+    _storage: &'a mut impl TopLevelStorage,
+    slot: U256,
+) -> StorageGuardMut<'a, T> {
+    StorageGuardMut::new(T::new(slot, (32 - T::SLOT_BYTES) as u8))
+}
+
 /// Overwrites the value in a cell.
 #[inline]
 fn overwrite_cell<T>(cell: &mut OnceCell<T>, value: T) {
@@ -458,6 +530,31 @@ impl StorageAddress {
         overwrite_cell(&mut self.cached, value);
         unsafe { Storage::set::<20>(self.slot, self.offset.into(), value.into()) }
     }
+
+    /// Sets the underlying [`Address`] in persistent storage, rejecting [`Address::ZERO`]
+    /// unless `allow_zero` is set.
+    ///
+    /// The error converts into the revert data for `error ZeroAddress()`, so it can be returned
+    /// directly from a `#[public]` method.
+    pub fn set_checked(&mut self, value: Address, allow_zero: bool) -> Result<(), ZeroAddress> {
+        if value.is_zero() && !allow_zero {
+            return Err(ZeroAddress);
+        }
+        self.set(value);
+        Ok(())
+    }
+}
+
+/// Error for writing the zero address via [`StorageAddress::set_checked`].
+///
+/// As revert data, this is the Solidity custom error `ZeroAddress()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZeroAddress;
+
+impl From<ZeroAddress> for Vec<u8> {
+    fn from(_: ZeroAddress) -> Self {
+        crate::function_selector!("ZeroAddress").to_vec()
+    }
 }
 
 impl StorageType for StorageAddress {
@@ -680,3 +777,273 @@ impl<T> StorageType for PhantomData<T> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stylus_proc::storage;
+    use alloy_primitives::uint;
+
+    #[storage(split)]
+    struct Kennel {
+        names: StorageVec<StorageString>,
+        ages: StorageMap<U256, StorageU8>,
+    }
+
+    #[test]
+    fn test_split_mut() {
+        let mut kennel = unsafe { Kennel::new(U256::ZERO, 0) };
+        let split = kennel.split_mut();
+
+        let mut name = split.names.grow();
+        let mut age = split.ages.setter(U256::ZERO);
+        name.set_str("Rex");
+        age.set(alloy_primitives::U8::from(3));
+
+        assert_eq!(kennel.names.get(0).unwrap().get_string(), "Rex");
+        assert_eq!(kennel.ages.get(U256::ZERO), alloy_primitives::U8::from(3));
+    }
+
+    #[storage]
+    struct Packed {
+        flag: StorageBool,
+        owner: StorageAddress,
+        kind: StorageU8,
+        total: StorageU256,
+    }
+
+    #[test]
+    fn test_packed_layout() {
+        assert_eq!(Packed::REQUIRED_SLOTS, 2);
+
+        let mut packed = unsafe { Packed::new(U256::ZERO, 0) };
+        let owner = Address::repeat_byte(0xaa);
+        packed.flag.set(true);
+        packed.owner.set(owner);
+        packed.kind.set(alloy_primitives::U8::from(7));
+        packed.total.set(U256::from(9));
+
+        // like Solidity, the first field occupies the lowest-order bytes of the slot
+        let mut expected = B256::ZERO;
+        expected[31] = 1;
+        expected[11..31].copy_from_slice(owner.as_slice());
+        expected[10] = 7;
+        assert_eq!(crate::test_host::storage(U256::ZERO), expected);
+        assert_eq!(
+            crate::test_host::storage(U256::from(1)),
+            B256::from(U256::from(9))
+        );
+    }
+
+    // bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)
+    const ADMIN_SLOT: U256 =
+        uint!(0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103_U256);
+
+    #[storage]
+    struct Proxy {
+        version: StorageU64,
+        #[slot(ADMIN_SLOT)]
+        admin: StorageAddress,
+        #[slot(ADMIN_SLOT.wrapping_add(uint!(1_U256)))]
+        beacons: StorageVec<StorageAddress>,
+    }
+
+    unsafe impl TopLevelStorage for Proxy {}
+
+    #[test]
+    fn test_explicit_slots() {
+        assert_eq!(Proxy::REQUIRED_SLOTS, 1);
+
+        let mut proxy = unsafe { Proxy::new(U256::from(100), 0) };
+        let admin = Address::repeat_byte(0xad);
+        proxy.version.set(alloy_primitives::U64::from(2));
+        proxy.admin.set(admin);
+        proxy.beacons.push(admin);
+
+        assert_eq!(
+            crate::test_host::storage(U256::from(100)),
+            B256::from(U256::from(2))
+        );
+        assert_eq!(crate::test_host::storage(ADMIN_SLOT), admin.into_word());
+        assert_eq!(
+            crate::test_host::storage(ADMIN_SLOT + U256::from(1)),
+            B256::from(U256::from(1))
+        );
+
+        let version_slot = U256::from(100);
+        let version = unsafe { at_slot::<StorageU64>(&proxy, version_slot) };
+        assert_eq!(version.get(), alloy_primitives::U64::from(2));
+
+        let mut admin = unsafe { at_slot_mut::<StorageAddress>(&mut proxy, ADMIN_SLOT) };
+        admin.set(Address::ZERO);
+        assert_eq!(crate::test_host::storage(ADMIN_SLOT), B256::ZERO);
+    }
+
+    #[storage]
+    struct Proxies {
+        proxy: Proxy,
+    }
+
+    #[test]
+    fn test_fixed_slots() {
+        // structs holding fixed slots have them too, but collections can't hold them
+        let fixed = [
+            Proxy::FIXED_SLOTS,
+            Packed::FIXED_SLOTS,
+            Proxies::FIXED_SLOTS,
+            StorageArray::<Proxy, 1>::FIXED_SLOTS,
+            StorageVec::<Packed>::FIXED_SLOTS,
+        ];
+        assert_eq!(fixed, [true, false, true, true, false]);
+
+        let mut proxies = unsafe { Proxies::new(U256::from(100), 0) };
+        proxies.proxy.admin.set(Address::repeat_byte(0xad));
+        assert_eq!(
+            crate::test_host::storage(ADMIN_SLOT),
+            Address::repeat_byte(0xad).into_word()
+        );
+    }
+
+    #[storage(namespace = "example.main")]
+    struct Main {
+        x: StorageU256,
+        y: StorageU256,
+    }
+
+    #[test]
+    fn test_namespace() {
+        // the example from ERC-7201
+        let slot = uint!(0x183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500_U256);
+        assert_eq!(Main::NAMESPACE_SLOT, slot);
+
+        let mut main = unsafe { Main::new(U256::ZERO, 0) };
+        main.x.set(U256::from(4));
+        main.y.set(U256::from(5));
+        assert_eq!(crate::test_host::storage(slot), B256::from(U256::from(4)));
+        assert_eq!(
+            crate::test_host::storage(slot + U256::from(1)),
+            B256::from(U256::from(5))
+        );
+
+        // the namespace is fixed, so there's only one instance
+        let fixed = [Main::FIXED_SLOTS, StorageArray::<Main, 1>::FIXED_SLOTS];
+        assert_eq!(fixed, [true, true]);
+    }
+
+    #[storage]
+    #[derive(crate::stylus_proc::Erase)]
+    struct Settings {
+        #[default(U256::from(30))]
+        fee: StorageU256,
+        #[default(true)]
+        enabled: StorageBool,
+        owner: StorageAddress,
+    }
+
+    #[test]
+    fn test_defaults() {
+        let root = U256::from(0x5959);
+        let mut settings = unsafe { Settings::new(root, 0) };
+        assert_eq!(settings.fee.get(), U256::ZERO);
+
+        assert!(settings.init_defaults());
+        assert_eq!(settings.fee.get(), U256::from(30));
+        assert!(settings.enabled.get());
+        assert_eq!(settings.owner.get(), Address::ZERO);
+
+        // later changes aren't overwritten
+        settings.fee.set(U256::from(5));
+        assert!(!settings.init_defaults());
+        assert_eq!(settings.fee.get(), U256::from(5));
+
+        // other instances track their defaults separately
+        let mut other = unsafe { Settings::new(root + U256::from(2), 0) };
+        assert!(other.init_defaults());
+        assert_eq!(other.fee.get(), U256::from(30));
+        other.erase();
+        assert_eq!(other.fee.get(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_slots_overlap() {
+        use crate::abi::internal::slots_overlap;
+
+        let slot = U256::from(10);
+        assert!(slots_overlap(slot, 1, slot, 1));
+        assert!(slots_overlap(slot, 3, U256::from(12), 1));
+        assert!(!slots_overlap(slot, 2, U256::from(12), 1));
+        assert!(!slots_overlap(U256::from(12), 1, slot, 2));
+        assert!(slots_overlap(U256::MAX, 1, U256::MAX, 2));
+        assert!(!slots_overlap(U256::MAX, 1, U256::ZERO, 5));
+        assert!(!slots_overlap(slot, 0, slot, 1));
+    }
+
+    #[test]
+    fn test_set_checked_address() {
+        let mut owner = unsafe { StorageAddress::new(U256::ZERO, 0) };
+        let alice = Address::repeat_byte(0xaa);
+
+        assert_eq!(owner.set_checked(alice, false), Ok(()));
+        assert_eq!(owner.set_checked(Address::ZERO, false), Err(ZeroAddress));
+        assert_eq!(owner.get(), alice);
+
+        assert_eq!(owner.set_checked(Address::ZERO, true), Ok(()));
+        assert_eq!(owner.get(), Address::ZERO);
+
+        let revert: Vec<u8> = ZeroAddress.into();
+        assert_eq!(revert, crate::crypto::keccak("ZeroAddress()")[..4]);
+    }
+
+    mod reset {
+        use super::*;
+        use crate::stylus_proc::{entrypoint, public, sol_storage, Erase, StorageReset};
+
+        sol_storage! {
+            #[derive(StorageReset)]
+            struct Ledger {
+                uint256 total;
+                mapping(address => uint256) balances;
+            }
+        }
+
+        #[entrypoint]
+        #[storage]
+        #[derive(Erase)]
+        struct Token {
+            #[borrow]
+            settings: Settings,
+            #[borrow]
+            ledger: Ledger,
+            holders: StorageVec<StorageAddress>,
+            balances: StorageMap<Address, StorageU256>,
+        }
+
+        #[public]
+        impl Token {}
+
+        #[test]
+        fn test_reset() {
+            let mut token = unsafe { Token::new(U256::ZERO, 0) };
+            let holder = Address::repeat_byte(0xbb);
+            token.settings.init_defaults();
+            token.holders.push(holder);
+            token.balances.insert(holder, U256::from(10));
+            token.ledger.total.set(U256::from(10));
+            token.ledger.balances.insert(holder, U256::from(10));
+
+            token.reset();
+            assert_eq!(token.settings.fee.get(), U256::ZERO);
+            assert!(!token.settings.enabled.get());
+            assert!(token.holders.is_empty());
+            assert_eq!(token.balances.get(holder), U256::from(10));
+
+            // nested structs with maps are reset too
+            assert_eq!(token.ledger.total.get(), U256::ZERO);
+            assert_eq!(token.ledger.balances.get(holder), U256::from(10));
+
+            // as are defaults, which can be applied again
+            assert!(token.settings.init_defaults());
+            assert_eq!(token.settings.fee.get(), U256::from(30));
+        }
+    }
+}
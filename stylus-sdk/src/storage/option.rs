@@ -0,0 +1,177 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{Erase, SimpleStorageType, StorageBool, StorageGuard, StorageGuardMut, StorageType};
+use alloy_primitives::U256;
+use core::marker::PhantomData;
+
+/// Accessor for an optional value in persistent storage, which tells a value that was never set
+/// apart from one that was set to zero.
+///
+/// The value is stored as `S` would be, along with a presence flag taking one more byte. Values
+/// smaller than a word keep the flag in the byte just above them, so a `StorageOption<StorageU64>`
+/// packs into 9 bytes of a slot. Values that fill a word, like [`StorageU256`](super::StorageU256),
+/// keep the flag in the low byte of the following slot, taking two slots in all.
+///
+/// ```no_run
+/// use stylus_sdk::prelude::*;
+/// use stylus_sdk::storage::{StorageOption, StorageU64};
+///
+/// #[storage]
+/// pub struct Auction {
+///     reserve_price: StorageOption<StorageU64>,
+/// }
+///
+/// impl Auction {
+///     fn has_reserve(&self) -> bool {
+///         // a reserve price of zero is different from not having one
+///         self.reserve_price.get().is_some()
+///     }
+/// }
+/// ```
+pub struct StorageOption<S: StorageType> {
+    slot: U256,
+    offset: u8,
+    marker: PhantomData<S>,
+}
+
+impl<S: StorageType> StorageType for StorageOption<S> {
+    type Wraps<'a> = StorageGuard<'a, Self> where Self: 'a;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self> where Self: 'a;
+
+    const SLOT_BYTES: usize = if Self::PACKED { S::SLOT_BYTES + 1 } else { 32 };
+
+    const REQUIRED_SLOTS: usize = if Self::PACKED { 0 } else { 2 };
+
+    const FIXED_SLOTS: bool = S::FIXED_SLOTS;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(S::REQUIRED_SLOTS == 0);
+        Self {
+            slot,
+            offset,
+            marker: PhantomData,
+        }
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl<S: StorageType> StorageOption<S> {
+    /// Whether the flag shares a word with the value.
+    const PACKED: bool = S::SLOT_BYTES < 32;
+
+    /// Whether a value is present.
+    pub fn is_some(&self) -> bool {
+        self.flag().get()
+    }
+
+    /// Whether the value is absent.
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// The presence flag.
+    fn flag(&self) -> StorageBool {
+        match Self::PACKED {
+            true => unsafe { StorageBool::new(self.slot, self.offset) },
+            false => unsafe { StorageBool::new(self.slot + U256::from(1), 31) },
+        }
+    }
+
+    /// The accessor for the value, which is zero when absent.
+    fn value(&self) -> S {
+        match Self::PACKED {
+            true => unsafe { S::new(self.slot, self.offset + 1) },
+            false => unsafe { S::new(self.slot, 0) },
+        }
+    }
+}
+
+impl<'a, S: SimpleStorageType<'a>> StorageOption<S> {
+    /// Gets the value, or [`None`] if it's absent.
+    pub fn get(&self) -> Option<S::Wraps<'a>> {
+        self.is_some().then(|| self.value().into())
+    }
+
+    /// Sets the value, making it present even if zero.
+    pub fn set(&mut self, value: S::Wraps<'a>) {
+        self.value().set_by_wrapped(value);
+        self.flag().set(true);
+    }
+
+    /// Removes and returns the value, if present.
+    pub fn take(&mut self) -> Option<S::Wraps<'a>> {
+        let value = self.get();
+        self.clear();
+        value
+    }
+}
+
+impl<S: Erase> StorageOption<S> {
+    /// Makes the value absent, erasing it.
+    pub fn clear(&mut self) {
+        self.value().erase();
+        self.flag().set(false);
+    }
+}
+
+impl<S: Erase> Erase for StorageOption<S> {
+    fn erase(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{StorageU256, StorageU64, StorageU8},
+        test_host,
+    };
+    use alloy_primitives::{B256, U64};
+
+    #[test]
+    fn test_option() {
+        let root = U256::from(0x6868);
+        let mut small = unsafe { StorageOption::<StorageU64>::new(root, 32 - 9) };
+        assert_eq!(small.get(), None);
+
+        // zero is present, unlike an unset value
+        small.set(U64::ZERO);
+        assert_eq!(small.get(), Some(U64::ZERO));
+        assert!(small.is_some());
+        small.set(U64::from(5));
+        assert_eq!(small.take(), Some(U64::from(5)));
+        assert!(small.is_none());
+
+        // the flag sits above the value
+        small.set(U64::from(0x0102));
+        let mut word = B256::ZERO;
+        word[23] = 1;
+        word[30..].copy_from_slice(&[1, 2]);
+        assert_eq!(test_host::storage(root), word);
+        small.clear();
+        assert_eq!(test_host::storage(root), B256::ZERO);
+
+        // values filling a word keep the flag in the next slot
+        let mut large = unsafe { StorageOption::<StorageU256>::new(root + U256::from(1), 0) };
+        large.set(U256::MAX);
+        assert_eq!(large.get(), Some(U256::MAX));
+        assert_eq!(test_host::storage(root + U256::from(2))[31], 1);
+    }
+
+    #[test]
+    fn test_option_layout() {
+        assert_eq!(StorageOption::<StorageU8>::SLOT_BYTES, 2);
+        assert_eq!(StorageOption::<StorageU8>::REQUIRED_SLOTS, 0);
+        assert_eq!(StorageOption::<StorageU256>::SLOT_BYTES, 32);
+        assert_eq!(StorageOption::<StorageU256>::REQUIRED_SLOTS, 2);
+    }
+}
@@ -42,6 +42,56 @@ pub trait StorageType: Sized {
     /// For complex types requiring more than one inline word, set this to the total size.
     const REQUIRED_SLOTS: usize = 0;
 
+    /// Whether the type keeps any of its data at fixed slots, like namespaced
+    /// [`#[storage]`](macro@stylus_proc::storage) structs and their `#[slot]` fields, rather than
+    /// relative to where it's placed.
+    /// Every instance of such a type shares those slots, so collections of it fail to compile, as
+    /// do structs with two fields of it.
+    ///
+    /// ```compile_fail,E0080
+    /// use stylus_sdk::{alloy_primitives::U256, prelude::*, storage::*};
+    ///
+    /// #[storage]
+    /// pub struct Admin {
+    ///     #[slot(U256::from_limbs([1, 0, 0, 0]))]
+    ///     admin: StorageAddress,
+    /// }
+    ///
+    /// // every element would be at slot 1
+    /// let admins = unsafe { StorageVec::<Admin>::new(U256::ZERO, 0) };
+    /// ```
+    ///
+    /// ```compile_fail,E0080
+    /// # use stylus_sdk::{alloy_primitives::U256, prelude::*, storage::*};
+    /// #
+    /// # #[storage]
+    /// # pub struct Admin {
+    /// #     #[slot(U256::from_limbs([1, 0, 0, 0]))]
+    /// #     admin: StorageAddress,
+    /// # }
+    /// #
+    /// #[storage]
+    /// pub struct Roles {
+    ///     owner: Admin,
+    ///     operator: Admin, // also at slot 1
+    /// }
+    ///
+    /// let roles = unsafe { Roles::new(U256::ZERO, 0) };
+    /// ```
+    ///
+    /// ```compile_fail,E0080
+    /// use stylus_sdk::{alloy_primitives::U256, prelude::*, storage::*};
+    ///
+    /// #[storage(namespace = "example.main")]
+    /// pub struct Main {
+    ///     x: StorageU256,
+    /// }
+    ///
+    /// // every value would be at the namespace's slot
+    /// let mains = unsafe { StorageMap::<U256, Main>::new(U256::ZERO, 0) };
+    /// ```
+    const FIXED_SLOTS: bool = false;
+
     /// Where in persistent storage the type should live. Although useful for framework designers
     /// creating new storage types, most user programs shouldn't call this.
     /// Note: implementations will have to be `const` once [`generic_const_exprs`] stabilizes.
@@ -73,6 +123,22 @@ pub trait Erase: StorageType {
     fn erase(&mut self);
 }
 
+/// Trait for accessors that can be reset to their zero value, as far as storage allows.
+///
+/// Unlike [`Erase`], this can be implemented by structs containing maps, whose entries are left in
+/// place since they can't be enumerated. Every [`Erase`] type resets by erasing itself, and
+/// `#[derive(StorageReset)]` implements it for structs, recursing into their fields.
+pub trait StorageReset {
+    /// Erases everything but maps, including in nested structs.
+    fn reset(&mut self);
+}
+
+impl<T: Erase> StorageReset for T {
+    fn reset(&mut self) {
+        self.erase();
+    }
+}
+
 /// Trait for simple accessors that store no more than their wrapped value.
 /// The type's representation must be entirely inline, or storage leaks become possible.
 /// Note: it is a logic error if erasure does anything more than writing the zero-value.
@@ -300,14 +366,13 @@ pub trait GlobalStorage {
         debug_assert!(B / 8 + offset <= 32);
 
         if B == 256 {
-            return Self::set_word(key, FixedBytes::from_slice(&value.to_be_bytes::<32>()));
+            let mut word = B256::ZERO;
+            value.copy_be_bytes_to(word.as_mut_slice());
+            return Self::set_word(key, word);
         }
 
         let mut word = Self::get_word(key);
-
-        let value = value.to_be_bytes_vec();
-        let dest = word[offset..].as_mut_ptr();
-        ptr::copy(value.as_ptr(), dest, B / 8);
+        value.copy_be_bytes_to(&mut word[offset..offset + B / 8]);
         Self::set_word(key, word);
     }
 
@@ -0,0 +1,221 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{GlobalStorage, Storage, StorageType, TopLevelStorage};
+use crate::hostio;
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U256};
+use core::ops::{Deref, DerefMut};
+
+/// A scope within which storage writes are tentative until [committed](Self::commit).
+///
+/// While a transaction is open, every write records the slot's prior value in a journal.
+/// [`rollback`](Self::rollback) replays the journal backwards, restoring each slot to what it was
+/// when the transaction began. Dropping a transaction without committing it rolls it back too.
+///
+/// The transaction mutably borrows the contract's [`TopLevelStorage`] for its lifetime, and
+/// derefs to it, so that accessors created during the scope can't outlive a rollback. On
+/// rollback, the storage is recreated at slot zero to discard any values its fields had cached,
+/// which is where [`#[entrypoint]`][entrypoint] places it.
+///
+/// ```ignore
+/// let mut tx = StorageTransaction::begin(self);
+/// tx.balances.setter(from).set(from_balance - amount);
+/// if tx.try_settle(amount).is_err() {
+///     tx.rollback();
+///     return Ok(false);
+/// }
+/// tx.commit();
+/// ```
+///
+/// Transactions may be nested by beginning one on another. Committing an inner transaction makes
+/// its writes part of the outer one, which may still roll them back.
+///
+/// Transactions are only available with the `storage-transaction` feature. Without it, storage
+/// writes don't check for an open transaction at all, so contracts that don't use them pay
+/// nothing. With it, writes made while a transaction is open record prior values at the cost of a
+/// storage read each, so transactions are best kept to the writes that actually need to be
+/// undone. Note too that a rollback only undoes storage writes.
+/// Events emitted and calls made within the scope still happen.
+///
+/// [entrypoint]: macro@stylus_proc::entrypoint
+pub struct StorageTransaction<'a, S: TopLevelStorage + StorageType> {
+    storage: &'a mut S,
+    start: usize,
+    done: bool,
+}
+
+impl<'a, S: TopLevelStorage + StorageType> StorageTransaction<'a, S> {
+    /// Begins a transaction, journaling all storage writes until it ends.
+    pub fn begin(storage: &'a mut S) -> Self {
+        let start = with_journal(|journal| {
+            journal.depth += 1;
+            journal.entries.len()
+        });
+        Self {
+            storage,
+            start,
+            done: false,
+        }
+    }
+
+    /// Keeps the writes made during the transaction.
+    pub fn commit(mut self) {
+        self.done = true;
+        with_journal(|journal| {
+            journal.depth -= 1;
+            if journal.depth == 0 {
+                journal.entries.clear();
+            }
+        });
+    }
+
+    /// Restores every slot written during the transaction to its prior value.
+    pub fn rollback(mut self) {
+        self.undo();
+    }
+
+    fn undo(&mut self) {
+        self.done = true;
+        with_journal(|journal| {
+            for (key, value) in journal.entries.drain(self.start..).rev() {
+                unsafe { hostio::storage_cache_bytes32(B256::from(key).as_ptr(), value.as_ptr()) };
+            }
+            journal.depth -= 1;
+        });
+        *self.storage = unsafe { S::new(U256::ZERO, 0) };
+    }
+}
+
+impl<'a, S: TopLevelStorage + StorageType> Drop for StorageTransaction<'a, S> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.undo();
+        }
+    }
+}
+
+impl<'a, S: TopLevelStorage + StorageType> Deref for StorageTransaction<'a, S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        self.storage
+    }
+}
+
+impl<'a, S: TopLevelStorage + StorageType> DerefMut for StorageTransaction<'a, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.storage
+    }
+}
+
+/// Prior values of the slots written since the outermost open transaction began.
+struct Journal {
+    depth: usize,
+    entries: Vec<(U256, B256)>,
+}
+
+impl Journal {
+    const fn new() -> Self {
+        Self {
+            depth: 0,
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// Records the current value of a slot that's about to be written, if a transaction is open.
+pub(super) fn record_write(key: U256) {
+    with_journal(|journal| {
+        if journal.depth > 0 {
+            journal.entries.push((key, Storage::get_word(key)));
+        }
+    })
+}
+
+#[cfg(not(any(test, feature = "stylus-test")))]
+fn with_journal<R>(f: impl FnOnce(&mut Journal) -> R) -> R {
+    static mut JOURNAL: Journal = Journal::new();
+    f(unsafe { &mut *core::ptr::addr_of_mut!(JOURNAL) })
+}
+
+/// Tests run in parallel, so each gets its own journal.
+#[cfg(any(test, feature = "stylus-test"))]
+fn with_journal<R>(f: impl FnOnce(&mut Journal) -> R) -> R {
+    std::thread_local! {
+        static JOURNAL: core::cell::RefCell<Journal> = const { core::cell::RefCell::new(Journal::new()) };
+    }
+    JOURNAL.with(|journal| f(&mut journal.borrow_mut()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{StorageMap, StorageU256},
+        stylus_proc::storage,
+    };
+
+    #[storage]
+    struct Ledger {
+        total: StorageU256,
+        balances: StorageMap<U256, StorageU256>,
+    }
+
+    unsafe impl TopLevelStorage for Ledger {}
+
+    fn ledger() -> Ledger {
+        unsafe { Ledger::new(U256::ZERO, 0) }
+    }
+
+    #[test]
+    fn test_commit_and_rollback() {
+        let mut ledger = ledger();
+        ledger.total.set(U256::from(10));
+
+        let mut tx = StorageTransaction::begin(&mut ledger);
+        tx.total.set(U256::from(20));
+        tx.balances.setter(U256::from(1)).set(U256::from(5));
+        tx.commit();
+        assert_eq!(ledger.total.get(), U256::from(20));
+
+        let mut tx = StorageTransaction::begin(&mut ledger);
+        tx.total.set(U256::from(30));
+        tx.total.set(U256::from(40));
+        tx.balances.setter(U256::from(1)).set(U256::from(6));
+        tx.balances.setter(U256::from(2)).set(U256::from(7));
+        tx.rollback();
+
+        // both the slots and the cached values are restored
+        assert_eq!(ledger.total.get(), U256::from(20));
+        assert_eq!(ledger.balances.get(U256::from(1)), U256::from(5));
+        assert_eq!(ledger.balances.get(U256::from(2)), U256::ZERO);
+        assert_eq!(
+            crate::test_host::storage(U256::ZERO),
+            B256::from(U256::from(20))
+        );
+    }
+
+    #[test]
+    fn test_nested_and_dropped() {
+        let mut ledger = ledger();
+
+        let mut outer = StorageTransaction::begin(&mut ledger);
+        outer.total.set(U256::from(1));
+        let mut inner = StorageTransaction::begin(&mut *outer);
+        inner.total.set(U256::from(2));
+        inner.commit();
+        assert_eq!(outer.total.get(), U256::from(2));
+
+        {
+            let mut dropped = StorageTransaction::begin(&mut *outer);
+            dropped.total.set(U256::from(3));
+        }
+        assert_eq!(outer.total.get(), U256::from(2));
+
+        // rolling back the outer transaction undoes the inner one's committed writes
+        outer.rollback();
+        assert_eq!(ledger.total.get(), U256::ZERO);
+        with_journal(|journal| assert_eq!((journal.depth, journal.entries.len()), (0, 0)));
+    }
+}
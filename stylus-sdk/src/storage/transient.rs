@@ -0,0 +1,289 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+use super::{Erase, SimpleStorageType, StorageGuard, StorageGuardMut, StorageKey, StorageType};
+use crate::hostio;
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U256};
+use alloy_sol_types::{sol, SolError};
+use core::marker::PhantomData;
+
+#[cfg(feature = "persistent-reentrancy-guard")]
+use super::{GlobalStorage, Storage};
+
+/// Reads a word of transient storage.
+fn load(slot: U256) -> U256 {
+    let key = B256::from(slot);
+    let mut value = B256::ZERO;
+    unsafe { hostio::transient_load_bytes32(key.as_ptr(), value.as_mut_ptr()) };
+    value.into()
+}
+
+/// Writes a word of transient storage.
+fn store(slot: U256, value: U256) {
+    let (key, value) = (B256::from(slot), B256::from(value));
+    unsafe { hostio::transient_store_bytes32(key.as_ptr(), value.as_ptr()) };
+}
+
+/// Accessor for a [`U256`] in transient storage, which the EVM discards at the end of every
+/// transaction. See [EIP-1153] for more information.
+///
+/// Transient values don't persist across transactions, but are shared by every call within one,
+/// which makes them suited to reentrancy locks and values accumulated over a transaction.
+/// Transient storage is a separate address space, so the type occupies a slot in the layout of a
+/// [`#[storage]`][storage] struct without touching it. Reads aren't cached, so they always see the
+/// latest value, even across reentrant calls.
+///
+/// [EIP-1153]: https://eips.ethereum.org/EIPS/eip-1153
+/// [storage]: crate::prelude::storage
+#[derive(Debug)]
+pub struct TransientU256 {
+    slot: U256,
+}
+
+impl TransientU256 {
+    /// Gets the value in transient storage, which is zero if unset during this transaction.
+    pub fn get(&self) -> U256 {
+        load(self.slot)
+    }
+
+    /// Sets the value in transient storage until the end of the transaction.
+    pub fn set(&mut self, value: U256) {
+        store(self.slot, value);
+    }
+}
+
+impl StorageType for TransientU256 {
+    type Wraps<'a> = U256;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self>;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(offset == 0);
+        Self { slot }
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        self.get()
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+impl<'a> SimpleStorageType<'a> for TransientU256 {
+    fn set_by_wrapped(&mut self, value: Self::Wraps<'a>) {
+        self.set(value);
+    }
+}
+
+impl From<TransientU256> for U256 {
+    fn from(value: TransientU256) -> Self {
+        value.get()
+    }
+}
+
+impl Erase for TransientU256 {
+    fn erase(&mut self) {
+        self.set(U256::ZERO);
+    }
+}
+
+/// Accessor for a map of [`U256`] values in transient storage, which the EVM discards at the end
+/// of every transaction. Keys are assigned slots as in a [`StorageMap`](super::StorageMap).
+///
+/// Like [`TransientU256`], the map takes up a slot in the layout of a [`#[storage]`][storage]
+/// struct, but lives in transient storage.
+///
+/// [storage]: crate::prelude::storage
+#[derive(Debug)]
+pub struct TransientMap<K: StorageKey> {
+    slot: U256,
+    marker: PhantomData<K>,
+}
+
+impl<K: StorageKey> TransientMap<K> {
+    /// Gets the value at the given key, which is zero if unset during this transaction.
+    pub fn get(&self, key: K) -> U256 {
+        load(key.to_slot(self.slot.into()))
+    }
+
+    /// Sets the value at the given key until the end of the transaction.
+    pub fn set(&mut self, key: K, value: U256) {
+        store(key.to_slot(self.slot.into()), value);
+    }
+
+    /// Sets the value at the given key to zero.
+    pub fn delete(&mut self, key: K) {
+        self.set(key, U256::ZERO);
+    }
+}
+
+impl<K: StorageKey> StorageType for TransientMap<K> {
+    type Wraps<'a> = StorageGuard<'a, Self> where Self: 'a;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self> where Self: 'a;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(offset == 0);
+        Self {
+            slot,
+            marker: PhantomData,
+        }
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+sol! {
+    /// A [`TransientReentrancyGuard`] was entered while already held.
+    #[allow(missing_docs)]
+    error ReentrancyGuardReentrantCall();
+}
+
+impl From<ReentrancyGuardReentrantCall> for Vec<u8> {
+    fn from(err: ReentrancyGuardReentrantCall) -> Self {
+        err.abi_encode()
+    }
+}
+
+/// A reentrancy guard kept in transient storage, which makes taking and releasing it much cheaper
+/// than the classic guard's writes to a persistent slot.
+///
+/// [`enter`](Self::enter) fails while the guard is held, and returns a [`ReentrancyLock`] that
+/// releases the guard when dropped. Since the lock doesn't borrow the guard, the rest of the method
+/// is free to use storage.
+///
+/// ```ignore
+/// #[public]
+/// impl Vault {
+///     pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+///         let _lock = self.guard.enter()?;
+///         ...
+///     }
+/// }
+/// ```
+///
+/// The guard only blocks calls that reenter the contract, so the `reentrant` feature must be enabled
+/// for them to get this far. On chains without transient storage, the `persistent-reentrancy-guard`
+/// feature keeps the guard in persistent storage instead.
+#[derive(Debug)]
+pub struct TransientReentrancyGuard {
+    slot: U256,
+}
+
+impl TransientReentrancyGuard {
+    /// Whether the guard is currently held.
+    pub fn entered(&self) -> bool {
+        !read_lock(self.slot).is_zero()
+    }
+
+    /// Takes the guard until the returned lock is dropped, failing with
+    /// [`ReentrancyGuardReentrantCall`] if it's already held.
+    pub fn enter(&mut self) -> Result<ReentrancyLock, ReentrancyGuardReentrantCall> {
+        if self.entered() {
+            return Err(ReentrancyGuardReentrantCall {});
+        }
+        write_lock(self.slot, U256::from(1));
+        Ok(ReentrancyLock { slot: self.slot })
+    }
+}
+
+impl StorageType for TransientReentrancyGuard {
+    type Wraps<'a> = StorageGuard<'a, Self>;
+    type WrapsMut<'a> = StorageGuardMut<'a, Self>;
+
+    unsafe fn new(slot: U256, offset: u8) -> Self {
+        debug_assert!(offset == 0);
+        Self { slot }
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+/// Holds a [`TransientReentrancyGuard`], releasing it when dropped.
+#[must_use = "the guard is released as soon as the lock is dropped"]
+#[derive(Debug)]
+pub struct ReentrancyLock {
+    slot: U256,
+}
+
+impl Drop for ReentrancyLock {
+    fn drop(&mut self) {
+        write_lock(self.slot, U256::ZERO);
+    }
+}
+
+#[cfg(not(feature = "persistent-reentrancy-guard"))]
+use {load as read_lock, store as write_lock};
+
+#[cfg(feature = "persistent-reentrancy-guard")]
+fn read_lock(slot: U256) -> U256 {
+    Storage::get_word(slot).into()
+}
+
+#[cfg(feature = "persistent-reentrancy-guard")]
+fn write_lock(slot: U256, value: U256) {
+    unsafe { Storage::set_word(slot, value.into()) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_host;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn test_transient() {
+        let slot = U256::from(0x6666);
+        let mut value = unsafe { TransientU256::new(slot, 0) };
+        let mut map = unsafe { TransientMap::<Address>::new(slot + U256::from(1), 0) };
+        let owner = Address::repeat_byte(1);
+
+        value.set(U256::from(7));
+        map.set(owner, U256::from(8));
+        assert_eq!(value.get(), U256::from(7));
+        assert_eq!(map.get(owner), U256::from(8));
+        assert_eq!(map.get(Address::ZERO), U256::ZERO);
+
+        // persistent storage is untouched
+        assert_eq!(test_host::storage(slot), B256::ZERO);
+        assert_eq!(test_host::transient(slot), B256::from(U256::from(7)));
+
+        map.delete(owner);
+        assert_eq!(map.get(owner), U256::ZERO);
+
+        // values don't outlive the transaction
+        test_host::end_transaction();
+        assert_eq!(value.get(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_reentrancy_guard() {
+        let mut guard = unsafe { TransientReentrancyGuard::new(U256::from(0x6767), 0) };
+        let Ok(lock) = guard.enter() else {
+            panic!("failed to enter guard");
+        };
+        assert!(guard.entered());
+        assert!(guard.enter().is_err());
+
+        let err: Vec<u8> = ReentrancyGuardReentrantCall {}.into();
+        assert_eq!(err, ReentrancyGuardReentrantCall::SELECTOR);
+
+        drop(lock);
+        assert!(!guard.entered());
+        let lock = guard.enter();
+        assert!(lock.is_ok() && guard.entered());
+    }
+}
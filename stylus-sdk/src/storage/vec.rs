@@ -21,6 +21,7 @@ impl<S: StorageType> StorageType for StorageVec<S> {
 
     unsafe fn new(slot: U256, offset: u8) -> Self {
         debug_assert!(offset == 0);
+        let () = Self::NOT_FIXED;
         Self {
             slot,
             base: OnceCell::new(),
@@ -38,6 +39,12 @@ impl<S: StorageType> StorageType for StorageVec<S> {
 }
 
 impl<S: StorageType> StorageVec<S> {
+    /// Elements live at slots derived from the vector's, so can't have fixed slots of their own.
+    const NOT_FIXED: () = assert!(
+        !S::FIXED_SLOTS,
+        "StorageVec elements can't have fixed slots"
+    );
+
     /// Returns `true` if the collection contains no elements.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -64,7 +71,7 @@ impl<S: StorageType> StorageVec<S> {
     ///
     /// Note: the accessor is protected by a [`StorageGuard`], which restricts
     /// its lifetime to that of `&self`.
-    pub fn getter(&self, index: impl TryInto<usize>) -> Option<StorageGuard<S>> {
+    pub fn getter(&self, index: impl TryInto<usize>) -> Option<StorageGuard<'_, S>> {
         let store = unsafe { self.accessor(index)? };
         Some(StorageGuard::new(store))
     }
@@ -73,7 +80,7 @@ impl<S: StorageType> StorageVec<S> {
     ///
     /// Note: the accessor is protected by a [`StorageGuardMut`], which restricts
     /// its lifetime to that of `&mut self`.
-    pub fn setter(&mut self, index: impl TryInto<usize>) -> Option<StorageGuardMut<S>> {
+    pub fn setter(&mut self, index: impl TryInto<usize>) -> Option<StorageGuardMut<'_, S>> {
         let store = unsafe { self.accessor(index)? };
         Some(StorageGuardMut::new(store))
     }
@@ -133,7 +140,7 @@ impl<S: StorageType> StorageVec<S> {
     /// ```
     ///
     /// [vec_push]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push
-    pub fn grow(&mut self) -> StorageGuardMut<S> {
+    pub fn grow(&mut self) -> StorageGuardMut<'_, S> {
         let index = self.len();
         unsafe { self.set_len(index + 1) };
 
@@ -143,7 +150,7 @@ impl<S: StorageType> StorageVec<S> {
     }
 
     /// Removes and returns an accessor to the last element of the vector, if any.
-    pub fn shrink(&mut self) -> Option<StorageGuardMut<S>> {
+    pub fn shrink(&mut self) -> Option<StorageGuardMut<'_, S>> {
         let index = match self.len() {
             0 => return None,
             x => x - 1,
@@ -156,7 +163,9 @@ impl<S: StorageType> StorageVec<S> {
 
     /// Shortens the vector, keeping the first `len` elements.
     ///
-    /// Note: this method does not erase any underlying storage.
+    /// Note: this method does not erase any underlying storage. That's cheaper than
+    /// [`truncate_erase`][Self::truncate_erase] when many elements are removed, but earns no gas
+    /// refund, and the old values reappear in elements added by [`grow`][Self::grow].
     pub fn truncate(&mut self, len: usize) {
         if len < self.len() {
             // SAFETY: operation leaves only existing values
@@ -212,6 +221,32 @@ impl<'a, S: SimpleStorageType<'a>> StorageVec<S> {
         }
         Some(value)
     }
+
+    /// Removes and returns the element at `index`, replacing it with the last element.
+    /// Returns [`None`] if the index is out of bounds.
+    ///
+    /// Like [`Vec::swap_remove`], this takes constant time but changes the order of the elements.
+    /// Unlike erasing an element through its [`setter`][Self::setter], which leaves a zero value
+    /// in its place, the vector shrinks and the freed storage is cleared as in [`pop`][Self::pop].
+    pub fn swap_remove(&mut self, index: impl TryInto<usize>) -> Option<S::Wraps<'a>> {
+        let index = index.try_into().ok()?;
+        if index >= self.len() {
+            return None;
+        }
+        let last = self.pop()?;
+        if index == self.len() {
+            return Some(last);
+        }
+        let (slot, offset) = self.index_slot(index);
+        // intentionally alias so that we can overwrite after load
+        unsafe {
+            let store = S::new(slot, offset);
+            let mut alias = S::new(slot, offset);
+            let value = store.load();
+            alias.set_by_wrapped(last);
+            Some(value)
+        }
+    }
 }
 
 impl<S: Erase> StorageVec<S> {
@@ -226,15 +261,28 @@ impl<S: Erase> StorageVec<S> {
             self.set_len(index);
         }
     }
+
+    /// Shortens the vector, keeping the first `len` elements and erasing the rest.
+    ///
+    /// Each removed element costs a storage write, so removing many may exceed the gas limit.
+    /// The writes earn refunds for clearing storage, but [`truncate`][Self::truncate] is cheaper
+    /// when the old values can be left in place.
+    pub fn truncate_erase(&mut self, len: usize) {
+        for i in len..self.len() {
+            unsafe { self.accessor_unchecked(i).erase() };
+        }
+        self.truncate(len);
+    }
+
+    /// Erases every element and empties the vector, as with [`truncate_erase`][Self::truncate_erase].
+    pub fn clear(&mut self) {
+        self.truncate_erase(0);
+    }
 }
 
 impl<S: Erase> Erase for StorageVec<S> {
     fn erase(&mut self) {
-        for i in 0..self.len() {
-            let mut store = unsafe { self.accessor_unchecked(i) };
-            store.erase()
-        }
-        self.truncate(0);
+        self.clear();
     }
 }
 
@@ -245,3 +293,87 @@ impl<'a, S: SimpleStorageType<'a>> Extend<S::Wraps<'a>> for StorageVec<S> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{StorageString, StorageU64},
+        stylus_proc::{storage, Erase},
+        test_host,
+    };
+    use alloc::vec::Vec;
+    use alloy_primitives::{B256, U64};
+
+    #[test]
+    fn test_swap_remove() {
+        let mut vec: StorageVec<StorageU64> = unsafe { StorageVec::new(U256::from(0x6363), 0) };
+        vec.extend((1..=5).map(U64::from));
+
+        assert_eq!(vec.swap_remove(1), Some(U64::from(2)));
+        let values: Vec<_> = (0..vec.len()).map(|i| vec.get(i).unwrap()).collect();
+        assert_eq!(values, [1, 5, 3, 4].map(U64::from));
+
+        // removing the last element is a pop
+        assert_eq!(vec.swap_remove(3), Some(U64::from(4)));
+        assert_eq!(vec.swap_remove(3), None);
+        assert_eq!(vec.len(), 3);
+
+        // the freed slot is cleared once emptied
+        let slot = *vec.base();
+        while vec.swap_remove(0).is_some() {}
+        assert!(vec.is_empty());
+        assert_eq!(test_host::storage(slot), B256::ZERO);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut vec: StorageVec<StorageU64> = unsafe { StorageVec::new(U256::from(0x6464), 0) };
+        vec.extend((1..=6).map(U64::from));
+        let base = *vec.base();
+
+        vec.truncate_erase(5);
+        assert_eq!(vec.len(), 5);
+        assert_eq!(vec.get(4), Some(U64::from(5)));
+        assert_ne!(test_host::storage(base + U256::from(1)), B256::ZERO);
+
+        vec.clear();
+        assert!(vec.is_empty());
+        assert_eq!(test_host::storage(U256::from(0x6464)), B256::ZERO);
+        assert_eq!(test_host::storage(base), B256::ZERO);
+        assert_eq!(test_host::storage(base + U256::from(1)), B256::ZERO);
+
+        // unlike truncate, the old values don't reappear
+        assert_eq!(vec.grow().get(), U64::ZERO);
+    }
+
+    #[storage]
+    #[derive(Erase)]
+    struct Dog {
+        name: StorageString,
+        age: StorageU64,
+    }
+
+    #[test]
+    fn test_erase_structs() {
+        let root = U256::from(0x6969);
+        let mut dogs: StorageVec<Dog> = unsafe { StorageVec::new(root, 0) };
+        for (name, age) in [("Rex", 3), ("a name too long to fit in a single word", 5)] {
+            let mut dog = dogs.grow();
+            dog.name.set_str(name);
+            dog.age.set(U64::from(age));
+        }
+        let base = *dogs.base();
+
+        dogs.erase();
+        assert!(dogs.is_empty());
+        assert_eq!(test_host::storage(root), B256::ZERO);
+        for i in 0..4 {
+            assert_eq!(test_host::storage(base + U256::from(i)), B256::ZERO);
+        }
+
+        // long strings clear their contents too
+        let name_slot = crate::crypto::keccak(B256::from(base + U256::from(2)));
+        assert_eq!(test_host::storage(name_slot.into()), B256::ZERO);
+    }
+}
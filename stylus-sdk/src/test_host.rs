@@ -0,0 +1,1050 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! A minimal, in-memory stand-in for the Stylus VM, for unit testing contracts natively.
+//!
+//! Native test binaries can't link against the `vm_hooks` wasm imports, so with the `stylus-test`
+//! feature, and in the SDK's own tests, the [`hostio`](crate::hostio) declarations forward here
+//! instead. Contracts can then be instantiated and called directly, with the functions in this
+//! module setting up the environment and inspecting what the contract did.
+//!
+//! ```no_run
+//! use stylus_sdk::{alloy_primitives::Address, msg, test_host};
+//!
+//! test_host::set_sender(Address::repeat_byte(0xa1));
+//! assert_eq!(msg::sender(), Address::repeat_byte(0xa1));
+//! ```
+//!
+//! Storage, transient storage, logging, console output, hashing, balances, account code, and mocked calls and
+//! deployments are modeled. Transaction values are fixed, as if on Arbitrum One, and the block
+//! only changes when moved with [`roll`], [`warp`], or [`advance_block`]. State is thread-local,
+//! and since each test runs on its own thread, tests don't observe one another's writes.
+//!
+//! Calls and deployments must be mocked with [`mock_call`] and [`mock_deploy`], or they panic.
+
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+use alloy_sol_types::SolEvent;
+use sha3::{Digest, Keccak256};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    format, ptr, slice,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[derive(Default)]
+struct State {
+    storage: HashMap<B256, B256>,
+    transient: HashMap<B256, B256>,
+    cached: HashSet<B256>,
+    stats: StorageAccessStats,
+    logs: Vec<(Vec<B256>, Vec<u8>)>,
+    output: Vec<u8>,
+    console: Vec<String>,
+    sender: Address,
+    origin: Option<Address>,
+    value: U256,
+    calldata: Vec<u8>,
+    balances: HashMap<Address, U256>,
+    code: HashMap<Address, Vec<u8>>,
+    deploy_result: Option<Result<Address, Vec<u8>>>,
+    deployments: Vec<(Vec<u8>, Option<B256>)>,
+    return_data: Vec<u8>,
+    gas_left: Option<u64>,
+    call_gas: u64,
+    call_results: HashMap<Address, CallResult>,
+    calls: Vec<CallRecord>,
+    expectations: Vec<Expectation>,
+    steps_left: Option<u64>,
+    block_number: Option<u64>,
+    timestamp: Option<u64>,
+    block_time: Option<u64>,
+}
+
+/// The block tests start at.
+const BLOCK_NUMBER: u64 = 200_000_000;
+
+/// A time in the past tests start at, so that deadlines can be tested relative to it.
+const TIMESTAMP: u64 = 1_700_000_000;
+
+/// A call made by the contract.
+#[derive(Clone)]
+struct CallRecord {
+    contract: Address,
+    data: Vec<u8>,
+    value: U256,
+    gas: u64,
+}
+
+/// Calls a test expects, set by [`expect_call`].
+struct Expectation {
+    contract: Address,
+    data: Vec<u8>,
+    value: U256,
+    count: Option<usize>,
+    /// The number of calls made before the expectation was set, which don't count toward it.
+    since: usize,
+}
+
+impl Expectation {
+    fn matches(&self, call: &CallRecord) -> bool {
+        call.contract == self.contract
+            && call.value == self.value
+            && call.data.starts_with(&self.data)
+    }
+}
+
+/// The mocked outcome of calls to a contract.
+#[derive(Clone)]
+enum CallResult {
+    Return(Result<Vec<u8>, Vec<u8>>),
+    /// Outcomes for successive calls, the last of which repeats.
+    Sequence(Vec<Result<Vec<u8>, Vec<u8>>>),
+    OutOfGas,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::default());
+}
+
+/// Reads a storage slot directly, bypassing the SDK's caches.
+pub fn storage(key: U256) -> B256 {
+    STATE.with(|state| {
+        let state = state.borrow();
+        state
+            .storage
+            .get(&B256::from(key))
+            .copied()
+            .unwrap_or_default()
+    })
+}
+
+/// Gets every nonzero storage slot and its value, ordered by slot. Useful for checking the contents
+/// of maps, whose slots depend on keys a test may not know.
+pub fn storage_dump() -> Vec<(B256, B256)> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let mut slots: Vec<_> = state
+            .storage
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(slot, value)| (*slot, *value))
+            .collect();
+        slots.sort();
+        slots
+    })
+}
+
+/// Reads a transient storage slot.
+pub fn transient(key: U256) -> B256 {
+    STATE.with(|state| {
+        let state = state.borrow();
+        state
+            .transient
+            .get(&B256::from(key))
+            .copied()
+            .unwrap_or_default()
+    })
+}
+
+/// Ends the current transaction, discarding transient storage and the storage cache as the VM
+/// would.
+pub fn end_transaction() {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.transient.clear();
+        state.cached.clear();
+    });
+}
+
+/// Counts of the storage accesses a contract makes through hostios, from
+/// [`storage_access_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StorageAccessStats {
+    /// Slots loaded.
+    pub reads: u64,
+    /// Slots written to the VM's storage cache.
+    pub writes: u64,
+    /// Loads of slots already in the VM's cache, having been loaded or written since it was last
+    /// cleared. These are the cheap, warm reads.
+    pub cache_hits: u64,
+    /// Loads of slots not in the VM's cache, which go to the state trie.
+    pub cache_misses: u64,
+}
+
+/// Gets the storage accesses made since the test began, or since the last
+/// [`reset_storage_access_stats`].
+///
+/// These count what reaches the VM, so values the SDK's storage types cache themselves don't
+/// show up. This lets a test assert that an optimized method loads a slot once rather than three
+/// times, without a full gas model.
+pub fn storage_access_stats() -> StorageAccessStats {
+    STATE.with(|state| state.borrow().stats)
+}
+
+/// Zeroes the counts [`storage_access_stats`] returns, such as before calling the method being
+/// measured.
+pub fn reset_storage_access_stats() {
+    STATE.with(|state| state.borrow_mut().stats = StorageAccessStats::default());
+}
+
+/// Gets each message printed to the console so far, whether with [`console!`](crate::console!)
+/// or the leveled macros like [`info!`](crate::info!). Without the `debug` feature, these print
+/// nothing.
+#[cfg(feature = "debug")]
+pub fn console() -> Vec<String> {
+    STATE.with(|state| state.borrow().console.clone())
+}
+
+/// Sets the address [`msg::sender`](crate::msg::sender) returns.
+pub fn set_sender(sender: Address) {
+    STATE.with(|state| state.borrow_mut().sender = sender);
+}
+
+/// Sets the wei [`msg::value`](crate::msg::value) returns. Until set, it's zero.
+pub fn set_value(value: U256) {
+    STATE.with(|state| state.borrow_mut().value = value);
+}
+
+/// Sets the address [`tx::origin`](crate::tx::origin) returns. Until set, it's the sender, as if
+/// the sender were an externally owned account.
+pub fn set_origin(origin: Address) {
+    STATE.with(|state| state.borrow_mut().origin = Some(origin));
+}
+
+/// Runs `f` as `sender`, restoring the previous sender afterward, like Foundry's `vm.prank`.
+pub fn prank<R>(sender: Address, f: impl FnOnce() -> R) -> R {
+    let _prank = start_prank(sender);
+    f()
+}
+
+/// Makes `sender` the sender until the returned guard is dropped or passed to [`stop_prank`],
+/// when the previous sender and origin are restored.
+///
+/// Unlike [`set_sender`], which lasts until changed, this scopes the impersonation, so later
+/// calls in a test can't accidentally run as the wrong account.
+#[must_use = "the prank ends when the guard is dropped"]
+pub fn start_prank(sender: Address) -> Prank {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let prank = Prank {
+            sender: state.sender,
+            origin: state.origin,
+        };
+        state.sender = sender;
+        prank
+    })
+}
+
+/// Ends a prank started with [`start_prank`].
+pub fn stop_prank(prank: Prank) {
+    drop(prank);
+}
+
+/// A scope in which calls are made as another sender, started by [`start_prank`].
+pub struct Prank {
+    sender: Address,
+    origin: Option<Address>,
+}
+
+impl Prank {
+    /// Also sets the address [`tx::origin`](crate::tx::origin) returns for the prank's duration.
+    pub fn with_origin(self, origin: Address) -> Self {
+        set_origin(origin);
+        self
+    }
+}
+
+impl Drop for Prank {
+    fn drop(&mut self) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.sender = self.sender;
+            state.origin = self.origin;
+        });
+    }
+}
+
+/// Sets the calldata [`read_args`](crate::hostio::read_args) reads, and that
+/// [`msg::sig`](crate::msg::sig) takes its selector from.
+pub fn set_calldata(calldata: &[u8]) {
+    STATE.with(|state| state.borrow_mut().calldata = calldata.to_vec());
+}
+
+/// The selector [`msg::sig`](crate::msg::sig) returns, from the calldata.
+pub(crate) fn sig() -> FixedBytes<4> {
+    STATE.with(|state| {
+        let mut sig = FixedBytes::ZERO;
+        let calldata = &state.borrow().calldata;
+        let prefix = &calldata[..calldata.len().min(4)];
+        sig[..prefix.len()].copy_from_slice(prefix);
+        sig
+    })
+}
+
+/// Sets the balance of the given account, in wei.
+pub fn set_balance(address: Address, balance: U256) {
+    STATE.with(|state| state.borrow_mut().balances.insert(address, balance));
+}
+
+/// Installs code at the given address, as if a contract were deployed there.
+pub fn set_code(address: Address, code: &[u8]) {
+    STATE.with(|state| state.borrow_mut().code.insert(address, code.to_vec()));
+}
+
+/// Sets the gas left, which calls deduct from. Until set, the gas left is unlimited.
+pub fn set_gas_left(gas: u64) {
+    STATE.with(|state| state.borrow_mut().gas_left = Some(gas));
+}
+
+/// Sets the gas each successful or reverting call uses, up to the gas it's given. Defaults to `0`.
+pub fn set_call_gas(gas: u64) {
+    STATE.with(|state| state.borrow_mut().call_gas = gas);
+}
+
+/// Sets the block number, like Foundry's `vm.roll`. Tests start at block `200_000_000`.
+pub fn roll(number: u64) {
+    STATE.with(|state| state.borrow_mut().block_number = Some(number));
+}
+
+/// Sets the block timestamp, like Foundry's `vm.warp`. Tests start at `1_700_000_000`, a time in
+/// the past, so that deadlines can be tested relative to it.
+pub fn warp(timestamp: u64) {
+    STATE.with(|state| state.borrow_mut().timestamp = Some(timestamp));
+}
+
+/// Sets the seconds between blocks that [`advance_block`] assumes. Defaults to `1`.
+pub fn set_block_time(seconds: u64) {
+    STATE.with(|state| state.borrow_mut().block_time = Some(seconds));
+}
+
+/// Moves `blocks` blocks ahead, advancing the timestamp by the time they'd take.
+pub fn advance_block(blocks: u64) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let number = state.block_number.unwrap_or(BLOCK_NUMBER);
+        let timestamp = state.timestamp.unwrap_or(TIMESTAMP);
+        let elapsed = blocks * state.block_time.unwrap_or(1);
+        state.block_number = Some(number + blocks);
+        state.timestamp = Some(timestamp + elapsed);
+    });
+}
+
+/// Limits the number of hostio calls made from now on, after which the next one panics as if the
+/// contract ran out of gas. Until set, the number is unlimited.
+///
+/// This lets tests assert that a method is bounded, such as a loop over user-controlled data. It's
+/// only an approximation of gas: every hostio costs a step, however expensive, and computation
+/// between them is free.
+pub fn set_step_budget(steps: u64) {
+    STATE.with(|state| state.borrow_mut().steps_left = Some(steps));
+}
+
+/// Spends a step of the budget set by [`set_step_budget`], if any.
+pub fn step() {
+    STATE.with(|state| {
+        if let Some(steps) = &mut state.borrow_mut().steps_left {
+            match steps.checked_sub(1) {
+                Some(left) => *steps = left,
+                None => panic!("out of gas: exceeded the step budget"),
+            }
+        }
+    });
+}
+
+/// Sets the outcome of subsequent calls to `contract`: the data it returns, or the data it
+/// reverts with. Calls to contracts without a mocked outcome panic.
+pub fn mock_call(contract: Address, result: Result<Vec<u8>, Vec<u8>>) {
+    let result = CallResult::Return(result);
+    STATE.with(|state| state.borrow_mut().call_results.insert(contract, result));
+}
+
+/// Sets the outcomes of successive calls to `contract`, as with [`mock_call`]. Once each has been
+/// used, the last outcome repeats.
+pub fn mock_call_sequence(
+    contract: Address,
+    results: impl IntoIterator<Item = Result<Vec<u8>, Vec<u8>>>,
+) {
+    let results: Vec<_> = results.into_iter().collect();
+    assert!(
+        !results.is_empty(),
+        "a call sequence needs at least one outcome"
+    );
+    let result = CallResult::Sequence(results);
+    STATE.with(|state| state.borrow_mut().call_results.insert(contract, result));
+}
+
+/// Makes subsequent calls to `contract` run out of gas, using all the gas they're given and
+/// failing without return data.
+pub fn mock_call_out_of_gas(contract: Address) {
+    let result = CallResult::OutOfGas;
+    STATE.with(|state| state.borrow_mut().call_results.insert(contract, result));
+}
+
+/// Gets the contract, calldata, and gas given of each call made so far.
+pub fn calls() -> Vec<(Address, Vec<u8>, u64)> {
+    STATE.with(|state| {
+        let calls = state.borrow().calls.clone();
+        let calls = calls.into_iter();
+        calls
+            .map(|call| (call.contract, call.data, call.gas))
+            .collect()
+    })
+}
+
+/// Expects a call to `contract` sending `value` wei, with calldata that starts with `data`, to be
+/// made from now on, like Foundry's `vm.expectCall`. Passing just a selector as `data` matches any
+/// arguments. Static and delegate calls send no value.
+///
+/// Expectations are checked by [`verify_expectations`].
+pub fn expect_call(contract: Address, data: &[u8], value: U256) {
+    expect(contract, data, value, None);
+}
+
+/// Expects exactly `count` calls matching `contract`, `data`, and `value`, as with
+/// [`expect_call`]. A `count` of `0` expects no such call.
+pub fn expect_call_count(contract: Address, data: &[u8], value: U256, count: usize) {
+    expect(contract, data, value, Some(count));
+}
+
+fn expect(contract: Address, data: &[u8], value: U256, count: Option<usize>) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let since = state.calls.len();
+        state.expectations.push(Expectation {
+            contract,
+            data: data.to_vec(),
+            value,
+            count,
+            since,
+        });
+    });
+}
+
+/// Checks that the calls expected with [`expect_call`] and [`expect_call_count`] were made,
+/// panicking with each unmet expectation if not. The expectations are cleared either way.
+pub fn verify_expectations() {
+    let unmet: Vec<_> = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let expectations = core::mem::take(&mut state.expectations);
+        expectations
+            .into_iter()
+            .filter_map(|expected| {
+                let calls = &state.calls[expected.since..];
+                let made = calls.iter().filter(|call| expected.matches(call)).count();
+                let met = match expected.count {
+                    Some(count) => made == count,
+                    None => made > 0,
+                };
+                let count = match expected.count {
+                    Some(count) => count.to_string(),
+                    None => "at least 1".into(),
+                };
+                (!met).then(|| {
+                    format!(
+                        "call to {} with value {} and data 0x{}: expected {count}, made {made}",
+                        expected.contract,
+                        expected.value,
+                        hex::encode(&expected.data),
+                    )
+                })
+            })
+            .collect()
+    });
+    if !unmet.is_empty() {
+        panic!("unmet call expectations:\n{}", unmet.join("\n"));
+    }
+}
+
+/// Gets the data last written as the contract's result, such as by an
+/// [`entrypoint`](macro@crate::stylus_proc::entrypoint).
+pub fn output() -> Vec<u8> {
+    STATE.with(|state| state.borrow().output.clone())
+}
+
+/// Gets the topics and data of each log emitted so far.
+pub fn logs() -> Vec<(Vec<B256>, Vec<u8>)> {
+    STATE.with(|state| state.borrow().logs.clone())
+}
+
+/// Decodes the logs emitted so far that are events of type `T`. As on chain, indexed `string`,
+/// `bytes`, and other dynamic fields hold the hashes of their values rather than the values.
+pub fn events<T: SolEvent>() -> Vec<T> {
+    logs()
+        .into_iter()
+        .filter(|(topics, _)| T::ANONYMOUS || topics.first() == Some(&T::SIGNATURE_HASH))
+        .filter_map(|(topics, data)| T::decode_raw_log(topics, &data, true).ok())
+        .collect()
+}
+
+/// Sets the outcome of subsequent deployments: the new contract's address, or revert data as if
+/// the init code reverted. Until set, deployments with a salt land at the address
+/// [`expected_create2_address`] gives for the contract, whose address is zero, and others panic.
+pub fn mock_deploy(result: Result<Address, Vec<u8>>) {
+    STATE.with(|state| state.borrow_mut().deploy_result = Some(result));
+}
+
+/// Computes the address a `CREATE2` deployment lands at, as
+/// [`deploy::create2_address`](crate::deploy::create2_address) does.
+pub fn expected_create2_address(deployer: Address, salt: B256, init_code: &[u8]) -> Address {
+    crate::deploy::create2_address(deployer, salt, init_code)
+}
+
+/// Gets the init code and salt, if any, of each deployment made so far.
+pub fn deployments() -> Vec<(Vec<u8>, Option<B256>)> {
+    STATE.with(|state| state.borrow().deployments.clone())
+}
+
+/// The hostios [`hostio`](crate::hostio) forwards to.
+#[allow(clippy::missing_safety_doc)]
+pub(crate) mod hooks {
+    use super::*;
+    use alloy_primitives::address;
+
+    unsafe fn deploy(
+        code: *const u8,
+        code_len: usize,
+        salt: Option<B256>,
+        contract: *mut u8,
+        revert_data_len: *mut usize,
+    ) {
+        let code = slice::from_raw_parts(code, code_len).to_vec();
+        let result = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.deployments.push((code.clone(), salt));
+            let result = match (state.deploy_result.clone(), salt) {
+                (Some(result), _) => result,
+                (None, Some(salt)) => {
+                    // the init code stands in for the child's code, and fails on collisions
+                    let address = expected_create2_address(Address::ZERO, salt, &code);
+                    match state.code.contains_key(&address) {
+                        true => Err(vec![]),
+                        false => {
+                            state.code.insert(address, code);
+                            Ok(address)
+                        }
+                    }
+                }
+                (None, None) => panic!("deployments must be mocked with test_host::mock_deploy"),
+            };
+            state.return_data = result.clone().err().unwrap_or_default();
+            result
+        });
+        let address = result.as_ref().copied().unwrap_or_default();
+        write_bytes(contract, address.as_slice());
+        *revert_data_len = result.err().map_or(0, |data| data.len());
+    }
+
+    /// Makes a mocked call, returning its status.
+    unsafe fn call(
+        contract: *const u8,
+        calldata: *const u8,
+        calldata_len: usize,
+        value: U256,
+        gas: u64,
+        return_data_len: *mut usize,
+    ) -> u8 {
+        let contract = Address::from_slice(slice::from_raw_parts(contract, 20));
+        let calldata = slice::from_raw_parts(calldata, calldata_len).to_vec();
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let gas_left = state.gas_left.unwrap_or(u64::MAX);
+            let gas = gas.min(gas_left);
+            state.calls.push(CallRecord {
+                contract,
+                data: calldata,
+                value,
+                gas,
+            });
+
+            let call_gas = state.call_gas.min(gas);
+            let (used, result) = match state.call_results.get_mut(&contract) {
+                Some(CallResult::Return(result)) => (call_gas, result.clone()),
+                Some(CallResult::Sequence(results)) if results.len() > 1 => {
+                    (call_gas, results.remove(0))
+                }
+                Some(CallResult::Sequence(results)) => (call_gas, results[0].clone()),
+                Some(CallResult::OutOfGas) => (gas, Err(vec![])),
+                None => panic!("calls must be mocked with test_host::mock_call"),
+            };
+            if let Some(gas_left) = &mut state.gas_left {
+                *gas_left -= used;
+            }
+            let status = result.is_err().into();
+            let data = result.unwrap_or_else(|data| data);
+            *return_data_len = data.len();
+            state.return_data = data;
+            status
+        })
+    }
+
+    unsafe fn code(address: *const u8) -> Vec<u8> {
+        let address = Address::from_slice(slice::from_raw_parts(address, 20));
+        STATE
+            .with(|state| state.borrow().code.get(&address).cloned())
+            .unwrap_or_default()
+    }
+
+    unsafe fn read_word(ptr: *const u8) -> B256 {
+        B256::from_slice(slice::from_raw_parts(ptr, 32))
+    }
+
+    unsafe fn write_bytes(dest: *mut u8, bytes: &[u8]) {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), dest, bytes.len());
+    }
+
+    fn log(text: String) {
+        STATE.with(|state| state.borrow_mut().console.push(text));
+    }
+
+    /// The base fee in wei.
+    const BASEFEE: u64 = 10_000_000;
+
+    pub unsafe fn storage_load_bytes32(key: *const u8, dest: *mut u8) {
+        let key = read_word(key);
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.stats.reads += 1;
+            match state.cached.insert(key) {
+                true => state.stats.cache_misses += 1,
+                false => state.stats.cache_hits += 1,
+            }
+        });
+        write_bytes(dest, storage(key.into()).as_slice());
+    }
+
+    /// Writes go straight to storage, since nothing can observe the difference from caching them.
+    pub unsafe fn storage_cache_bytes32(key: *const u8, value: *const u8) {
+        let (key, value) = (read_word(key), read_word(value));
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.stats.writes += 1;
+            state.cached.insert(key);
+            state.storage.insert(key, value);
+        });
+    }
+
+    pub unsafe fn storage_flush_cache(clear: bool) {
+        if clear {
+            STATE.with(|state| state.borrow_mut().cached.clear());
+        }
+    }
+
+    pub unsafe fn transient_load_bytes32(key: *const u8, dest: *mut u8) {
+        let value = transient(read_word(key).into());
+        write_bytes(dest, value.as_slice());
+    }
+
+    pub unsafe fn transient_store_bytes32(key: *const u8, value: *const u8) {
+        let (key, value) = (read_word(key), read_word(value));
+        STATE.with(|state| state.borrow_mut().transient.insert(key, value));
+    }
+
+    pub unsafe fn emit_log(data: *const u8, len: usize, topics: usize) {
+        let data = slice::from_raw_parts(data, len);
+        let (topic_data, data) = data.split_at(32 * topics);
+        let topics = topic_data.chunks(32).map(B256::from_slice).collect();
+        STATE.with(|state| state.borrow_mut().logs.push((topics, data.to_vec())));
+    }
+
+    /// Also provides the symbol `alloy_primitives` links against when its `native-keccak` feature is on.
+    #[no_mangle]
+    pub unsafe extern "C" fn native_keccak256(bytes: *const u8, len: usize, output: *mut u8) {
+        let hash = Keccak256::digest(slice::from_raw_parts(bytes, len));
+        write_bytes(output, &hash);
+    }
+
+    pub unsafe fn msg_reentrant() -> bool {
+        false
+    }
+
+    pub unsafe fn msg_sender(sender: *mut u8) {
+        let value = STATE.with(|state| state.borrow().sender);
+        write_bytes(sender, value.as_slice());
+    }
+
+    pub unsafe fn msg_value(value: *mut u8) {
+        let wei = STATE.with(|state| state.borrow().value);
+        write_bytes(value, &wei.to_be_bytes::<32>());
+    }
+
+    pub unsafe fn contract_address(address: *mut u8) {
+        write_bytes(address, Address::ZERO.as_slice());
+    }
+
+    /// Accounts without code, including those of mocked deployments, don't exist.
+    pub unsafe fn account_codehash(address: *const u8, dest: *mut u8) {
+        let code = code(address);
+        let hash = if code.is_empty() {
+            B256::ZERO
+        } else {
+            B256::from_slice(&Keccak256::digest(code))
+        };
+        write_bytes(dest, hash.as_slice());
+    }
+
+    pub unsafe fn account_code(
+        address: *const u8,
+        offset: usize,
+        size: usize,
+        dest: *mut u8,
+    ) -> usize {
+        let code = code(address);
+        let code = code.get(offset..).unwrap_or_default();
+        let code = &code[..size.min(code.len())];
+        write_bytes(dest, code);
+        code.len()
+    }
+
+    pub unsafe fn account_code_size(address: *const u8) -> usize {
+        code(address).len()
+    }
+
+    pub unsafe fn evm_gas_left() -> u64 {
+        STATE.with(|state| state.borrow().gas_left.unwrap_or(u64::MAX))
+    }
+
+    pub unsafe fn evm_ink_left() -> u64 {
+        u64::MAX
+    }
+
+    /// Unit tests run as if on Arbitrum One.
+    pub unsafe fn chainid() -> u64 {
+        42161
+    }
+
+    pub unsafe fn block_number() -> u64 {
+        STATE.with(|state| state.borrow().block_number.unwrap_or(BLOCK_NUMBER))
+    }
+
+    pub unsafe fn block_timestamp() -> u64 {
+        STATE.with(|state| state.borrow().timestamp.unwrap_or(TIMESTAMP))
+    }
+
+    pub unsafe fn create1(
+        code: *const u8,
+        code_len: usize,
+        _endowment: *const u8,
+        contract: *mut u8,
+        revert_data_len: *mut usize,
+    ) {
+        deploy(code, code_len, None, contract, revert_data_len);
+    }
+
+    pub unsafe fn create2(
+        code: *const u8,
+        code_len: usize,
+        _endowment: *const u8,
+        salt: *const u8,
+        contract: *mut u8,
+        revert_data_len: *mut usize,
+    ) {
+        deploy(
+            code,
+            code_len,
+            Some(read_word(salt)),
+            contract,
+            revert_data_len,
+        );
+    }
+
+    pub unsafe fn read_return_data(dest: *mut u8, offset: usize, size: usize) -> usize {
+        STATE.with(|state| {
+            let data = &state.borrow().return_data;
+            let data = data.get(offset..).unwrap_or_default();
+            let data = &data[..size.min(data.len())];
+            write_bytes(dest, data);
+            data.len()
+        })
+    }
+
+    pub unsafe fn return_data_size() -> usize {
+        STATE.with(|state| state.borrow().return_data.len())
+    }
+
+    pub unsafe fn read_args(dest: *mut u8) {
+        STATE.with(|state| write_bytes(dest, &state.borrow().calldata));
+    }
+
+    pub unsafe fn call_contract(
+        contract: *const u8,
+        calldata: *const u8,
+        calldata_len: usize,
+        value: *const u8,
+        gas: u64,
+        return_data_len: *mut usize,
+    ) -> u8 {
+        let value = read_word(value).into();
+        call(
+            contract,
+            calldata,
+            calldata_len,
+            value,
+            gas,
+            return_data_len,
+        )
+    }
+
+    pub unsafe fn delegate_call_contract(
+        contract: *const u8,
+        calldata: *const u8,
+        calldata_len: usize,
+        gas: u64,
+        return_data_len: *mut usize,
+    ) -> u8 {
+        let value = U256::ZERO;
+        call(
+            contract,
+            calldata,
+            calldata_len,
+            value,
+            gas,
+            return_data_len,
+        )
+    }
+
+    pub unsafe fn static_call_contract(
+        contract: *const u8,
+        calldata: *const u8,
+        calldata_len: usize,
+        gas: u64,
+        return_data_len: *mut usize,
+    ) -> u8 {
+        let value = U256::ZERO;
+        call(
+            contract,
+            calldata,
+            calldata_len,
+            value,
+            gas,
+            return_data_len,
+        )
+    }
+
+    pub unsafe fn log_txt(text: *const u8, len: usize) {
+        log(String::from_utf8_lossy(slice::from_raw_parts(text, len)).into_owned());
+    }
+
+    pub unsafe fn account_balance(address: *const u8, dest: *mut u8) {
+        let address = Address::from_slice(slice::from_raw_parts(address, 20));
+        let balance = STATE.with(|state| state.borrow().balances.get(&address).copied());
+        write_bytes(dest, &balance.unwrap_or_default().to_be_bytes::<32>());
+    }
+
+    /// Arbitrum One's minimum base fee of 0.01 gwei.
+    pub unsafe fn block_basefee(basefee: *mut u8) {
+        write_bytes(basefee, &U256::from(BASEFEE).to_be_bytes::<32>());
+    }
+
+    /// Arbitrum's coinbase, the sequencer's address.
+    pub unsafe fn block_coinbase(coinbase: *mut u8) {
+        write_bytes(
+            coinbase,
+            address!("A4b000000000000000000073657175656e636572").as_slice(),
+        );
+    }
+
+    /// Arbitrum's block gas limit.
+    pub unsafe fn block_gas_limit() -> u64 {
+        1 << 50
+    }
+
+    /// Native memory isn't metered.
+    pub unsafe fn pay_for_memory_grow(_pages: u16) {}
+
+    pub unsafe fn write_result(data: *const u8, len: usize) {
+        let data = slice::from_raw_parts(data, len).to_vec();
+        STATE.with(|state| state.borrow_mut().output = data);
+    }
+
+    /// Transactions pay the base fee, without a tip, as on Arbitrum.
+    pub unsafe fn tx_gas_price(gas_price: *mut u8) {
+        write_bytes(gas_price, &U256::from(BASEFEE).to_be_bytes::<32>());
+    }
+
+    /// Stylus's default ink price.
+    pub unsafe fn tx_ink_price() -> u32 {
+        10_000
+    }
+
+    pub unsafe fn tx_origin(origin: *mut u8) {
+        let value = STATE.with(|state| {
+            let state = state.borrow();
+            state.origin.unwrap_or(state.sender)
+        });
+        write_bytes(origin, value.as_slice());
+    }
+
+    pub unsafe fn log_f32(value: f32) {
+        log(value.to_string());
+    }
+
+    pub unsafe fn log_f64(value: f64) {
+        log(value.to_string());
+    }
+
+    pub unsafe fn log_i32(value: i32) {
+        log(value.to_string());
+    }
+
+    pub unsafe fn log_i64(value: i64) {
+        log(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{StorageType, StorageU64, StorageVec};
+    use alloy_primitives::U64;
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[test]
+    fn test_step_budget() {
+        let mut vec: StorageVec<StorageU64> = unsafe { StorageVec::new(U256::from(0x6b6b), 0) };
+        vec.extend((1..=3).map(U64::from));
+
+        // clearing is bounded by the length, which takes a few steps per element
+        set_step_budget(20);
+        vec.clear();
+        assert!(vec.is_empty());
+
+        // an unbounded loop runs out
+        set_step_budget(1_000);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| loop {
+            vec.push(U64::from(1));
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_environment() {
+        let account = Address::repeat_byte(0xa1);
+        set_sender(account);
+        set_balance(account, U256::from(5));
+        assert_eq!(crate::tx::origin(), account);
+        assert_eq!(crate::types::AddressVM::balance(&account), U256::from(5));
+        assert_eq!(crate::block::gas_limit(), 1 << 50);
+        assert_eq!(crate::tx::gas_price(), crate::block::basefee());
+
+        crate::contract::output(b"result");
+        assert_eq!(output(), b"result");
+    }
+
+    #[test]
+    fn test_block_helpers() {
+        use crate::block;
+        assert_eq!(
+            (block::number(), block::timestamp()),
+            (BLOCK_NUMBER, TIMESTAMP)
+        );
+
+        advance_block(10);
+        assert_eq!(block::number(), BLOCK_NUMBER + 10);
+        assert_eq!(block::timestamp(), TIMESTAMP + 10);
+
+        set_block_time(12);
+        advance_block(2);
+        assert_eq!(block::number(), BLOCK_NUMBER + 12);
+        assert_eq!(block::timestamp(), TIMESTAMP + 34);
+
+        roll(5);
+        warp(100);
+        advance_block(1);
+        assert_eq!((block::number(), block::timestamp()), (6, 112));
+    }
+
+    #[test]
+    fn test_prank() {
+        use crate::{msg, tx};
+        let (alice, bob, carol) = (
+            Address::repeat_byte(0xa1),
+            Address::repeat_byte(0xb0),
+            Address::repeat_byte(0xca),
+        );
+        set_sender(alice);
+
+        assert_eq!(prank(bob, msg::sender), bob);
+        assert_eq!(msg::sender(), alice);
+
+        let prank = start_prank(bob).with_origin(carol);
+        assert_eq!((msg::sender(), tx::origin()), (bob, carol));
+        {
+            let _nested = start_prank(carol);
+            assert_eq!(msg::sender(), carol);
+        }
+        assert_eq!(msg::sender(), bob);
+        stop_prank(prank);
+        assert_eq!((msg::sender(), tx::origin()), (alice, alice));
+    }
+
+    #[test]
+    fn test_expect_call() {
+        use crate::call::RawCall;
+        let token = Address::repeat_byte(0x20);
+        mock_call(token, Ok(vec![]));
+        expect_call(token, &[1, 2], U256::from(3));
+        expect_call_count(token, &[1], U256::ZERO, 2);
+        expect_call_count(token, &[4], U256::ZERO, 0);
+
+        #[allow(unused_unsafe)]
+        unsafe {
+            let value = U256::from(3);
+            RawCall::new_with_value(value)
+                .call(token, &[1, 2, 3])
+                .unwrap();
+            RawCall::new().call(token, &[1]).unwrap();
+            RawCall::new_static().call(token, &[1, 5]).unwrap();
+        }
+        verify_expectations();
+
+        // expectations only count calls made after they're set
+        expect_call(token, &[1], U256::ZERO);
+        let result = panic::catch_unwind(verify_expectations);
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("expected at least 1, made 0"), "{message}");
+
+        // and they're cleared once checked
+        verify_expectations();
+    }
+
+    #[test]
+    fn test_storage_access_stats() {
+        use crate::storage::{GlobalStorage, StorageCache};
+        let (a, b) = (U256::from(0x6f6f), U256::from(0x7070));
+        unsafe { StorageCache::set_word(a, B256::repeat_byte(1)) };
+        assert_eq!(storage_access_stats().writes, 1);
+        reset_storage_access_stats();
+
+        // written slots are already cached, and loaded ones become so
+        StorageCache::get_word(a);
+        StorageCache::get_word(b);
+        StorageCache::get_word(b);
+        let stats = StorageAccessStats {
+            reads: 3,
+            writes: 0,
+            cache_hits: 2,
+            cache_misses: 1,
+        };
+        assert_eq!(storage_access_stats(), stats);
+
+        // clearing the cache makes the next load cold
+        StorageCache::clear();
+        StorageCache::get_word(a);
+        assert_eq!(storage_access_stats().cache_misses, 2);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn test_console() {
+        assert!(console().is_empty());
+        crate::console!("minted {} tokens", 5);
+        crate::error!("transfer failed");
+        crate::debug::console_log("done");
+        assert_eq!(
+            console(),
+            ["minted 5 tokens", "[ERROR] transfer failed", "done"]
+        );
+    }
+}
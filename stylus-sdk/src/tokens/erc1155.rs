@@ -0,0 +1,719 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Implementation of the [ERC-1155] multi-token standard.
+//!
+//! The [`Erc1155`] component tracks balances of any number of token types, each identified by
+//! a `uint256` id. Safe transfers and mints to contracts call the receiver's
+//! `onERC1155Received` or `onERC1155BatchReceived` hook, and fail unless it returns the hook's
+//! selector.
+//!
+//! ```ignore
+//! #[storage]
+//! #[entrypoint]
+//! struct Game {
+//!     #[borrow]
+//!     items: Erc1155,
+//! }
+//!
+//! #[public]
+//! #[inherit(Erc1155)]
+//! impl Game {
+//!     pub fn craft(&mut self, id: U256) -> Result<(), Erc1155Error> {
+//!         ...
+//!         Erc1155::_mint(self, msg::sender(), id, U256::from(1), vec![])
+//!     }
+//! }
+//! ```
+//!
+//! Contracts advertise the standard's interfaces through `supportsInterface` by listing
+//! `Erc1155` in `#[implements]`, which answers for [`IERC1155`] and [`IERC1155_METADATA_URI`].
+//!
+//! Metadata URIs are set via [`Erc1155::_set_uri`] for all tokens, or
+//! [`Erc1155::_set_token_uri`] for a particular one. The errors and events match those of
+//! OpenZeppelin's implementation.
+//!
+//! [ERC-1155]: https://eips.ethereum.org/EIPS/eip-1155
+
+use crate::{
+    abi::{interface_id, Bytes},
+    evm,
+    math::MathError,
+    msg,
+    storage::{StorageBool, StorageMap, StorageString, StorageU256, TopLevelStorage},
+    stylus_proc::{public, storage, SolidityError},
+    types::AddressVM,
+};
+use alloc::{string::String, vec, vec::Vec};
+use alloy_primitives::{Address, FixedBytes, U256};
+use alloy_sol_types::sol;
+use core::borrow::BorrowMut;
+use interface::IERC1155Receiver;
+
+/// The selector of `onERC1155Received`, which receivers return to accept a transfer.
+pub const ON_ERC1155_RECEIVED: [u8; 4] =
+    crate::function_selector!("onERC1155Received", Address, Address, U256, U256, Bytes);
+
+/// The selector of `onERC1155BatchReceived`, which receivers return to accept a batch transfer.
+pub const ON_ERC1155_BATCH_RECEIVED: [u8; 4] = crate::function_selector!(
+    "onERC1155BatchReceived",
+    Address,
+    Address,
+    Vec<U256>,
+    Vec<U256>,
+    Bytes
+);
+
+/// The [ERC-165] id of the ERC-1155 interface, `0xd9b67a26`.
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+pub const IERC1155: u32 = interface_id(&[
+    crate::function_selector!("balanceOf", Address, U256),
+    crate::function_selector!("balanceOfBatch", Vec<Address>, Vec<U256>),
+    crate::function_selector!("setApprovalForAll", Address, bool),
+    crate::function_selector!("isApprovedForAll", Address, Address),
+    crate::function_selector!("safeTransferFrom", Address, Address, U256, U256, Bytes),
+    crate::function_selector!(
+        "safeBatchTransferFrom",
+        Address,
+        Address,
+        Vec<U256>,
+        Vec<U256>,
+        Bytes
+    ),
+]);
+
+/// The [ERC-165] id of the ERC-1155 metadata URI extension, `0x0e89341c`.
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+pub const IERC1155_METADATA_URI: u32 = interface_id(&[crate::function_selector!("uri", U256)]);
+
+mod interface {
+    use stylus_proc::sol_interface;
+
+    sol_interface! {
+        interface IERC1155Receiver {
+            function onERC1155Received(address operator, address from, uint256 id, uint256 value, bytes data) external returns (bytes4);
+            function onERC1155BatchReceived(address operator, address from, uint256[] ids, uint256[] values, bytes data) external returns (bytes4);
+        }
+    }
+}
+
+sol! {
+    /// Emitted when `operator` moves `value` tokens of type `id` from `from` to `to`.
+    event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+
+    /// Emitted when `operator` moves tokens of several types from `from` to `to`.
+    event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+
+    /// Emitted when `account` approves or revokes `operator` managing all its tokens.
+    event ApprovalForAll(address indexed account, address indexed operator, bool approved);
+
+    /// Emitted when the URI of token type `id` changes to `value`.
+    event URI(string value, uint256 indexed id);
+
+    /// The sender's balance of a token is too low for the transfer.
+    #[allow(missing_docs)]
+    error ERC1155InsufficientBalance(address sender, uint256 balance, uint256 needed, uint256 tokenId);
+
+    /// Tokens can't be sent from the given address, such as the zero address.
+    #[allow(missing_docs)]
+    error ERC1155InvalidSender(address sender);
+
+    /// Tokens can't be sent to the given address, either because it's zero or because it's a
+    /// contract that didn't accept them.
+    #[allow(missing_docs)]
+    error ERC1155InvalidReceiver(address receiver);
+
+    /// The operator isn't the owner and wasn't approved by them.
+    #[allow(missing_docs)]
+    error ERC1155MissingApprovalForAll(address operator, address owner);
+
+    /// The given address can't be approved as an operator, such as the zero address.
+    #[allow(missing_docs)]
+    error ERC1155InvalidOperator(address operator);
+
+    /// Arrays that should be parallel have different lengths.
+    #[allow(missing_docs)]
+    error ERC1155InvalidArrayLength(uint256 idsLength, uint256 valuesLength);
+}
+
+/// Represents the ways [`Erc1155`] methods may fail.
+#[derive(SolidityError)]
+pub enum Erc1155Error {
+    /// The sender's balance is too low.
+    InsufficientBalance(ERC1155InsufficientBalance),
+    /// The sender is invalid.
+    InvalidSender(ERC1155InvalidSender),
+    /// The receiver is invalid or refused the tokens.
+    InvalidReceiver(ERC1155InvalidReceiver),
+    /// The caller isn't approved to move the tokens.
+    MissingApprovalForAll(ERC1155MissingApprovalForAll),
+    /// The operator is invalid.
+    InvalidOperator(ERC1155InvalidOperator),
+    /// Parallel arrays differ in length.
+    InvalidArrayLength(ERC1155InvalidArrayLength),
+    /// The receiver's balance would overflow, reverting with Solidity's `Panic(0x11)`.
+    Overflow(MathError),
+}
+
+/// Tracks balances of many token types.
+#[storage]
+pub struct Erc1155 {
+    balances: StorageMap<U256, StorageMap<Address, StorageU256>>,
+    operator_approvals: StorageMap<Address, StorageMap<Address, StorageBool>>,
+    uri: StorageString,
+    token_uris: StorageMap<U256, StorageString>,
+}
+
+fn check_lengths(ids: usize, values: usize) -> Result<(), Erc1155Error> {
+    if ids != values {
+        let err = ERC1155InvalidArrayLength {
+            idsLength: U256::from(ids),
+            valuesLength: U256::from(values),
+        };
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+impl Erc1155 {
+    /// Moves `values` of each token type in `ids` from `from` to `to`, emitting
+    /// [`TransferSingle`] for a single id and [`TransferBatch`] otherwise.
+    ///
+    /// A zero `from` mints the tokens, and a zero `to` burns them. This doesn't call the
+    /// receiver's hook; see [`_update_with_acceptance_check`](Self::_update_with_acceptance_check).
+    pub fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: &[U256],
+        values: &[U256],
+    ) -> Result<(), Erc1155Error> {
+        check_lengths(ids.len(), values.len())?;
+
+        for (&id, &value) in ids.iter().zip(values) {
+            let mut balances = self.balances.setter(id);
+            if !from.is_zero() {
+                let mut balance = balances.setter(from);
+                let have = balance.get();
+                if have < value {
+                    let err = ERC1155InsufficientBalance {
+                        sender: from,
+                        balance: have,
+                        needed: value,
+                        tokenId: id,
+                    };
+                    return Err(err.into());
+                }
+                balance.set(have - value);
+            }
+            if !to.is_zero() {
+                let mut balance = balances.setter(to);
+                let new_balance = balance.get().checked_add(value);
+                balance.set(new_balance.ok_or(MathError::Overflow)?);
+            }
+        }
+
+        let operator = msg::sender();
+        if let ([id], [value]) = (ids, values) {
+            evm::log(TransferSingle {
+                operator,
+                from,
+                to,
+                id: *id,
+                value: *value,
+            });
+        } else {
+            evm::log(TransferBatch {
+                operator,
+                from,
+                to,
+                ids: ids.to_vec(),
+                values: values.to_vec(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Calls [`_update`](Self::_update), then checks that `to` accepts the tokens if it's a
+    /// contract.
+    ///
+    /// A receiver accepts by returning [`ON_ERC1155_RECEIVED`] from `onERC1155Received`, or for
+    /// multiple ids, [`ON_ERC1155_BATCH_RECEIVED`] from `onERC1155BatchReceived`. If the hook
+    /// reverts or returns anything else, this fails with [`ERC1155InvalidReceiver`].
+    pub fn _update_with_acceptance_check<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Vec<u8>,
+    ) -> Result<(), Erc1155Error> {
+        storage.borrow_mut()._update(from, to, &ids, &values)?;
+        if to.is_zero() || !to.has_code() {
+            return Ok(());
+        }
+
+        let receiver = IERC1155Receiver::new(to);
+        let operator = msg::sender();
+        let (result, expected) = match (&ids[..], &values[..]) {
+            ([id], [value]) => {
+                let result = receiver.on_erc_1155_received(
+                    &mut *storage,
+                    operator,
+                    from,
+                    *id,
+                    *value,
+                    data.into(),
+                );
+                (result, ON_ERC1155_RECEIVED)
+            }
+            _ => {
+                let result = receiver.on_erc_1155_batch_received(
+                    &mut *storage,
+                    operator,
+                    from,
+                    ids,
+                    values,
+                    data.into(),
+                );
+                (result, ON_ERC1155_BATCH_RECEIVED)
+            }
+        };
+        match result {
+            Ok(FixedBytes(returned)) if returned == expected => Ok(()),
+            _ => Err(ERC1155InvalidReceiver { receiver: to }.into()),
+        }
+    }
+
+    /// Creates `value` tokens of type `id` for `to`, checking that `to` accepts them.
+    pub fn _mint<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<(), Erc1155Error> {
+        Self::_mint_batch(storage, to, vec![id], vec![value], data)
+    }
+
+    /// Creates tokens of several types for `to`, checking that `to` accepts them.
+    pub fn _mint_batch<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Vec<u8>,
+    ) -> Result<(), Erc1155Error> {
+        if to.is_zero() {
+            return Err(ERC1155InvalidReceiver { receiver: to }.into());
+        }
+        Self::_update_with_acceptance_check(storage, Address::ZERO, to, ids, values, data)
+    }
+
+    /// Destroys `value` tokens of type `id` held by `from`.
+    pub fn _burn(&mut self, from: Address, id: U256, value: U256) -> Result<(), Erc1155Error> {
+        self._burn_batch(from, &[id], &[value])
+    }
+
+    /// Destroys tokens of several types held by `from`.
+    pub fn _burn_batch(
+        &mut self,
+        from: Address,
+        ids: &[U256],
+        values: &[U256],
+    ) -> Result<(), Erc1155Error> {
+        if from.is_zero() {
+            return Err(ERC1155InvalidSender { sender: from }.into());
+        }
+        self._update(from, Address::ZERO, ids, values)
+    }
+
+    /// Approves or revokes `operator` managing all of `owner`'s tokens, emitting
+    /// [`ApprovalForAll`].
+    pub fn _set_approval_for_all(
+        &mut self,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Erc1155Error> {
+        if operator.is_zero() {
+            return Err(ERC1155InvalidOperator { operator }.into());
+        }
+        self.operator_approvals
+            .setter(owner)
+            .insert(operator, approved);
+        evm::log(ApprovalForAll {
+            account: owner,
+            operator,
+            approved,
+        });
+        Ok(())
+    }
+
+    /// Sets the URI returned for token types without their own.
+    ///
+    /// Per the standard, clients replace any `{id}` in the URI with the token's hex-encoded id.
+    pub fn _set_uri(&mut self, uri: &str) {
+        self.uri.set_str(uri);
+    }
+
+    /// Sets the URI of token type `id`, emitting [`URI`].
+    pub fn _set_token_uri(&mut self, id: U256, uri: &str) {
+        self.token_uris.setter(id).set_str(uri);
+        evm::log(URI {
+            value: uri.into(),
+            id,
+        });
+    }
+
+    fn check_authorized(&self, from: Address) -> Result<(), Erc1155Error> {
+        let operator = msg::sender();
+        if from != operator && !self.is_approved_for_all(from, operator) {
+            let err = ERC1155MissingApprovalForAll {
+                operator,
+                owner: from,
+            };
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    fn check_transfer(&self, from: Address, to: Address) -> Result<(), Erc1155Error> {
+        if to.is_zero() {
+            return Err(ERC1155InvalidReceiver { receiver: to }.into());
+        }
+        if from.is_zero() {
+            return Err(ERC1155InvalidSender { sender: from }.into());
+        }
+        self.check_authorized(from)
+    }
+}
+
+#[public]
+#[interface_ids(IERC1155, IERC1155_METADATA_URI)]
+impl Erc1155 {
+    /// The number of tokens of type `id` held by `account`.
+    pub fn balance_of(&self, account: Address, id: U256) -> U256 {
+        self.balances.getter(id).get(account)
+    }
+
+    /// The balance of each account for the token type at the same index in `ids`.
+    pub fn balance_of_batch(
+        &self,
+        accounts: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> Result<Vec<U256>, Erc1155Error> {
+        check_lengths(ids.len(), accounts.len())?;
+        let balances = accounts
+            .into_iter()
+            .zip(ids)
+            .map(|(account, id)| self.balance_of(account, id))
+            .collect();
+        Ok(balances)
+    }
+
+    /// Approves or revokes `operator` managing all of the caller's tokens.
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Erc1155Error> {
+        self._set_approval_for_all(msg::sender(), operator, approved)
+    }
+
+    /// Whether `operator` may manage all of `account`'s tokens.
+    pub fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {
+        self.operator_approvals.getter(account).get(operator)
+    }
+
+    /// The metadata URI of token type `id`.
+    pub fn uri(&self, id: U256) -> String {
+        let uri = self.token_uris.getter(id);
+        match uri.is_empty() {
+            true => self.uri.get_string(),
+            false => uri.get_string(),
+        }
+    }
+
+    /// Moves `value` tokens of type `id` from `from` to `to`, which must accept them if it's a
+    /// contract. The caller must be `from` or an approved operator.
+    pub fn safe_transfer_from<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), Erc1155Error> {
+        storage.borrow_mut().check_transfer(from, to)?;
+        Self::_update_with_acceptance_check(storage, from, to, vec![id], vec![value], data.0)
+    }
+
+    /// Moves tokens of several types from `from` to `to`, which must accept them if it's a
+    /// contract. The caller must be `from` or an approved operator.
+    pub fn safe_batch_transfer_from<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), Erc1155Error> {
+        storage.borrow_mut().check_transfer(from, to)?;
+        Self::_update_with_acceptance_check(storage, from, to, ids, values, data.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageType, test_host};
+    use alloy_primitives::B256;
+    use alloy_sol_types::SolEvent;
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+
+    unsafe impl TopLevelStorage for Erc1155 {}
+
+    fn token() -> Erc1155 {
+        unsafe { Erc1155::new(U256::ZERO, 0) }
+    }
+
+    fn ids(ids: &[u64]) -> Vec<U256> {
+        ids.iter().map(|&id| U256::from(id)).collect()
+    }
+
+    #[test]
+    fn test_selectors() {
+        assert_eq!(u32::from_be_bytes(ON_ERC1155_RECEIVED), 0xf23a6e61);
+        assert_eq!(u32::from_be_bytes(ON_ERC1155_BATCH_RECEIVED), 0xbc197c81);
+        assert_eq!(IERC1155, 0xd9b67a26);
+        assert_eq!(IERC1155_METADATA_URI, 0x0e89341c);
+    }
+
+    #[test]
+    fn test_supports_interface() {
+        use crate::abi::{AbiType, Router, ERC165_INTERFACE_ID};
+        use alloy_sol_types::SolType;
+
+        #[storage]
+        struct Game {
+            #[borrow]
+            items: Erc1155,
+        }
+
+        unsafe impl TopLevelStorage for Game {}
+
+        #[public]
+        #[inherit(Erc1155)]
+        #[implements(Erc1155)]
+        impl Game {}
+
+        let supports = |id: u32| {
+            let mut game = unsafe { Game::new(U256::ZERO, 0) };
+            let selector = crate::function_selector!("supportsInterface", FixedBytes<4>);
+            let input = (FixedBytes(id.to_be_bytes()),);
+            let input = <(FixedBytes<4>,) as AbiType>::SolType::abi_encode_params(&input);
+            let output = Game::route(&mut game, u32::from_be_bytes(selector), &input).unwrap();
+            <bool as AbiType>::SolType::abi_decode(&output.unwrap(), true).unwrap()
+        };
+        assert!(supports(ERC165_INTERFACE_ID));
+        assert!(supports(0xd9b67a26));
+        assert!(supports(0x0e89341c));
+
+        // the XOR of every method, uri included, isn't an interface
+        assert!(!supports(Erc1155::__stylus_interface_id()));
+        assert!(!supports(0xffffffff));
+    }
+
+    /// Encodes a selector as the `bytes4` a receiver's hook returns.
+    fn hook_return(selector: [u8; 4]) -> Vec<u8> {
+        let mut word = selector.to_vec();
+        word.resize(32, 0);
+        word
+    }
+
+    #[test]
+    fn test_receiver_hooks() {
+        use crate::abi;
+
+        let receiver = Address::repeat_byte(0xc0);
+        test_host::set_code(receiver, b"receiver");
+        test_host::set_sender(ALICE);
+        let mut token = token();
+        let (one, two) = (U256::from(1), U256::from(2));
+
+        // receivers accept by returning the hook's selector
+        test_host::mock_call(receiver, Ok(hook_return(ON_ERC1155_RECEIVED)));
+        let result = Erc1155::_mint(&mut token, receiver, one, U256::from(5), b"hi".to_vec());
+        assert!(result.is_ok());
+        let (to, data, _) = test_host::calls().pop().unwrap();
+        assert_eq!(to, receiver);
+        let args = (
+            ALICE,
+            Address::ZERO,
+            one,
+            U256::from(5),
+            Bytes(b"hi".to_vec()),
+        );
+        assert_eq!(data, abi::encode_call(ON_ERC1155_RECEIVED, &args));
+        assert_eq!(token.balance_of(receiver, one), U256::from(5));
+
+        // returning anything else, including the other hook's selector, refuses the tokens
+        let refused = |result: Result<(), Erc1155Error>| matches!(result, Err(Erc1155Error::InvalidReceiver(err)) if err.receiver == receiver);
+        test_host::mock_call(receiver, Ok(hook_return(ON_ERC1155_BATCH_RECEIVED)));
+        let result = Erc1155::_mint(&mut token, receiver, one, U256::from(1), vec![]);
+        assert!(refused(result));
+        test_host::mock_call(receiver, Ok(vec![]));
+        let result = Erc1155::_mint(&mut token, receiver, one, U256::from(1), vec![]);
+        assert!(refused(result));
+
+        // as does reverting
+        test_host::mock_call(receiver, Err(b"no thanks".to_vec()));
+        let result = Erc1155::_mint(&mut token, receiver, one, U256::from(1), vec![]);
+        assert!(refused(result));
+
+        // batches call the batch hook with the full arrays
+        let minted = Erc1155::_mint_batch(&mut token, ALICE, ids(&[1, 2]), ids(&[10, 20]), vec![]);
+        assert!(minted.is_ok());
+        test_host::mock_call(receiver, Ok(hook_return(ON_ERC1155_BATCH_RECEIVED)));
+        let result = Erc1155::safe_batch_transfer_from(
+            &mut token,
+            ALICE,
+            receiver,
+            ids(&[1, 2]),
+            ids(&[3, 4]),
+            Bytes(vec![]),
+        );
+        assert!(result.is_ok());
+        let (_, data, _) = test_host::calls().pop().unwrap();
+        let args = (ALICE, ALICE, ids(&[1, 2]), ids(&[3, 4]), Bytes(vec![]));
+        assert_eq!(data, abi::encode_call(ON_ERC1155_BATCH_RECEIVED, &args));
+        assert_eq!(token.balance_of(receiver, two), U256::from(4));
+
+        test_host::mock_call(receiver, Ok(hook_return(ON_ERC1155_RECEIVED)));
+        let result = Erc1155::safe_batch_transfer_from(
+            &mut token,
+            ALICE,
+            receiver,
+            ids(&[1, 2]),
+            ids(&[1, 1]),
+            Bytes(vec![]),
+        );
+        assert!(refused(result));
+
+        // accounts without code aren't called
+        let calls = test_host::calls().len();
+        let data = Bytes(b"ignored".to_vec());
+        let result = Erc1155::safe_transfer_from(&mut token, ALICE, BOB, two, one, data);
+        assert!(result.is_ok());
+        assert_eq!(test_host::calls().len(), calls);
+        assert_eq!(token.balance_of(BOB, two), one);
+    }
+
+    #[test]
+    fn test_transfers() {
+        let mut token = token();
+        let minted = Erc1155::_mint_batch(&mut token, ALICE, ids(&[1, 2]), ids(&[10, 20]), vec![]);
+        assert!(minted.is_ok());
+
+        test_host::set_sender(BOB);
+        let result = Erc1155::safe_transfer_from(
+            &mut token,
+            ALICE,
+            BOB,
+            U256::from(1),
+            U256::from(4),
+            Bytes(vec![]),
+        );
+        assert!(matches!(
+            result,
+            Err(Erc1155Error::MissingApprovalForAll(_))
+        ));
+
+        test_host::set_sender(ALICE);
+        assert!(token.set_approval_for_all(BOB, true).is_ok());
+
+        test_host::set_sender(BOB);
+        let result = Erc1155::safe_batch_transfer_from(
+            &mut token,
+            ALICE,
+            BOB,
+            ids(&[1, 2]),
+            ids(&[4, 5]),
+            Bytes(vec![]),
+        );
+        assert!(result.is_ok());
+
+        let balances = token.balance_of_batch(vec![ALICE, ALICE, BOB, BOB], ids(&[1, 2, 1, 2]));
+        assert_eq!(balances.ok(), Some(ids(&[6, 15, 4, 5])));
+        assert!(matches!(
+            token.balance_of_batch(vec![ALICE], ids(&[1, 2])),
+            Err(Erc1155Error::InvalidArrayLength(_))
+        ));
+
+        match token._burn(BOB, U256::from(2), U256::from(6)) {
+            Err(Erc1155Error::InsufficientBalance(err)) => {
+                assert_eq!((err.balance, err.tokenId), (U256::from(5), U256::from(2)))
+            }
+            _ => panic!("expected insufficient balance"),
+        }
+        assert!(token._burn(BOB, U256::from(2), U256::from(5)).is_ok());
+
+        let logs = test_host::logs();
+        let events: Vec<_> = logs.iter().map(|(topics, _)| topics[0]).collect();
+        assert_eq!(
+            events,
+            [
+                TransferBatch::SIGNATURE_HASH,
+                ApprovalForAll::SIGNATURE_HASH,
+                TransferBatch::SIGNATURE_HASH,
+                TransferSingle::SIGNATURE_HASH,
+            ]
+        );
+
+        // batch events encode both arrays in full
+        let (topics, data) = &logs[2];
+        let event = TransferBatch::decode_raw_log(topics.iter().copied(), data, true).unwrap();
+        assert_eq!((event.operator, event.from, event.to), (BOB, ALICE, BOB));
+        assert_eq!((event.ids, event.values), (ids(&[1, 2]), ids(&[4, 5])));
+    }
+
+    #[test]
+    fn test_balance_overflow() {
+        use crate::call::FromRevert;
+
+        let mut token = token();
+        assert!(Erc1155::_mint(&mut token, BOB, U256::from(3), U256::MAX, vec![]).is_ok());
+        let result = Erc1155::_mint(&mut token, BOB, U256::from(3), U256::from(1), vec![]);
+        let Err(err) = result else {
+            panic!("expected overflow");
+        };
+        assert!(matches!(err, Erc1155Error::Overflow(MathError::Overflow)));
+
+        // reverts like Solidity's checked arithmetic
+        let data: Vec<u8> = err.into();
+        assert_eq!(data, Vec::<u8>::from(MathError::Overflow));
+        assert!(matches!(
+            Erc1155Error::from_revert(&data),
+            Some(Erc1155Error::Overflow(MathError::Overflow))
+        ));
+    }
+
+    #[test]
+    fn test_uri() {
+        let mut token = token();
+        token._set_uri("https://example.com/{id}.json");
+        token._set_token_uri(U256::from(7), "ipfs://seven");
+        assert_eq!(token.uri(U256::from(1)), "https://example.com/{id}.json");
+        assert_eq!(token.uri(U256::from(7)), "ipfs://seven");
+
+        let logs = test_host::logs();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].0[0], URI::SIGNATURE_HASH);
+        assert_eq!(logs[0].0[1], B256::from(U256::from(7)));
+    }
+}
@@ -0,0 +1,492 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Implementation of the [ERC-20] standard.
+//!
+//! The [`Erc20`] component provides all the standard methods. Its name, symbol, and decimals
+//! are configured via the [`Erc20Params`] trait.
+//!
+//! ```ignore
+//! struct MyParams;
+//!
+//! impl Erc20Params for MyParams {
+//!     const NAME: &'static str = "My Token";
+//!     const SYMBOL: &'static str = "MTK";
+//!     const DECIMALS: u8 = 18;
+//! }
+//!
+//! #[storage]
+//! #[entrypoint]
+//! struct MyToken {
+//!     #[borrow]
+//!     erc20: Erc20<MyParams>,
+//! }
+//!
+//! #[public]
+//! #[inherit(Erc20<MyParams>)]
+//! impl MyToken { ... }
+//! ```
+//!
+//! Every change to balances, including mints and burns, goes through [`Erc20::_update`].
+//! Contracts that need to restrict or react to transfers, such as to pause them, define their
+//! own `transfer` and `transferFrom` methods, which take priority over the inherited ones, and
+//! perform their checks before delegating to the component.
+//!
+//! The component also supports gasless approvals via [EIP-2612]'s `permit`, which verifies an
+//! [EIP-712] signature over a domain named [`Erc20Params::NAME`].
+//!
+//! The errors and events match those of OpenZeppelin's implementation.
+//!
+//! [ERC-20]: https://eips.ethereum.org/EIPS/eip-20
+//! [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+
+use crate::{
+    block, contract,
+    crypto::{self, eip712},
+    evm, msg,
+    storage::{StorageMap, StorageU256},
+    stylus_proc::{public, storage, SolidityError},
+};
+use alloc::string::String;
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{sol, SolValue};
+use core::marker::PhantomData;
+
+/// Configures the immutable metadata of an [`Erc20`] token.
+pub trait Erc20Params {
+    /// The token's name.
+    const NAME: &'static str;
+
+    /// The token's symbol.
+    const SYMBOL: &'static str;
+
+    /// The number of decimals balances are displayed with.
+    const DECIMALS: u8;
+
+    /// The version of the EIP-712 signing domain used by `permit`.
+    const VERSION: &'static str = "1";
+}
+
+/// The `typeHash` of EIP-2612's `Permit` struct.
+pub const PERMIT_TYPE_HASH: B256 = B256::new(
+    crate::keccak_const::Keccak256::new()
+        .update(
+            b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+        )
+        .finalize(),
+);
+
+sol! {
+    /// Emitted when `value` tokens move from `from` to `to`. Mints are from the zero address,
+    /// and burns are to it.
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    /// Emitted when `owner` sets the allowance of `spender` to `value`.
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    /// The sender's balance is too low for the transfer.
+    #[allow(missing_docs)]
+    error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+
+    /// Tokens can't be sent from the given address, such as the zero address.
+    #[allow(missing_docs)]
+    error ERC20InvalidSender(address sender);
+
+    /// Tokens can't be sent to the given address, such as the zero address.
+    #[allow(missing_docs)]
+    error ERC20InvalidReceiver(address receiver);
+
+    /// The spender's allowance is too low for the transfer.
+    #[allow(missing_docs)]
+    error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+
+    /// The given address can't approve spending, such as the zero address.
+    #[allow(missing_docs)]
+    error ERC20InvalidApprover(address approver);
+
+    /// The given address can't be approved to spend, such as the zero address.
+    #[allow(missing_docs)]
+    error ERC20InvalidSpender(address spender);
+
+    /// The permit's deadline has passed.
+    #[allow(missing_docs)]
+    error ERC2612ExpiredSignature(uint256 deadline);
+
+    /// The permit was signed by someone other than the owner.
+    #[allow(missing_docs)]
+    error ERC2612InvalidSigner(address signer, address owner);
+}
+
+/// Represents the ways [`Erc20`] methods may fail.
+#[derive(SolidityError)]
+pub enum Erc20Error {
+    /// The sender's balance is too low.
+    InsufficientBalance(ERC20InsufficientBalance),
+    /// The sender is invalid.
+    InvalidSender(ERC20InvalidSender),
+    /// The receiver is invalid.
+    InvalidReceiver(ERC20InvalidReceiver),
+    /// The spender's allowance is too low.
+    InsufficientAllowance(ERC20InsufficientAllowance),
+    /// The approver is invalid.
+    InvalidApprover(ERC20InvalidApprover),
+    /// The spender is invalid.
+    InvalidSpender(ERC20InvalidSpender),
+    /// The permit has expired.
+    ExpiredSignature(ERC2612ExpiredSignature),
+    /// The permit's signature isn't the owner's.
+    InvalidSigner(ERC2612InvalidSigner),
+}
+
+/// An ERC-20 token whose metadata is given by `P`.
+#[storage]
+pub struct Erc20<P> {
+    balances: StorageMap<Address, StorageU256>,
+    allowances: StorageMap<Address, StorageMap<Address, StorageU256>>,
+    total_supply: StorageU256,
+    nonces: StorageMap<Address, StorageU256>,
+    params: PhantomData<P>,
+}
+
+impl<P: Erc20Params> Erc20<P> {
+    /// Moves `value` tokens from `from` to `to`, failing if either is the zero address.
+    pub fn _transfer(&mut self, from: Address, to: Address, value: U256) -> Result<(), Erc20Error> {
+        if from.is_zero() {
+            return Err(ERC20InvalidSender { sender: from }.into());
+        }
+        if to.is_zero() {
+            return Err(ERC20InvalidReceiver { receiver: to }.into());
+        }
+        self._update(from, to, value)
+    }
+
+    /// Creates `value` tokens and assigns them to `account`.
+    pub fn _mint(&mut self, account: Address, value: U256) -> Result<(), Erc20Error> {
+        if account.is_zero() {
+            return Err(ERC20InvalidReceiver { receiver: account }.into());
+        }
+        self._update(Address::ZERO, account, value)
+    }
+
+    /// Destroys `value` tokens held by `account`.
+    pub fn _burn(&mut self, account: Address, value: U256) -> Result<(), Erc20Error> {
+        if account.is_zero() {
+            return Err(ERC20InvalidSender { sender: account }.into());
+        }
+        self._update(account, Address::ZERO, value)
+    }
+
+    /// Moves `value` tokens from `from` to `to`, emitting [`Transfer`].
+    ///
+    /// A zero `from` mints the tokens, and a zero `to` burns them. All balance changes go
+    /// through this method, and it performs no checks on the addresses themselves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if minting would overflow the total supply.
+    pub fn _update(&mut self, from: Address, to: Address, value: U256) -> Result<(), Erc20Error> {
+        if from.is_zero() {
+            let supply = self.total_supply.get();
+            let supply = supply.checked_add(value).expect("total supply overflow");
+            self.total_supply.set(supply);
+        } else {
+            let mut balance = self.balances.setter(from);
+            let have = balance.get();
+            if have < value {
+                let err = ERC20InsufficientBalance {
+                    sender: from,
+                    balance: have,
+                    needed: value,
+                };
+                return Err(err.into());
+            }
+            balance.set(have - value);
+        }
+
+        if to.is_zero() {
+            // can't underflow since the supply is at least the burned balance
+            self.total_supply.set(self.total_supply.get() - value);
+        } else {
+            // can't overflow since balances sum to the supply
+            let mut balance = self.balances.setter(to);
+            let new_balance = balance.get() + value;
+            balance.set(new_balance);
+        }
+
+        evm::log(Transfer { from, to, value });
+        Ok(())
+    }
+
+    /// Sets the allowance of `spender` over `owner`'s tokens, emitting [`Approval`].
+    pub fn _approve(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Erc20Error> {
+        if owner.is_zero() {
+            return Err(ERC20InvalidApprover { approver: owner }.into());
+        }
+        if spender.is_zero() {
+            return Err(ERC20InvalidSpender { spender }.into());
+        }
+        self.allowances.setter(owner).insert(spender, value);
+        evm::log(Approval {
+            owner,
+            spender,
+            value,
+        });
+        Ok(())
+    }
+
+    /// Deducts `value` from the allowance of `spender` over `owner`'s tokens.
+    ///
+    /// An allowance of [`U256::MAX`] is treated as infinite and left as is.
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Erc20Error> {
+        let mut allowances = self.allowances.setter(owner);
+        let mut allowance = allowances.setter(spender);
+        let have = allowance.get();
+        if have == U256::MAX {
+            return Ok(());
+        }
+        if have < value {
+            let err = ERC20InsufficientAllowance {
+                spender,
+                allowance: have,
+                needed: value,
+            };
+            return Err(err.into());
+        }
+        allowance.set(have - value);
+        Ok(())
+    }
+}
+
+#[public]
+impl<P: Erc20Params> Erc20<P> {
+    /// The EIP-712 domain separator that `permit` signatures are made over.
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator() -> B256 {
+        eip712::domain_separator(P::NAME, P::VERSION, block::chainid(), contract::address())
+    }
+
+    /// The nonce `owner` must sign their next permit with.
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.nonces.get(owner)
+    }
+
+    /// Sets `owner`'s allowance for `spender` to `value`, given `owner`'s signature.
+    ///
+    /// The signature covers the owner's current nonce, which is then incremented so that the
+    /// permit can't be replayed. Fails if `deadline` has passed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        deadline: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Erc20Error> {
+        if U256::from(block::timestamp()) > deadline {
+            return Err(ERC2612ExpiredSignature { deadline }.into());
+        }
+
+        let mut nonce = self.nonces.setter(owner);
+        let current = nonce.get();
+        nonce.set(current + U256::from(1));
+
+        let permit = (PERMIT_TYPE_HASH, owner, spender, value, current, deadline);
+        let struct_hash = crypto::keccak(permit.abi_encode());
+        let digest = eip712::hash_typed_data(Self::domain_separator(), struct_hash);
+
+        let signer = crypto::ecrecover(digest, v, r, s).unwrap_or_default();
+        if signer != owner {
+            return Err(ERC2612InvalidSigner { signer, owner }.into());
+        }
+        self._approve(owner, spender, value)
+    }
+
+    /// The token's name.
+    pub fn name() -> String {
+        P::NAME.into()
+    }
+
+    /// The token's symbol.
+    pub fn symbol() -> String {
+        P::SYMBOL.into()
+    }
+
+    /// The number of decimals balances are displayed with.
+    pub fn decimals() -> u8 {
+        P::DECIMALS
+    }
+
+    /// The number of tokens in existence.
+    pub fn total_supply(&self) -> U256 {
+        self.total_supply.get()
+    }
+
+    /// The number of tokens held by `owner`.
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        self.balances.get(owner)
+    }
+
+    /// Moves `value` of the caller's tokens to `to`.
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Erc20Error> {
+        self._transfer(msg::sender(), to, value)?;
+        Ok(true)
+    }
+
+    /// Moves `value` of `from`'s tokens to `to`, spending the caller's allowance.
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Erc20Error> {
+        self._spend_allowance(from, msg::sender(), value)?;
+        self._transfer(from, to, value)?;
+        Ok(true)
+    }
+
+    /// Sets the caller's allowance for `spender` to `value`.
+    pub fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Erc20Error> {
+        self._approve(msg::sender(), spender, value)?;
+        Ok(true)
+    }
+
+    /// The number of `owner`'s tokens `spender` may still transfer.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances.getter(owner).get(spender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::StorageType, test_host};
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+
+    struct Params;
+
+    impl Erc20Params for Params {
+        const NAME: &'static str = "Test Token";
+        const SYMBOL: &'static str = "TEST";
+        const DECIMALS: u8 = 18;
+    }
+
+    fn token() -> Erc20<Params> {
+        unsafe { Erc20::new(U256::ZERO, 0) }
+    }
+
+    #[test]
+    fn test_mint_burn_transfer() {
+        let mut token = token();
+        assert!(token._mint(ALICE, U256::from(100)).is_ok());
+        assert!(token._burn(ALICE, U256::from(30)).is_ok());
+        assert_eq!(token.total_supply(), U256::from(70));
+
+        test_host::set_sender(ALICE);
+        assert!(token.transfer(BOB, U256::from(50)).is_ok());
+        assert_eq!(token.balance_of(ALICE), U256::from(20));
+        assert_eq!(token.balance_of(BOB), U256::from(50));
+
+        match token.transfer(BOB, U256::from(21)) {
+            Err(Erc20Error::InsufficientBalance(err)) => {
+                assert_eq!((err.balance, err.needed), (U256::from(20), U256::from(21)))
+            }
+            _ => panic!("expected insufficient balance"),
+        }
+        assert!(matches!(
+            token.transfer(Address::ZERO, U256::from(1)),
+            Err(Erc20Error::InvalidReceiver(_))
+        ));
+        assert!(matches!(
+            token._mint(Address::ZERO, U256::from(1)),
+            Err(Erc20Error::InvalidReceiver(_))
+        ));
+
+        let transfers: Vec<_> = test_host::logs()
+            .into_iter()
+            .map(|(topics, data)| (topics[1], topics[2], U256::from_be_slice(&data)))
+            .collect();
+        assert_eq!(
+            transfers,
+            [
+                (
+                    Address::ZERO.into_word(),
+                    ALICE.into_word(),
+                    U256::from(100)
+                ),
+                (ALICE.into_word(), Address::ZERO.into_word(), U256::from(30)),
+                (ALICE.into_word(), BOB.into_word(), U256::from(50)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowances() {
+        let mut token = token();
+        assert!(token._mint(ALICE, U256::from(100)).is_ok());
+
+        test_host::set_sender(ALICE);
+        assert!(token.approve(BOB, U256::from(40)).is_ok());
+        assert!(matches!(
+            token.approve(Address::ZERO, U256::from(40)),
+            Err(Erc20Error::InvalidSpender(_))
+        ));
+
+        test_host::set_sender(BOB);
+        assert!(token.transfer_from(ALICE, BOB, U256::from(30)).is_ok());
+        assert_eq!(token.allowance(ALICE, BOB), U256::from(10));
+        assert!(matches!(
+            token.transfer_from(ALICE, BOB, U256::from(11)),
+            Err(Erc20Error::InsufficientAllowance(_))
+        ));
+
+        // an infinite allowance isn't spent
+        test_host::set_sender(ALICE);
+        assert!(token.approve(BOB, U256::MAX).is_ok());
+        test_host::set_sender(BOB);
+        assert!(token.transfer_from(ALICE, BOB, U256::from(70)).is_ok());
+        assert_eq!(token.allowance(ALICE, BOB), U256::MAX);
+        assert_eq!(token.balance_of(BOB), U256::from(100));
+    }
+
+    #[test]
+    fn test_permit() {
+        use alloy_primitives::b256;
+        use alloy_sol_types::eip712_domain;
+
+        assert_eq!(
+            PERMIT_TYPE_HASH,
+            b256!("6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9")
+        );
+        let domain = eip712_domain! {
+            name: "Test Token",
+            version: "1",
+            chain_id: 42161,
+            verifying_contract: Address::ZERO,
+        };
+        assert_eq!(Erc20::<Params>::domain_separator(), domain.separator());
+
+        // permits are rejected once their deadline passes
+        let mut token = token();
+        let deadline = U256::from(crate::block::timestamp() - 1);
+        let result = token.permit(ALICE, BOB, U256::MAX, deadline, 27, B256::ZERO, B256::ZERO);
+        assert!(matches!(result, Err(Erc20Error::ExpiredSignature(_))));
+        assert_eq!(token.nonces(ALICE), U256::ZERO);
+        assert_eq!(token.allowance(ALICE, BOB), U256::ZERO);
+    }
+}
@@ -0,0 +1,709 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Implementation of the [ERC-4626] tokenized vault standard.
+//!
+//! An [`Erc4626`] vault holds an underlying ERC-20 asset and issues shares for it, which are
+//! themselves an [`Erc20`] token. The contract embeds both components side by side and
+//! inherits both, since the vault's methods mint and burn shares.
+//!
+//! ```ignore
+//! struct VaultParams;
+//!
+//! impl Erc20Params for VaultParams {
+//!     const NAME: &'static str = "Vault USDC";
+//!     const SYMBOL: &'static str = "vUSDC";
+//!     const DECIMALS: u8 = 6;
+//! }
+//!
+//! impl Erc4626Params for VaultParams {}
+//!
+//! #[storage]
+//! #[entrypoint]
+//! struct Vault {
+//!     #[borrow]
+//!     shares: Erc20<VaultParams>,
+//!     #[borrow]
+//!     vault: Erc4626<VaultParams>,
+//! }
+//!
+//! #[public]
+//! #[inherit(Erc4626<VaultParams>, Erc20<VaultParams>)]
+//! impl Vault { ... }
+//! ```
+//!
+//! The contract sets the underlying asset via [`Erc4626::_set_asset`], typically from an
+//! initialization method.
+//!
+//! # Rounding and inflation attacks
+//!
+//! Conversions between assets and shares always round in the vault's favor. Deposits round the
+//! shares minted down, while withdrawals round the shares burned up.
+//!
+//! An empty vault is susceptible to the first depositor "donating" assets to inflate the value
+//! of a share, so that later deposits round down to few or no shares. As in OpenZeppelin's
+//! implementation, conversions account for a virtual share and a virtual asset, which makes
+//! such attacks unprofitable. Setting [`Erc4626Params::DECIMALS_OFFSET`] scales the virtual
+//! shares by a power of ten, which also makes them orders of magnitude more expensive.
+//!
+//! [ERC-4626]: https://eips.ethereum.org/EIPS/eip-4626
+
+use super::{
+    erc20::{Erc20, Erc20Params},
+    safe_erc20::{safe_transfer, safe_transfer_from},
+};
+use crate::{
+    contract, evm,
+    math::{self, mul_div, MathError, Rounding},
+    msg,
+    storage::{StorageAddress, TopLevelStorage},
+    stylus_proc::{public, storage, SolidityError},
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::sol;
+use core::{
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+};
+use interface::IERC20;
+
+pub use super::safe_erc20::SafeERC20FailedOperation;
+
+/// Configures an [`Erc4626`] vault, whose shares are given by its [`Erc20Params`].
+///
+/// [`DECIMALS`](Erc20Params::DECIMALS) should be those of the asset. As in OpenZeppelin's
+/// implementation, the vault reports the shares' decimals as these plus
+/// [`DECIMALS_OFFSET`](Self::DECIMALS_OFFSET).
+pub trait Erc4626Params: Erc20Params {
+    /// The number of decimals by which shares are more precise than assets.
+    ///
+    /// Raising this makes inflation attacks more expensive, at the cost of larger share amounts.
+    const DECIMALS_OFFSET: u8 = 0;
+}
+
+mod interface {
+    use stylus_proc::sol_interface;
+
+    sol_interface! {
+        interface IERC20 {
+            function balanceOf(address account) external view returns (uint256);
+        }
+    }
+}
+
+sol! {
+    /// Emitted when `sender` deposits `assets`, minting `shares` to `owner`.
+    event Deposit(address indexed sender, address indexed owner, uint256 assets, uint256 shares);
+
+    /// Emitted when `sender` burns `owner`'s `shares`, withdrawing `assets` to `receiver`.
+    event Withdraw(
+        address indexed sender,
+        address indexed receiver,
+        address indexed owner,
+        uint256 assets,
+        uint256 shares
+    );
+
+    /// The deposit is more than `receiver` may deposit.
+    #[allow(missing_docs)]
+    error ERC4626ExceededMaxDeposit(address receiver, uint256 assets, uint256 max);
+
+    /// The mint is more than `receiver` may mint.
+    #[allow(missing_docs)]
+    error ERC4626ExceededMaxMint(address receiver, uint256 shares, uint256 max);
+
+    /// The withdrawal is more than `owner` may withdraw.
+    #[allow(missing_docs)]
+    error ERC4626ExceededMaxWithdraw(address owner, uint256 assets, uint256 max);
+
+    /// The redemption is more than `owner` may redeem.
+    #[allow(missing_docs)]
+    error ERC4626ExceededMaxRedeem(address owner, uint256 shares, uint256 max);
+}
+
+/// Represents the ways [`Erc4626`] methods may fail, apart from those of [`Erc20`].
+#[derive(SolidityError)]
+pub enum Erc4626Error {
+    /// The deposit is too large.
+    ExceededMaxDeposit(ERC4626ExceededMaxDeposit),
+    /// The mint is too large.
+    ExceededMaxMint(ERC4626ExceededMaxMint),
+    /// The withdrawal is too large.
+    ExceededMaxWithdraw(ERC4626ExceededMaxWithdraw),
+    /// The redemption is too large.
+    ExceededMaxRedeem(ERC4626ExceededMaxRedeem),
+    /// The underlying asset couldn't be transferred.
+    FailedOperation(SafeERC20FailedOperation),
+}
+
+/// An ERC-4626 vault whose shares are an [`Erc20<P>`].
+#[storage]
+pub struct Erc4626<P> {
+    asset: StorageAddress,
+    params: PhantomData<P>,
+}
+
+impl<P: Erc4626Params> Erc4626<P> {
+    /// Sets the underlying asset. Contracts call this once, when initializing the vault.
+    pub fn _set_asset(&mut self, asset: Address) {
+        self.asset.set(asset);
+    }
+
+    fn vault<S: Borrow<Self>>(storage: &S) -> &Self {
+        storage.borrow()
+    }
+
+    fn shares<S: Borrow<Erc20<P>>>(storage: &S) -> &Erc20<P> {
+        storage.borrow()
+    }
+
+    fn shares_mut<S: BorrowMut<Erc20<P>>>(storage: &mut S) -> &mut Erc20<P> {
+        storage.borrow_mut()
+    }
+
+    /// The share supply and assets conversions use, including the virtual share and asset.
+    fn virtual_totals<S>(storage: &S) -> Result<(U256, U256), Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        let virtual_shares = math::pow(U256::from(10), P::DECIMALS_OFFSET.into())?;
+        let supply = Self::shares(storage).total_supply();
+        let supply = supply
+            .checked_add(virtual_shares)
+            .ok_or(MathError::Overflow)?;
+        let total_assets = Self::total_assets(storage)?;
+        let total_assets = total_assets
+            .checked_add(U256::from(1))
+            .ok_or(MathError::Overflow)?;
+        Ok((supply, total_assets))
+    }
+
+    /// Converts assets to shares, rounding as requested.
+    pub fn _convert_to_shares<S>(
+        storage: &S,
+        assets: U256,
+        rounding: Rounding,
+    ) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        let (supply, total_assets) = Self::virtual_totals(storage)?;
+        mul_div(assets, supply, total_assets, rounding).map_err(Into::into)
+    }
+
+    /// Converts shares to assets, rounding as requested.
+    pub fn _convert_to_assets<S>(
+        storage: &S,
+        shares: U256,
+        rounding: Rounding,
+    ) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        let (supply, total_assets) = Self::virtual_totals(storage)?;
+        mul_div(shares, total_assets, supply, rounding).map_err(Into::into)
+    }
+
+    /// Pulls `assets` from `caller` and mints `shares` to `receiver`, emitting [`Deposit`].
+    pub fn _deposit<S>(
+        storage: &mut S,
+        caller: Address,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Vec<u8>>
+    where
+        S: TopLevelStorage + BorrowMut<Self> + BorrowMut<Erc20<P>>,
+    {
+        // pull the assets first, so that reentrant calls see the updated balance
+        let asset = Self::vault(storage).asset();
+        safe_transfer_from(storage, asset, caller, contract::address(), assets)?;
+
+        Self::shares_mut(storage)._mint(receiver, shares)?;
+        evm::log(Deposit {
+            sender: caller,
+            owner: receiver,
+            assets,
+            shares,
+        });
+        Ok(())
+    }
+
+    /// Burns `owner`'s `shares` and sends `assets` to `receiver`, emitting [`Withdraw`].
+    ///
+    /// Spends `caller`'s allowance over the shares unless `caller` is the owner.
+    pub fn _withdraw<S>(
+        storage: &mut S,
+        caller: Address,
+        receiver: Address,
+        owner: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Vec<u8>>
+    where
+        S: TopLevelStorage + BorrowMut<Self> + BorrowMut<Erc20<P>>,
+    {
+        if caller != owner {
+            Self::shares_mut(storage)._spend_allowance(owner, caller, shares)?;
+        }
+
+        // burn the shares first, so that reentrant calls see the reduced supply
+        Self::shares_mut(storage)._burn(owner, shares)?;
+        let asset = Self::vault(storage).asset();
+        safe_transfer(storage, asset, receiver, assets)?;
+
+        evm::log(Withdraw {
+            sender: caller,
+            receiver,
+            owner,
+            assets,
+            shares,
+        });
+        Ok(())
+    }
+}
+
+#[public]
+#[inherit(Erc20<P>)]
+impl<P: Erc4626Params> Erc4626<P> {
+    /// The underlying asset.
+    pub fn asset(&self) -> Address {
+        self.asset.get()
+    }
+
+    /// The number of decimals shares are displayed with, which is the asset's plus the offset.
+    pub fn decimals() -> u8 {
+        P::DECIMALS + P::DECIMALS_OFFSET
+    }
+
+    /// The amount of the asset the vault holds.
+    pub fn total_assets<S>(storage: &S) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self>,
+    {
+        let asset = IERC20::new(Self::vault(storage).asset());
+        Ok(asset.balance_of(storage, contract::address())?)
+    }
+
+    /// The shares the vault would exchange for `assets`, rounded down.
+    pub fn convert_to_shares<S>(storage: &S, assets: U256) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        Self::_convert_to_shares(storage, assets, Rounding::Floor)
+    }
+
+    /// The assets the vault would exchange for `shares`, rounded down.
+    pub fn convert_to_assets<S>(storage: &S, shares: U256) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        Self::_convert_to_assets(storage, shares, Rounding::Floor)
+    }
+
+    /// The most `receiver` may deposit, which is unlimited.
+    pub fn max_deposit(&self, _receiver: Address) -> U256 {
+        U256::MAX
+    }
+
+    /// The most `receiver` may mint, which is unlimited.
+    pub fn max_mint(&self, _receiver: Address) -> U256 {
+        U256::MAX
+    }
+
+    /// The most assets `owner` may withdraw, which is the value of their shares.
+    pub fn max_withdraw<S>(storage: &S, owner: Address) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        let shares = Self::shares(storage).balance_of(owner);
+        Self::_convert_to_assets(storage, shares, Rounding::Floor)
+    }
+
+    /// The most shares `owner` may redeem, which is their balance.
+    pub fn max_redeem<S>(storage: &S, owner: Address) -> U256
+    where
+        S: TopLevelStorage + Borrow<Erc20<P>>,
+    {
+        Self::shares(storage).balance_of(owner)
+    }
+
+    /// The shares a deposit of `assets` would mint, rounded down.
+    pub fn preview_deposit<S>(storage: &S, assets: U256) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        Self::_convert_to_shares(storage, assets, Rounding::Floor)
+    }
+
+    /// The assets minting `shares` would cost, rounded up.
+    pub fn preview_mint<S>(storage: &S, shares: U256) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        Self::_convert_to_assets(storage, shares, Rounding::Ceil)
+    }
+
+    /// The shares a withdrawal of `assets` would burn, rounded up.
+    pub fn preview_withdraw<S>(storage: &S, assets: U256) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        Self::_convert_to_shares(storage, assets, Rounding::Ceil)
+    }
+
+    /// The assets redeeming `shares` would return, rounded down.
+    pub fn preview_redeem<S>(storage: &S, shares: U256) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + Borrow<Self> + Borrow<Erc20<P>>,
+    {
+        Self::_convert_to_assets(storage, shares, Rounding::Floor)
+    }
+
+    /// Deposits `assets` from the caller, minting shares to `receiver`.
+    pub fn deposit<S>(storage: &mut S, assets: U256, receiver: Address) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + BorrowMut<Self> + BorrowMut<Erc20<P>>,
+    {
+        let max = Self::vault(storage).max_deposit(receiver);
+        if assets > max {
+            let err = ERC4626ExceededMaxDeposit {
+                receiver,
+                assets,
+                max,
+            };
+            return Err(Erc4626Error::ExceededMaxDeposit(err).into());
+        }
+        let shares = Self::preview_deposit(storage, assets)?;
+        Self::_deposit(storage, msg::sender(), receiver, assets, shares)?;
+        Ok(shares)
+    }
+
+    /// Mints exactly `shares` to `receiver`, depositing the assets they cost from the caller.
+    pub fn mint<S>(storage: &mut S, shares: U256, receiver: Address) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + BorrowMut<Self> + BorrowMut<Erc20<P>>,
+    {
+        let max = Self::vault(storage).max_mint(receiver);
+        if shares > max {
+            let err = ERC4626ExceededMaxMint {
+                receiver,
+                shares,
+                max,
+            };
+            return Err(Erc4626Error::ExceededMaxMint(err).into());
+        }
+        let assets = Self::preview_mint(storage, shares)?;
+        Self::_deposit(storage, msg::sender(), receiver, assets, shares)?;
+        Ok(assets)
+    }
+
+    /// Withdraws exactly `assets` to `receiver`, burning the shares they cost from `owner`.
+    pub fn withdraw<S>(
+        storage: &mut S,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + BorrowMut<Self> + BorrowMut<Erc20<P>>,
+    {
+        let max = Self::max_withdraw(storage, owner)?;
+        if assets > max {
+            let err = ERC4626ExceededMaxWithdraw { owner, assets, max };
+            return Err(Erc4626Error::ExceededMaxWithdraw(err).into());
+        }
+        let shares = Self::preview_withdraw(storage, assets)?;
+        Self::_withdraw(storage, msg::sender(), receiver, owner, assets, shares)?;
+        Ok(shares)
+    }
+
+    /// Redeems `owner`'s `shares`, sending the assets they're worth to `receiver`.
+    pub fn redeem<S>(
+        storage: &mut S,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Vec<u8>>
+    where
+        S: TopLevelStorage + BorrowMut<Self> + BorrowMut<Erc20<P>>,
+    {
+        let max = Self::max_redeem(storage, owner);
+        if shares > max {
+            let err = ERC4626ExceededMaxRedeem { owner, shares, max };
+            return Err(Erc4626Error::ExceededMaxRedeem(err).into());
+        }
+        let assets = Self::preview_redeem(storage, shares)?;
+        Self::_withdraw(storage, msg::sender(), receiver, owner, assets, shares)?;
+        Ok(assets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abi::{self, AbiType, Router},
+        storage::StorageType,
+        test_host,
+    };
+    use alloy_sol_types::SolType;
+
+    const ALICE: Address = Address::repeat_byte(0xa1);
+    const BOB: Address = Address::repeat_byte(0xb0);
+
+    struct Params;
+
+    impl Erc20Params for Params {
+        const NAME: &'static str = "Vault Shares";
+        const SYMBOL: &'static str = "vTEST";
+        const DECIMALS: u8 = 18;
+    }
+
+    impl Erc4626Params for Params {
+        const DECIMALS_OFFSET: u8 = 3;
+    }
+
+    #[storage]
+    struct Vault {
+        #[borrow]
+        shares: Erc20<Params>,
+        #[borrow]
+        vault: Erc4626<Params>,
+    }
+
+    unsafe impl TopLevelStorage for Vault {}
+
+    #[public]
+    #[inherit(Erc4626<Params>, Erc20<Params>)]
+    impl Vault {}
+
+    /// Makes a vault over a mocked asset.
+    fn vault(asset: Address) -> Vault {
+        let mut vault = unsafe { Vault::new(U256::ZERO, 0) };
+        vault.vault._set_asset(asset);
+        test_host::set_code(asset, b"asset");
+        vault
+    }
+
+    /// Encodes a `uint256`, or a `bool` when 0 or 1, as the asset returns it.
+    fn word(value: u64) -> Result<Vec<u8>, Vec<u8>> {
+        Ok(U256::from(value).to_be_bytes_vec())
+    }
+
+    /// The calldata of the last call, which should be to `asset`.
+    fn last_call(asset: Address) -> Vec<u8> {
+        let (to, data, _) = test_host::calls().pop().unwrap();
+        assert_eq!(to, asset);
+        data
+    }
+
+    #[test]
+    fn test_composition() {
+        const ASSET: Address = Address::repeat_byte(0xaa);
+
+        let mut vault = unsafe { Vault::new(U256::ZERO, 0) };
+        vault.vault._set_asset(ASSET);
+        assert!(vault.shares._mint(ALICE, U256::from(5)).is_ok());
+
+        let mut call = |selector: [u8; 4], input: &[u8]| {
+            let selector = u32::from_be_bytes(selector);
+            Vault::route(&mut vault, selector, input).unwrap().unwrap()
+        };
+
+        // vault methods that don't call the asset
+        let asset = call(function_selector!("asset"), &[]);
+        assert_eq!(asset, ASSET.into_word().to_vec());
+        let owner = <(Address,) as AbiType>::SolType::abi_encode_params(&(ALICE,));
+        let max_redeem = call(function_selector!("maxRedeem", Address), &owner);
+        assert_eq!(U256::from_be_slice(&max_redeem), U256::from(5));
+
+        // the shares' decimals include the offset
+        let decimals = call(function_selector!("decimals"), &[]);
+        assert_eq!(U256::from_be_slice(&decimals), U256::from(21));
+
+        // inherited share methods
+        let supply = call(function_selector!("totalSupply"), &[]);
+        assert_eq!(U256::from_be_slice(&supply), U256::from(5));
+    }
+
+    #[test]
+    fn test_conversion_overflow() {
+        const ASSET: Address = Address::repeat_byte(0xab);
+
+        let mut vault = unsafe { Vault::new(U256::ZERO, 0) };
+        vault.vault._set_asset(ASSET);
+        let balance = |amount: U256| Ok(amount.to_be_bytes::<32>().to_vec());
+
+        // the virtual share can't be added to a full supply
+        crate::test_host::mock_call(ASSET, balance(U256::from(7)));
+        let alice = Address::repeat_byte(0xa2);
+        assert!(vault.shares._mint(alice, U256::MAX).is_ok());
+        let overflow: Vec<u8> = MathError::Overflow.into();
+        let result = Erc4626::<Params>::convert_to_shares(&vault, U256::from(1));
+        assert_eq!(result, Err(overflow.clone()));
+
+        // nor the virtual asset to a full vault
+        let mut vault = unsafe { Vault::new(U256::from(0x7171), 0) };
+        vault.vault._set_asset(ASSET);
+        crate::test_host::mock_call(ASSET, balance(U256::MAX));
+        let result = Erc4626::<Params>::convert_to_assets(&vault, U256::from(1));
+        assert_eq!(result, Err(overflow));
+    }
+
+    #[test]
+    fn test_rounding() {
+        const ASSET: Address = Address::repeat_byte(0xac);
+        type Vault4626 = Erc4626<Params>;
+
+        // 1000 shares and 2 assets, so with the 1000 virtual shares and the virtual asset,
+        // an asset is worth 2000 / 3 shares
+        let mut vault = vault(ASSET);
+        assert!(vault.shares._mint(BOB, U256::from(1000)).is_ok());
+        test_host::mock_call(ASSET, word(2));
+        let one = U256::from(1);
+
+        // the vault gets the remainder, whichever way the conversion goes
+        let deposit = Vault4626::preview_deposit(&vault, one);
+        let mint = Vault4626::preview_mint(&vault, U256::from(666));
+        let withdraw = Vault4626::preview_withdraw(&vault, one);
+        let redeem = Vault4626::preview_redeem(&vault, U256::from(667));
+        assert_eq!(deposit, Ok(U256::from(666)));
+        assert_eq!(mint, Ok(one));
+        assert_eq!(withdraw, Ok(U256::from(667)));
+        assert_eq!(redeem, Ok(one));
+
+        // the conversions themselves round down
+        assert_eq!(Vault4626::convert_to_shares(&vault, one), deposit);
+        let assets = Vault4626::convert_to_assets(&vault, U256::from(666));
+        assert_eq!(assets, Ok(U256::ZERO));
+    }
+
+    #[test]
+    fn test_deposits_and_withdrawals() {
+        const ASSET: Address = Address::repeat_byte(0xad);
+        const CAROL: Address = Address::repeat_byte(0xca);
+        type Vault4626 = Erc4626<Params>;
+
+        let mut vault = vault(ASSET);
+        let vault_address = contract::address();
+        let transfer_from = |from: Address, assets: u64| {
+            let selector = function_selector!("transferFrom", Address, Address, U256);
+            abi::encode_call(selector, &(from, vault_address, U256::from(assets)))
+        };
+        let transfer = |to: Address, assets: u64| {
+            let selector = function_selector!("transfer", Address, U256);
+            abi::encode_call(selector, &(to, U256::from(assets)))
+        };
+        let shares_of = |vault: &Vault, owner: Address| vault.shares.balance_of(owner);
+
+        // depositing into the empty vault mints 1000 shares per asset, given the offset
+        test_host::set_sender(ALICE);
+        test_host::mock_call_sequence(ASSET, [word(0), word(1)]);
+        let shares = Vault4626::deposit(&mut vault, U256::from(100), BOB);
+        assert_eq!(shares, Ok(U256::from(100_000)));
+        assert_eq!(last_call(ASSET), transfer_from(ALICE, 100));
+        assert_eq!(shares_of(&vault, BOB), U256::from(100_000));
+
+        // minting 1500 shares costs 1500 * 101 / 101000 assets, rounded up
+        test_host::mock_call_sequence(ASSET, [word(100), word(1)]);
+        let assets = Vault4626::mint(&mut vault, U256::from(1500), ALICE);
+        assert_eq!(assets, Ok(U256::from(2)));
+        assert_eq!(last_call(ASSET), transfer_from(ALICE, 2));
+        assert_eq!(shares_of(&vault, ALICE), U256::from(1500));
+
+        // withdrawing an asset burns 102500 / 103 shares, rounded up
+        test_host::set_sender(BOB);
+        test_host::mock_call_sequence(ASSET, [word(102), word(102), word(1)]);
+        let shares = Vault4626::withdraw(&mut vault, U256::from(1), CAROL, BOB);
+        assert_eq!(shares, Ok(U256::from(996)));
+        assert_eq!(last_call(ASSET), transfer(CAROL, 1));
+        assert_eq!(shares_of(&vault, BOB), U256::from(99_004));
+
+        // redeeming 1500 shares returns 1500 * 102 / 101504 assets, rounded down
+        test_host::set_sender(ALICE);
+        test_host::mock_call_sequence(ASSET, [word(101), word(1)]);
+        let assets = Vault4626::redeem(&mut vault, U256::from(1500), ALICE, ALICE);
+        assert_eq!(assets, Ok(U256::from(1)));
+        assert_eq!(last_call(ASSET), transfer(ALICE, 1));
+        assert_eq!(shares_of(&vault, ALICE), U256::ZERO);
+
+        // owners can't redeem more than they hold
+        let calls = test_host::calls().len();
+        let result = Vault4626::redeem(&mut vault, U256::from(1), ALICE, ALICE);
+        let err = ERC4626ExceededMaxRedeem {
+            owner: ALICE,
+            shares: U256::from(1),
+            max: U256::ZERO,
+        };
+        assert_eq!(result, Err(Erc4626Error::ExceededMaxRedeem(err).into()));
+        assert_eq!(test_host::calls().len(), calls);
+
+        // nor can others spend their shares without an allowance
+        test_host::mock_call(ASSET, word(101));
+        let result = Vault4626::withdraw(&mut vault, U256::from(1), ALICE, BOB);
+        assert!(result.is_err());
+        assert_eq!(shares_of(&vault, BOB), U256::from(99_004));
+
+        let deposits = test_host::events::<Deposit>();
+        let deposits: Vec<_> = deposits
+            .iter()
+            .map(|event| (event.sender, event.owner, event.assets, event.shares))
+            .collect();
+        assert_eq!(
+            deposits,
+            [
+                (ALICE, BOB, U256::from(100), U256::from(100_000)),
+                (ALICE, ALICE, U256::from(2), U256::from(1500)),
+            ]
+        );
+        let withdrawals = test_host::events::<Withdraw>();
+        let withdrawals: Vec<_> = withdrawals
+            .iter()
+            .map(|event| {
+                (
+                    event.sender,
+                    event.receiver,
+                    event.owner,
+                    event.assets,
+                    event.shares,
+                )
+            })
+            .collect();
+        assert_eq!(
+            withdrawals,
+            [
+                (BOB, CAROL, BOB, U256::from(1), U256::from(996)),
+                (ALICE, ALICE, ALICE, U256::from(1), U256::from(1500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inflation_attack() {
+        const ASSET: Address = Address::repeat_byte(0xae);
+        type Vault4626 = Erc4626<Params>;
+
+        // the attacker deposits an asset into the empty vault
+        let mut vault = vault(ASSET);
+        test_host::set_sender(ALICE);
+        test_host::mock_call_sequence(ASSET, [word(0), word(1)]);
+        let shares = Vault4626::deposit(&mut vault, U256::from(1), ALICE);
+        assert_eq!(shares, Ok(U256::from(1000)));
+
+        // then donates 10000 assets ahead of the victim depositing as many, who still gets
+        // 10000 * 2000 / 10002 shares rather than none
+        test_host::set_sender(BOB);
+        test_host::mock_call_sequence(ASSET, [word(10_001), word(1)]);
+        let shares = Vault4626::deposit(&mut vault, U256::from(10_000), BOB);
+        assert_eq!(shares, Ok(U256::from(1999)));
+
+        // the virtual shares capture most of the donation, so the attack loses money
+        test_host::mock_call(ASSET, word(20_001));
+        let attacker = Vault4626::preview_redeem(&vault, U256::from(1000));
+        let victim = Vault4626::preview_redeem(&vault, U256::from(1999));
+        assert_eq!(attacker, Ok(U256::from(5001)));
+        assert_eq!(victim, Ok(U256::from(9998)));
+    }
+}
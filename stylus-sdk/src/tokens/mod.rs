@@ -0,0 +1,30 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Reusable implementations of token standards.
+//!
+//! As with the [`access`](crate::access) components, each token is a [`#[storage]`][storage]
+//! type meant to be embedded in a contract with `#[borrow]` and inherited via
+//! [`#[inherit]`][inherit]. Internal methods, prefixed with an underscore, are left for the
+//! contract to call from its own methods.
+//!
+//! The [`safe_erc20`] helpers, like [`safe_transfer`] and [`force_approve`], call other tokens,
+//! tolerating those that don't return a `bool`.
+//!
+//! Note that this code is unaudited.
+//!
+//! [storage]: macro@crate::prelude::storage
+//! [inherit]: macro@crate::prelude::public
+
+pub use erc1155::{Erc1155, Erc1155Error};
+pub use erc20::{Erc20, Erc20Error, Erc20Params};
+pub use erc4626::{Erc4626, Erc4626Error, Erc4626Params};
+pub use safe_erc20::{
+    force_approve, safe_decrease_allowance, safe_increase_allowance, safe_transfer,
+    safe_transfer_from,
+};
+
+pub mod erc1155;
+pub mod erc20;
+pub mod erc4626;
+pub mod safe_erc20;
@@ -0,0 +1,280 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Calls to ERC-20 tokens that tolerate non-standard return values, like OpenZeppelin's
+//! [`SafeERC20`].
+//!
+//! Some tokens, notably USDT, return nothing from `transfer` and `transferFrom` instead of a
+//! `bool`, which makes calls through a [`sol_interface!`] fail to decode. Others return `false`
+//! rather than reverting. The helpers here treat empty return data as success, as long as the
+//! token has code, and fail with [`SafeERC20FailedOperation`] when it returns anything but `true`.
+//! Reverts are passed on with the token's revert data.
+//!
+//! Approvals go through [`force_approve`], which also handles tokens that refuse to change a
+//! nonzero allowance to another nonzero value, and [`safe_increase_allowance`] and
+//! [`safe_decrease_allowance`] build on it.
+//!
+//! ```ignore
+//! #[public]
+//! impl Escrow {
+//!     pub fn release(&mut self, token: Address, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+//!         safe_transfer(self, token, to, amount)
+//!     }
+//! }
+//! ```
+//!
+//! [`SafeERC20`]: https://docs.openzeppelin.com/contracts/5.x/api/token/erc20#SafeERC20
+//! [`sol_interface!`]: crate::prelude::sol_interface
+
+use crate::{
+    abi::{self, AbiType},
+    call::{self, Call},
+    contract,
+    math::MathError,
+    storage::TopLevelStorage,
+    types::AddressVM,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{sol, SolError, SolType};
+
+sol! {
+    /// A call to `token` returned something other than `true`, or it has no code.
+    #[allow(missing_docs)]
+    error SafeERC20FailedOperation(address token);
+
+    /// Decreasing `spender`'s allowance by `requestedDecrease` would take it below zero.
+    #[allow(missing_docs)]
+    error SafeERC20FailedDecreaseAllowance(
+        address spender,
+        uint256 currentAllowance,
+        uint256 requestedDecrease
+    );
+}
+
+/// Transfers `value` of `token` from the contract to `to`.
+pub fn safe_transfer(
+    storage: &mut impl TopLevelStorage,
+    token: Address,
+    to: Address,
+    value: U256,
+) -> Result<(), Vec<u8>> {
+    let selector = function_selector!("transfer", Address, U256);
+    call_optional_return(storage, token, &abi::encode_call(selector, &(to, value)))
+}
+
+/// Transfers `value` of `token` from `from` to `to`, spending the contract's allowance.
+pub fn safe_transfer_from(
+    storage: &mut impl TopLevelStorage,
+    token: Address,
+    from: Address,
+    to: Address,
+    value: U256,
+) -> Result<(), Vec<u8>> {
+    let selector = function_selector!("transferFrom", Address, Address, U256);
+    call_optional_return(
+        storage,
+        token,
+        &abi::encode_call(selector, &(from, to, value)),
+    )
+}
+
+/// Sets the contract's allowance for `spender` over `token` to `value`.
+///
+/// Tokens like USDT revert when changing a nonzero allowance to another nonzero value, to guard
+/// against a spender using both. If the approval fails, the allowance is reset to zero and the
+/// approval is tried again.
+pub fn force_approve(
+    storage: &mut impl TopLevelStorage,
+    token: Address,
+    spender: Address,
+    value: U256,
+) -> Result<(), Vec<u8>> {
+    let selector = function_selector!("approve", Address, U256);
+    let approve = |value: U256| abi::encode_call(selector, &(spender, value));
+    if call_optional_return(storage, token, &approve(value)).is_ok() {
+        return Ok(());
+    }
+    call_optional_return(storage, token, &approve(U256::ZERO))?;
+    call_optional_return(storage, token, &approve(value))
+}
+
+/// Raises the contract's allowance for `spender` over `token` by `value`, failing with a
+/// `Panic` if it would overflow.
+pub fn safe_increase_allowance(
+    storage: &mut impl TopLevelStorage,
+    token: Address,
+    spender: Address,
+    value: U256,
+) -> Result<(), Vec<u8>> {
+    let allowance = allowance(storage, token, spender)?;
+    let allowance = allowance.checked_add(value).ok_or(MathError::Overflow)?;
+    force_approve(storage, token, spender, allowance)
+}
+
+/// Lowers the contract's allowance for `spender` over `token` by `value`, failing with
+/// [`SafeERC20FailedDecreaseAllowance`] if it's less than that.
+pub fn safe_decrease_allowance(
+    storage: &mut impl TopLevelStorage,
+    token: Address,
+    spender: Address,
+    value: U256,
+) -> Result<(), Vec<u8>> {
+    let allowance = allowance(storage, token, spender)?;
+    let Some(lowered) = allowance.checked_sub(value) else {
+        let err = SafeERC20FailedDecreaseAllowance {
+            spender,
+            currentAllowance: allowance,
+            requestedDecrease: value,
+        };
+        return Err(err.abi_encode());
+    };
+    force_approve(storage, token, spender, lowered)
+}
+
+/// Reads the contract's allowance for `spender` over `token`.
+fn allowance(
+    storage: &impl TopLevelStorage,
+    token: Address,
+    spender: Address,
+) -> Result<U256, Vec<u8>> {
+    let selector = function_selector!("allowance", Address, Address);
+    let data = abi::encode_call(selector, &(contract::address(), spender));
+    let output = call::static_call(storage, token, &data)?;
+    let allowance = <U256 as AbiType>::SolType::abi_decode(&output, true);
+    let allowance = allowance.map_err(call::Error::from)?;
+    Ok(allowance)
+}
+
+/// Calls `token`, requiring it to return `true` or nothing at all.
+fn call_optional_return(
+    storage: &mut impl TopLevelStorage,
+    token: Address,
+    data: &[u8],
+) -> Result<(), Vec<u8>> {
+    let output = call::call(Call::new_in(storage), token, data)?;
+    let success = match output.get(..32) {
+        Some(word) => U256::from_be_slice(word) == U256::from(1),
+        // a call to an account without code always succeeds with no output
+        None => output.is_empty() && token.has_code(),
+    };
+    match success {
+        true => Ok(()),
+        false => Err(SafeERC20FailedOperation { token }.abi_encode()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_host;
+    use alloc::vec;
+
+    struct Contract;
+
+    unsafe impl TopLevelStorage for Contract {}
+
+    #[test]
+    fn test_safe_transfer() {
+        let token = Address::repeat_byte(0x20);
+        let (alice, bob) = (Address::repeat_byte(0xa1), Address::repeat_byte(0xb0));
+        let value = U256::from(7);
+        test_host::set_code(token, b"token");
+        let failed = SafeERC20FailedOperation { token }.abi_encode();
+
+        // standard tokens return true
+        test_host::mock_call(token, Ok(U256::from(1).to_be_bytes_vec()));
+        assert_eq!(safe_transfer(&mut Contract, token, bob, value), Ok(()));
+        let (to, data, _) = test_host::calls().pop().unwrap();
+        assert_eq!(to, token);
+        let selector = function_selector!("transfer", Address, U256);
+        assert_eq!(data, abi::encode_call(selector, &(bob, value)));
+
+        // others return nothing
+        test_host::mock_call(token, Ok(vec![]));
+        assert_eq!(
+            safe_transfer_from(&mut Contract, token, alice, bob, value),
+            Ok(())
+        );
+        let (_, data, _) = test_host::calls().pop().unwrap();
+        let selector = function_selector!("transferFrom", Address, Address, U256);
+        assert_eq!(data, abi::encode_call(selector, &(alice, bob, value)));
+
+        // returning false or garbage fails
+        test_host::mock_call(token, Ok(U256::ZERO.to_be_bytes_vec()));
+        let result = safe_transfer(&mut Contract, token, bob, value);
+        assert_eq!(result, Err(failed.clone()));
+        test_host::mock_call(token, Ok(vec![1]));
+        let result = safe_transfer(&mut Contract, token, bob, value);
+        assert_eq!(result, Err(failed));
+
+        // reverts are passed on
+        test_host::mock_call(token, Err(b"insufficient balance".to_vec()));
+        let result = safe_transfer_from(&mut Contract, token, alice, bob, value);
+        assert_eq!(result, Err(b"insufficient balance".to_vec()));
+
+        // accounts without code aren't tokens
+        let account = Address::repeat_byte(0xee);
+        test_host::mock_call(account, Ok(vec![]));
+        let failed = SafeERC20FailedOperation { token: account }.abi_encode();
+        let result = safe_transfer(&mut Contract, account, bob, value);
+        assert_eq!(result, Err(failed));
+    }
+
+    #[test]
+    fn test_approvals() {
+        let token = Address::repeat_byte(0x20);
+        let spender = Address::repeat_byte(0x5e);
+        test_host::set_code(token, b"token");
+        let word = |value: u64| U256::from(value).to_be_bytes_vec();
+        let approve = |value: u64| {
+            let selector = function_selector!("approve", Address, U256);
+            abi::encode_call(selector, &(spender, U256::from(value)))
+        };
+        let last_calls = |count: usize| -> Vec<_> {
+            let calls = test_host::calls();
+            let calls = calls[calls.len() - count..].iter();
+            calls.map(|(_, data, _)| data.clone()).collect()
+        };
+
+        // standard tokens approve directly
+        test_host::mock_call(token, Ok(word(1)));
+        let result = force_approve(&mut Contract, token, spender, U256::from(5));
+        assert_eq!(result, Ok(()));
+        assert_eq!(last_calls(1), [approve(5)]);
+
+        // others must be reset to zero first
+        let usdt = [Err(b"nonzero allowance".to_vec()), Ok(vec![]), Ok(vec![])];
+        test_host::mock_call_sequence(token, usdt);
+        let result = force_approve(&mut Contract, token, spender, U256::from(5));
+        assert_eq!(result, Ok(()));
+        assert_eq!(last_calls(3), [approve(5), approve(0), approve(5)]);
+
+        // allowances are adjusted from their current values
+        test_host::mock_call_sequence(token, [Ok(word(5)), Ok(word(1))]);
+        let result = safe_increase_allowance(&mut Contract, token, spender, U256::from(3));
+        assert_eq!(result, Ok(()));
+        let selector = function_selector!("allowance", Address, Address);
+        let query = abi::encode_call(selector, &(contract::address(), spender));
+        assert_eq!(last_calls(2), [query, approve(8)]);
+
+        test_host::mock_call_sequence(token, [Ok(word(5)), Ok(word(1))]);
+        let result = safe_decrease_allowance(&mut Contract, token, spender, U256::from(2));
+        assert_eq!(result, Ok(()));
+        assert_eq!(last_calls(1), [approve(3)]);
+
+        // allowances can't go below zero or overflow
+        test_host::mock_call(token, Ok(word(2)));
+        let result = safe_decrease_allowance(&mut Contract, token, spender, U256::from(3));
+        let err = SafeERC20FailedDecreaseAllowance {
+            spender,
+            currentAllowance: U256::from(2),
+            requestedDecrease: U256::from(3),
+        };
+        assert_eq!(result, Err(err.abi_encode()));
+
+        test_host::mock_call(token, Ok(U256::MAX.to_be_bytes_vec()));
+        let result = safe_increase_allowance(&mut Contract, token, spender, U256::from(1));
+        assert_eq!(result, Err(MathError::Overflow.into()));
+    }
+}
@@ -22,9 +22,9 @@ pub trait AddressVM {
     /// The balance in wei of the account.
     fn balance(&self) -> U256;
 
-    /// Gets the code at the given address. The semantics are equivalent to that of the EVM's [`EXT_CODESIZE`].
+    /// Gets the code at the given address. The semantics are equivalent to that of the EVM's [`EXT_CODECOPY`].
     ///
-    /// [`EXT_CODE_COPY`]: https://www.evm.codes/#3C
+    /// [`EXT_CODECOPY`]: https://www.evm.codes/#3C
     fn code(&self) -> Vec<u8>;
 
     /// Gets the size of the code in bytes at the given address. The semantics are equivalent
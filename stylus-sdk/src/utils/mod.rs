@@ -0,0 +1,60 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Helpers for the standard endpoints that off-chain tooling expects, and for producing output
+//! that people read.
+//!
+//! Note that this code is unaudited.
+
+pub use multicall::MulticallError;
+
+pub mod multicall;
+
+use crate::encoding::address_to_checksum_hex;
+use alloc::string::String;
+use alloy_primitives::Address;
+
+/// Formats an address as an [EIP-55] checksummed hex string, such as for a revert message or a
+/// token URI.
+///
+/// Letters in the address are uppercased wherever the corresponding nibble of the keccak256 hash
+/// of its lowercase hex is 8 or more, so that wallets can detect mistyped addresses. This is the
+/// same as [`address_to_checksum_hex`].
+///
+/// ```
+/// use stylus_sdk::{alloy_primitives::address, utils::to_checksum};
+///
+/// let owner = address!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+/// assert_eq!(to_checksum(owner), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+/// ```
+///
+/// [EIP-55]: https://eips.ethereum.org/EIPS/eip-55
+pub fn to_checksum(address: Address) -> String {
+    address_to_checksum_hex(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_checksum() {
+        // the examples of EIP-55
+        let checksummed = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0xde709f2102306220921060314715629080e2fb77",
+        ];
+        for expected in checksummed {
+            let address: Address = expected.to_lowercase().parse().unwrap();
+            assert_eq!(to_checksum(address), expected);
+        }
+        assert_eq!(
+            to_checksum(Address::ZERO),
+            "0x0000000000000000000000000000000000000000"
+        );
+    }
+}
@@ -0,0 +1,417 @@
+// Copyright 2024, Offchain Labs, Inc.
+// For licensing, see https://github.com/OffchainLabs/stylus-sdk-rs/blob/main/licenses/COPYRIGHT.md
+
+//! Batching of calls to the contract's own methods, with the signatures of [Multicall3].
+//!
+//! Each call must target the contract itself. Rather than being made externally, it's routed to
+//! the method with [`contract::call_self`], so [`msg::sender`](crate::msg::sender) is still the
+//! original caller, as with OpenZeppelin's `Multicall`. Calls to any other address fail with
+//! [`UnsupportedTarget`], since relaying them would let anyone act as the contract, spending its
+//! tokens and using its approvals and roles.
+//!
+//! The contract exposes whichever of the functions it wants from its own methods.
+//!
+//! ```ignore
+//! #[public]
+//! impl Contract {
+//!     pub fn aggregate3(
+//!         &mut self,
+//!         calls: Vec<(Address, bool, Bytes)>,
+//!     ) -> Result<Vec<(bool, Bytes)>, MulticallError> {
+//!         multicall::aggregate3(self, calls)
+//!     }
+//! }
+//! ```
+//!
+//! # Failing calls
+//!
+//! Calls that may fail run as part of the multicall, which succeeds even when they revert. With
+//! the `storage-transaction` feature, each runs in a [`StorageTransaction`], so that a call that
+//! reverts has its storage writes rolled back, as an external call's would be.
+//!
+//! **Without the feature, nothing undoes them.** A method that writes storage and then returns
+//! `Err` is reported as failed by `tryAggregate` and `aggregate3`, but the writes it made before
+//! failing are kept. Contracts that don't enable the feature should only expose `aggregate`,
+//! which reverts entirely when any call fails, unless each of their methods fails before
+//! writing anything. Either way, events a failed call emits are still logged.
+//!
+//! # Value
+//!
+//! Every call in the batch sees the same [`msg::value`](crate::msg::value), though it was only
+//! sent once. A payable method that credits `msg::value` would credit it once per call, letting
+//! a caller spend the same wei many times over. Keep the multicall methods themselves
+//! non-payable, so that calls with value revert before any of the batch runs.
+//!
+//! [Multicall3]: https://github.com/mds1/multicall
+//! [`StorageTransaction`]: crate::storage::StorageTransaction
+
+use crate::{
+    abi::{Bytes, Router},
+    block, contract,
+    storage::{StorageType, TopLevelStorage},
+    stylus_proc::SolidityError,
+    ArbResult,
+};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::sol;
+use core::borrow::BorrowMut;
+
+sol! {
+    /// The call at `index` reverted with `data`.
+    #[allow(missing_docs)]
+    error CallFailed(uint256 index, bytes data);
+
+    /// The call at `index` targets an address other than the contract.
+    #[allow(missing_docs)]
+    error UnsupportedTarget(uint256 index, address target);
+}
+
+/// Represents the ways multicalls may fail.
+#[derive(SolidityError)]
+pub enum MulticallError {
+    /// A call that had to succeed reverted.
+    CallFailed(CallFailed),
+    /// A call targets another contract.
+    UnsupportedTarget(UnsupportedTarget),
+}
+
+/// Makes each call, as Multicall3's `aggregate`, failing if any reverts. Returns the block number
+/// and each call's result.
+pub fn aggregate<S>(
+    storage: &mut S,
+    calls: Vec<(Address, Bytes)>,
+) -> Result<(U256, Vec<Bytes>), MulticallError>
+where
+    S: Router<S> + TopLevelStorage + StorageType + BorrowMut<S::Storage>,
+{
+    let calls = calls
+        .into_iter()
+        .map(|(target, data)| (target, false, data));
+    let results = batch(storage, calls)?;
+    let results = results.into_iter().map(|(_, data)| data).collect();
+    Ok((U256::from(block::number()), results))
+}
+
+/// Makes each call, as Multicall3's `tryAggregate`, failing if any reverts when
+/// `require_success` is set. Returns whether each call succeeded, along with its result or
+/// revert data.
+pub fn try_aggregate<S>(
+    storage: &mut S,
+    require_success: bool,
+    calls: Vec<(Address, Bytes)>,
+) -> Result<Vec<(bool, Bytes)>, MulticallError>
+where
+    S: Router<S> + TopLevelStorage + StorageType + BorrowMut<S::Storage>,
+{
+    let calls = calls
+        .into_iter()
+        .map(|(target, data)| (target, !require_success, data));
+    batch(storage, calls)
+}
+
+/// Makes each call, as Multicall3's `aggregate3`, failing if any reverts unless it allows
+/// failure. Returns whether each call succeeded, along with its result or revert data.
+pub fn aggregate3<S>(
+    storage: &mut S,
+    calls: Vec<(Address, bool, Bytes)>,
+) -> Result<Vec<(bool, Bytes)>, MulticallError>
+where
+    S: Router<S> + TopLevelStorage + StorageType + BorrowMut<S::Storage>,
+{
+    batch(storage, calls)
+}
+
+/// Makes each call, given its target, whether it may fail, and calldata.
+fn batch<S>(
+    storage: &mut S,
+    calls: impl IntoIterator<Item = (Address, bool, Bytes)>,
+) -> Result<Vec<(bool, Bytes)>, MulticallError>
+where
+    S: Router<S> + TopLevelStorage + StorageType + BorrowMut<S::Storage>,
+{
+    let calls: Vec<_> = calls.into_iter().collect();
+
+    // reject every call before making any, so that none run on the contract's behalf
+    let this = contract::address();
+    if let Some(index) = calls.iter().position(|(target, ..)| *target != this) {
+        let err = UnsupportedTarget {
+            index: U256::from(index),
+            target: calls[index].0,
+        };
+        return Err(err.into());
+    }
+
+    let mut results = vec![];
+    for (index, (_, allow_failure, data)) in calls.into_iter().enumerate() {
+        let result = match allow_failure {
+            true => try_call(storage, &data),
+            false => call(storage, &data),
+        };
+        let success = result.is_ok();
+        let data = match (result, allow_failure) {
+            (Ok(data), _) => data,
+            (Err(data), true) => data,
+            (Err(data), false) => {
+                let index = U256::from(index);
+                let data = data.into();
+                return Err(CallFailed { index, data }.into());
+            }
+        };
+        results.push((success, data.into()));
+    }
+    Ok(results)
+}
+
+/// Routes a call to one of the contract's methods.
+fn call<S>(storage: &mut S, data: &[u8]) -> ArbResult
+where
+    S: Router<S> + TopLevelStorage + BorrowMut<S::Storage>,
+{
+    let Some(selector) = data.get(..4) else {
+        return Err(vec![]);
+    };
+    let selector = u32::from_be_bytes(selector.try_into().unwrap());
+    contract::call_self(storage, selector, &data[4..])
+}
+
+/// Routes a call that may fail, rolling back its storage writes if it does.
+#[cfg(feature = "storage-transaction")]
+fn try_call<S>(storage: &mut S, data: &[u8]) -> ArbResult
+where
+    S: Router<S> + TopLevelStorage + StorageType + BorrowMut<S::Storage>,
+{
+    let mut tx = crate::storage::StorageTransaction::begin(storage);
+    let result = call(&mut *tx, data);
+    match result {
+        Ok(_) => tx.commit(),
+        Err(_) => tx.rollback(),
+    }
+    result
+}
+
+/// Routes a call that may fail. Without transactions, its storage writes are kept if it does.
+#[cfg(not(feature = "storage-transaction"))]
+fn try_call<S>(storage: &mut S, data: &[u8]) -> ArbResult
+where
+    S: Router<S> + TopLevelStorage + BorrowMut<S::Storage>,
+{
+    call(storage, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abi::AbiType,
+        msg,
+        storage::{StorageAddress, StorageType, StorageU256},
+        stylus_proc::{public, storage},
+        test_host,
+    };
+    use alloy_sol_types::{SolError, SolType};
+
+    #[storage]
+    struct Contract {
+        total: StorageU256,
+        last_sender: StorageAddress,
+    }
+
+    unsafe impl TopLevelStorage for Contract {}
+
+    #[public]
+    impl Contract {
+        fn add(&mut self, amount: U256) -> U256 {
+            let total = self.total.get() + amount;
+            self.total.set(total);
+            self.last_sender.set(msg::sender());
+            total
+        }
+
+        fn fail(&self) -> Result<(), Vec<u8>> {
+            Err(b"failed".to_vec())
+        }
+
+        fn add_then_fail(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+            self.add(amount);
+            Err(b"failed".to_vec())
+        }
+
+        #[payable]
+        fn deposit(&mut self) -> U256 {
+            self.add(msg::value())
+        }
+
+        fn aggregate(
+            &mut self,
+            calls: Vec<(Address, Bytes)>,
+        ) -> Result<(U256, Vec<Bytes>), MulticallError> {
+            aggregate(self, calls)
+        }
+
+        fn try_aggregate(
+            &mut self,
+            require_success: bool,
+            calls: Vec<(Address, Bytes)>,
+        ) -> Result<Vec<(bool, Bytes)>, MulticallError> {
+            try_aggregate(self, require_success, calls)
+        }
+
+        fn aggregate3(
+            &mut self,
+            calls: Vec<(Address, bool, Bytes)>,
+        ) -> Result<Vec<(bool, Bytes)>, MulticallError> {
+            aggregate3(self, calls)
+        }
+    }
+
+    fn add(amount: u64) -> Bytes {
+        let mut call = function_selector!("add", U256).to_vec();
+        call.extend(<U256 as AbiType>::SolType::abi_encode(&U256::from(amount)));
+        call.into()
+    }
+
+    fn fail() -> Bytes {
+        function_selector!("fail").to_vec().into()
+    }
+
+    fn add_then_fail(amount: u64) -> Bytes {
+        let mut call = function_selector!("addThenFail", U256).to_vec();
+        call.extend(<U256 as AbiType>::SolType::abi_encode(&U256::from(amount)));
+        call.into()
+    }
+
+    fn deposit() -> Bytes {
+        function_selector!("deposit").to_vec().into()
+    }
+
+    #[test]
+    fn test_multicall() {
+        let mut contract = unsafe { Contract::new(U256::ZERO, 0) };
+        let this = contract::address();
+        let sender = Address::repeat_byte(0x5e);
+        test_host::set_sender(sender);
+
+        // calls are routed internally, keeping the caller
+        let calls = vec![(this, add(2)), (this, add(3))];
+        let (block, results) = contract.aggregate(calls).ok().unwrap();
+        assert_eq!(block, U256::from(block::number()));
+        let words: Vec<_> = results.iter().map(|r| U256::from_be_slice(r)).collect();
+        assert_eq!(words, [U256::from(2), U256::from(5)]);
+        assert_eq!(contract.last_sender.get(), sender);
+        assert!(test_host::calls().is_empty());
+
+        // failures are reported or tolerated as requested
+        let calls = vec![
+            (this, add(1)),
+            (this, fail()),
+            (this, Bytes::from_vec(vec![])),
+        ];
+        let Err(MulticallError::CallFailed(err)) = contract.try_aggregate(true, calls.clone())
+        else {
+            panic!("the call must succeed");
+        };
+        assert_eq!((err.index, &err.data[..]), (U256::from(1), &b"failed"[..]));
+        let results = contract.try_aggregate(false, calls).ok().unwrap();
+        let success: Vec<_> = results.iter().map(|(success, _)| *success).collect();
+        assert_eq!(success, [true, false, false]);
+        assert_eq!(*results[1].1, b"failed"[..]);
+
+        let calls = vec![(this, true, fail()), (this, false, add(4))];
+        let results = contract.aggregate3(calls).ok().unwrap();
+        assert_eq!(U256::from_be_slice(&results[1].1), U256::from(11));
+
+        let encoded: Vec<u8> = MulticallError::from(CallFailed {
+            index: U256::ZERO,
+            data: Default::default(),
+        })
+        .into();
+        assert_eq!(encoded[..4], CallFailed::SELECTOR);
+    }
+
+    #[test]
+    fn test_failed_call_writes() {
+        let mut contract = unsafe { Contract::new(U256::ZERO, 0) };
+        let this = contract::address();
+
+        let calls = vec![(this, true, add(1)), (this, true, add_then_fail(2))];
+        let results = contract.aggregate3(calls).ok().unwrap();
+        assert_eq!((results[0].0, results[1].0), (true, false));
+
+        // only transactions can undo the writes of a call that reverts within the multicall
+        let total = contract.total.get();
+        #[cfg(feature = "storage-transaction")]
+        assert_eq!(total, U256::from(1));
+        #[cfg(not(feature = "storage-transaction"))]
+        assert_eq!(total, U256::from(3));
+
+        let calls = vec![(this, add_then_fail(4))];
+        let results = contract.try_aggregate(false, calls).ok().unwrap();
+        assert!(!results[0].0);
+        #[cfg(feature = "storage-transaction")]
+        assert_eq!(contract.total.get(), total);
+        #[cfg(not(feature = "storage-transaction"))]
+        assert_eq!(contract.total.get(), total + U256::from(4));
+    }
+
+    #[test]
+    fn test_value() {
+        let mut contract = unsafe { Contract::new(U256::ZERO, 0) };
+        let this = contract::address();
+        test_host::set_value(U256::from(100));
+
+        // each call sees the value sent to the multicall
+        let calls = vec![(this, false, deposit()), (this, false, deposit())];
+        let results = contract.aggregate3(calls.clone()).ok().unwrap();
+        assert_eq!(U256::from_be_slice(&results[1].1), U256::from(200));
+
+        // which is why the multicall must not be payable
+        let selector = function_selector!("aggregate3", Vec<(Address, bool, Bytes)>);
+        let args = <Vec<(Address, bool, Bytes)> as AbiType>::SolType::abi_encode_params(&calls);
+        let result = contract::call_self(&mut contract, u32::from_be_bytes(selector), &args);
+        assert!(result.is_err());
+        assert_eq!(contract.total.get(), U256::from(200));
+    }
+
+    #[test]
+    fn test_external_targets() {
+        let mut contract = unsafe { Contract::new(U256::ZERO, 0) };
+        let this = contract::address();
+        let token = Address::repeat_byte(0x20);
+        test_host::mock_call(token, Ok(vec![]));
+
+        // no call is made, not even to the contract, if any targets another address
+        let calls = vec![(this, true, add(1)), (token, true, fail())];
+        let Err(MulticallError::UnsupportedTarget(err)) = contract.aggregate3(calls) else {
+            panic!("external targets must be rejected");
+        };
+        assert_eq!((err.index, err.target), (U256::from(1), token));
+        assert!(test_host::calls().is_empty());
+        assert_eq!(contract.total.get(), U256::ZERO);
+
+        let calls = vec![(token, Bytes::from_vec(vec![]))];
+        let result = contract.try_aggregate(false, calls);
+        assert!(matches!(result, Err(MulticallError::UnsupportedTarget(_))));
+    }
+
+    #[test]
+    fn test_multicall3_selectors() {
+        let selectors = [
+            function_selector!("aggregate", Vec<(Address, Bytes)>),
+            function_selector!("tryAggregate", bool, Vec<(Address, Bytes)>),
+            function_selector!("aggregate3", Vec<(Address, bool, Bytes)>),
+        ];
+        assert_eq!(
+            selectors,
+            [
+                [0x25, 0x2d, 0xba, 0x42],
+                [0xbc, 0xe3, 0x8b, 0xd7],
+                [0x82, 0xad, 0x56, 0xcb]
+            ]
+        );
+        for selector in selectors {
+            let allow = Contract::__stylus_allow_reentrant(u32::from_be_bytes(selector));
+            assert!(allow.is_some());
+        }
+    }
+}